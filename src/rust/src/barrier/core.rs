@@ -0,0 +1,171 @@
+use crate::{
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    percpu::can_preempt_in_scheduler,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        sched::{schedule, schedule_reschedule},
+        sync::wait::{task_wait, task_wait_interrupted, task_wake},
+        types::{TaskCB, TaskPendObject, TaskStatus},
+    },
+    utils::list::LinkedList,
+};
+
+use super::{
+    error::BarrierError,
+    global::{BarrierManager, LatchManager},
+    types::{BarrierCallback, BarrierId},
+};
+
+/// 唤醒等待列表上的所有任务，返回是否唤醒了任务
+pub(super) fn wake_all(list: &mut LinkedList) -> bool {
+    let woken = !LinkedList::is_empty(list);
+    while !LinkedList::is_empty(list) {
+        let resumed_task = TaskCB::from_pend_list(list.next);
+        task_wake(resumed_task);
+    }
+    woken
+}
+
+/// 在等待列表上阻塞当前任务，须在关中断后调用，被唤醒后仍处于关中断状态
+pub(super) fn pend_operation(
+    list: &mut LinkedList,
+    pend_object: TaskPendObject,
+    timeout: u32,
+    int_save: &mut u32,
+) -> SystemResult<()> {
+    let run_task = get_current_task();
+    task_wait(list, pend_object, timeout);
+
+    // 立即调度
+    schedule_reschedule();
+
+    // 解锁并重新加锁
+    restore_interrupt_state(*int_save);
+
+    *int_save = disable_interrupts();
+
+    if task_wait_interrupted(run_task) {
+        return Err(SystemError::Task(TaskError::Interrupted));
+    }
+    if run_task.task_status.contains(TaskStatus::TIMEOUT) {
+        run_task.task_status.remove(TaskStatus::TIMEOUT);
+        return Err(BarrierError::Timeout.into());
+    }
+    Ok(())
+}
+
+/// 检查当前上下文能否等待
+pub(super) fn check_pend(timeout: u32) -> SystemResult<()> {
+    if is_interrupt_active() {
+        return Err(BarrierError::PendInterrupt.into());
+    }
+    if timeout == 0 {
+        return Err(BarrierError::Unavailable.into());
+    }
+    if !can_preempt_in_scheduler() {
+        return Err(BarrierError::PendInLock.into());
+    }
+    Ok(())
+}
+
+/// 屏障与倒计数锁存器系统初始化
+pub fn barrier_init() {
+    BarrierManager::initialize();
+    LatchManager::initialize();
+}
+
+/// 创建屏障，每轮`parties`个任务到达后一起放行
+///
+/// 每轮最后到达的任务在放行其他任务前以本轮的代数调用`callback`，回调在关中断的状态下执行，
+/// 不能阻塞。
+pub fn barrier_create(parties: u32, callback: BarrierCallback) -> SystemResult<BarrierId> {
+    if parties == 0 {
+        return Err(BarrierError::PartiesInvalid.into());
+    }
+
+    let int_save = disable_interrupts();
+
+    if !BarrierManager::has_available_barrier() {
+        restore_interrupt_state(int_save);
+        return Err(BarrierError::AllBusy.into());
+    }
+    let id = BarrierManager::allocate(parties, callback);
+
+    restore_interrupt_state(int_save);
+    Ok(id)
+}
+
+/// 删除屏障
+pub fn barrier_delete(id: BarrierId) -> SystemResult<()> {
+    let barrier = BarrierManager::get_barrier_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = BarrierManager::deallocate(barrier, id);
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 到达屏障并等待本轮的其他任务，本轮最后到达时返回`true`
+///
+/// 本轮已到达的任务数取自等待列表，超时或被信号打断的任务离开等待列表时即退出本轮，不计入到达的任务数。
+pub fn barrier_wait(id: BarrierId, timeout: u32) -> SystemResult<bool> {
+    let barrier = BarrierManager::get_barrier_mut(id)?;
+
+    let mut int_save = disable_interrupts();
+
+    if barrier.is_unused() || !barrier.matches_id(id) {
+        restore_interrupt_state(int_save);
+        return Err(BarrierError::Invalid.into());
+    }
+
+    // 本轮最后到达，执行回调后放行所有任务
+    if barrier.waiting_count() + 1 == barrier.parties {
+        if let Some(callback) = barrier.callback {
+            callback(barrier.generation);
+        }
+        barrier.generation = barrier.generation.wrapping_add(1);
+        let woken = wake_all(&mut barrier.wait_list);
+
+        restore_interrupt_state(int_save);
+
+        if woken {
+            schedule();
+        }
+        return Ok(true);
+    }
+
+    if let Err(e) = check_pend(timeout) {
+        restore_interrupt_state(int_save);
+        return Err(e);
+    }
+
+    let result = pend_operation(
+        &mut barrier.wait_list,
+        TaskPendObject::Barrier(id.into()),
+        timeout,
+        &mut int_save,
+    );
+
+    restore_interrupt_state(int_save);
+    result.map(|()| false)
+}
+
+/// 获取屏障已完成的轮数
+pub fn barrier_generation_get(id: BarrierId) -> SystemResult<u32> {
+    let barrier = BarrierManager::get_barrier_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = if barrier.is_unused() || !barrier.matches_id(id) {
+        Err(BarrierError::Invalid.into())
+    } else {
+        Ok(barrier.generation)
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
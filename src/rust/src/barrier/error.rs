@@ -0,0 +1,87 @@
+//! 屏障与倒计数锁存器错误码定义
+
+/// 屏障与倒计数锁存器操作错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BarrierError {
+    /// 句柄无效
+    Invalid,
+    /// 指针为空
+    PtrNull,
+    /// 所有屏障或锁存器都在使用中
+    AllBusy,
+    /// 屏障的参与任务数为0
+    PartiesInvalid,
+    /// 条件未满足且不等待
+    Unavailable,
+    /// 在中断中等待
+    PendInterrupt,
+    /// 在调度锁定状态下等待
+    PendInLock,
+    /// 等待超时
+    Timeout,
+    /// 有任务在等待，无法删除
+    Pended,
+}
+
+impl From<BarrierError> for u32 {
+    fn from(err: BarrierError) -> u32 {
+        match err {
+            BarrierError::Invalid => ERRNO_BARRIER_INVALID,
+            BarrierError::PtrNull => ERRNO_BARRIER_PTR_NULL,
+            BarrierError::AllBusy => ERRNO_BARRIER_ALL_BUSY,
+            BarrierError::PartiesInvalid => ERRNO_BARRIER_PARTIES_INVALID,
+            BarrierError::Unavailable => ERRNO_BARRIER_UNAVAILABLE,
+            BarrierError::PendInterrupt => ERRNO_BARRIER_PEND_INTERR,
+            BarrierError::PendInLock => ERRNO_BARRIER_PEND_IN_LOCK,
+            BarrierError::Timeout => ERRNO_BARRIER_TIMEOUT,
+            BarrierError::Pended => ERRNO_BARRIER_PENDED,
+        }
+    }
+}
+
+impl TryFrom<u32> for BarrierError {
+    type Error = ();
+
+    fn try_from(errno: u32) -> Result<Self, Self::Error> {
+        match errno {
+            ERRNO_BARRIER_INVALID => Ok(BarrierError::Invalid),
+            ERRNO_BARRIER_PTR_NULL => Ok(BarrierError::PtrNull),
+            ERRNO_BARRIER_ALL_BUSY => Ok(BarrierError::AllBusy),
+            ERRNO_BARRIER_PARTIES_INVALID => Ok(BarrierError::PartiesInvalid),
+            ERRNO_BARRIER_UNAVAILABLE => Ok(BarrierError::Unavailable),
+            ERRNO_BARRIER_PEND_INTERR => Ok(BarrierError::PendInterrupt),
+            ERRNO_BARRIER_PEND_IN_LOCK => Ok(BarrierError::PendInLock),
+            ERRNO_BARRIER_TIMEOUT => Ok(BarrierError::Timeout),
+            ERRNO_BARRIER_PENDED => Ok(BarrierError::Pended),
+            _ => Err(()),
+        }
+    }
+}
+
+const ERRNO_BARRIER_INVALID: u32 = 0x02002101;
+const ERRNO_BARRIER_PTR_NULL: u32 = 0x02002102;
+const ERRNO_BARRIER_ALL_BUSY: u32 = 0x02002103;
+const ERRNO_BARRIER_PARTIES_INVALID: u32 = 0x02002104;
+const ERRNO_BARRIER_UNAVAILABLE: u32 = 0x02002105;
+const ERRNO_BARRIER_PEND_INTERR: u32 = 0x02002106;
+const ERRNO_BARRIER_PEND_IN_LOCK: u32 = 0x02002107;
+const ERRNO_BARRIER_TIMEOUT: u32 = 0x02002108;
+const ERRNO_BARRIER_PENDED: u32 = 0x02002109;
+
+impl core::fmt::Display for BarrierError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let desc = match self {
+            Self::Invalid => "Invalid barrier or latch handle",
+            Self::PtrNull => "Barrier or latch pointer is null",
+            Self::AllBusy => "All barriers or latches are busy",
+            Self::PartiesInvalid => "Barrier parties is zero",
+            Self::Unavailable => "Barrier or latch is not released",
+            Self::PendInterrupt => "Cannot wait for barrier or latch in interrupt context",
+            Self::PendInLock => "Cannot wait for barrier or latch in scheduler locked state",
+            Self::Timeout => "Barrier or latch wait timeout",
+            Self::Pended => "Barrier or latch is pended and cannot be deleted",
+        };
+        write!(f, "{}", desc)
+    }
+}
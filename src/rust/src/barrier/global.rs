@@ -0,0 +1,139 @@
+//! 屏障与倒计数锁存器全局变量
+use crate::config::{BARRIER_LIMIT, LATCH_LIMIT};
+use crate::result::SystemResult;
+use crate::utils::list::LinkedList;
+
+use super::error::BarrierError;
+use super::types::{BarrierCallback, BarrierControlBlock, BarrierId, LatchControlBlock, LatchId};
+
+pub static mut BARRIER_POOL: [BarrierControlBlock; BARRIER_LIMIT as usize] =
+    [BarrierControlBlock::UNINIT; BARRIER_LIMIT as usize];
+
+pub static mut UNUSED_BARRIER_LIST: LinkedList = LinkedList::new();
+
+pub static mut LATCH_POOL: [LatchControlBlock; LATCH_LIMIT as usize] =
+    [LatchControlBlock::UNINIT; LATCH_LIMIT as usize];
+
+pub static mut UNUSED_LATCH_LIST: LinkedList = LinkedList::new();
+
+pub struct BarrierManager;
+
+impl BarrierManager {
+    /// 初始化屏障池
+    #[inline]
+    pub fn initialize() {
+        LinkedList::init(&raw mut UNUSED_BARRIER_LIST);
+        for id in 0..BARRIER_LIMIT {
+            let barrier = Self::get_barrier_by_id(id);
+            barrier.id = id.into();
+            LinkedList::tail_insert(&raw mut UNUSED_BARRIER_LIST, &raw mut barrier.wait_list);
+        }
+    }
+
+    /// 检查是否有可用的屏障
+    #[inline]
+    pub fn has_available_barrier() -> bool {
+        !LinkedList::is_empty(&raw const UNUSED_BARRIER_LIST)
+    }
+
+    // 通过索引获取屏障
+    #[inline]
+    fn get_barrier_by_id(id: u32) -> &'static mut BarrierControlBlock {
+        unsafe { &mut BARRIER_POOL[id as usize] }
+    }
+
+    /// 分配一个新的屏障
+    #[inline]
+    pub fn allocate(parties: u32, callback: BarrierCallback) -> BarrierId {
+        let node = LinkedList::first(&raw const UNUSED_BARRIER_LIST);
+        LinkedList::remove(node);
+        let barrier = BarrierControlBlock::from_wait_list(node);
+        barrier.initialize(parties, callback);
+        barrier.get_id()
+    }
+
+    /// 释放屏障
+    #[inline]
+    pub fn deallocate(barrier: &mut BarrierControlBlock, id: BarrierId) -> SystemResult<()> {
+        if !barrier.matches_id(id) || barrier.is_unused() {
+            return Err(BarrierError::Invalid.into());
+        }
+        if barrier.has_waiting_tasks() {
+            return Err(BarrierError::Pended.into());
+        }
+        barrier.reset();
+        LinkedList::tail_insert(&raw mut UNUSED_BARRIER_LIST, &raw mut barrier.wait_list);
+        Ok(())
+    }
+
+    /// 获取屏障
+    #[inline]
+    pub fn get_barrier_mut(id: BarrierId) -> SystemResult<&'static mut BarrierControlBlock> {
+        let index = id.get_index() as u32;
+        if index >= BARRIER_LIMIT {
+            return Err(BarrierError::Invalid.into());
+        }
+        Ok(Self::get_barrier_by_id(index))
+    }
+}
+
+pub struct LatchManager;
+
+impl LatchManager {
+    /// 初始化锁存器池
+    #[inline]
+    pub fn initialize() {
+        LinkedList::init(&raw mut UNUSED_LATCH_LIST);
+        for id in 0..LATCH_LIMIT {
+            let latch = Self::get_latch_by_id(id);
+            latch.id = id.into();
+            LinkedList::tail_insert(&raw mut UNUSED_LATCH_LIST, &raw mut latch.wait_list);
+        }
+    }
+
+    /// 检查是否有可用的锁存器
+    #[inline]
+    pub fn has_available_latch() -> bool {
+        !LinkedList::is_empty(&raw const UNUSED_LATCH_LIST)
+    }
+
+    // 通过索引获取锁存器
+    #[inline]
+    fn get_latch_by_id(id: u32) -> &'static mut LatchControlBlock {
+        unsafe { &mut LATCH_POOL[id as usize] }
+    }
+
+    /// 分配一个新的锁存器
+    #[inline]
+    pub fn allocate(count: u32) -> LatchId {
+        let node = LinkedList::first(&raw const UNUSED_LATCH_LIST);
+        LinkedList::remove(node);
+        let latch = LatchControlBlock::from_wait_list(node);
+        latch.initialize(count);
+        latch.get_id()
+    }
+
+    /// 释放锁存器
+    #[inline]
+    pub fn deallocate(latch: &mut LatchControlBlock, id: LatchId) -> SystemResult<()> {
+        if !latch.matches_id(id) || latch.is_unused() {
+            return Err(BarrierError::Invalid.into());
+        }
+        if latch.has_waiting_tasks() {
+            return Err(BarrierError::Pended.into());
+        }
+        latch.reset();
+        LinkedList::tail_insert(&raw mut UNUSED_LATCH_LIST, &raw mut latch.wait_list);
+        Ok(())
+    }
+
+    /// 获取锁存器
+    #[inline]
+    pub fn get_latch_mut(id: LatchId) -> SystemResult<&'static mut LatchControlBlock> {
+        let index = id.get_index() as u32;
+        if index >= LATCH_LIMIT {
+            return Err(BarrierError::Invalid.into());
+        }
+        Ok(Self::get_latch_by_id(index))
+    }
+}
@@ -0,0 +1,114 @@
+//! 倒计数锁存器
+
+use crate::{
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    result::SystemResult,
+    task::{sched::schedule, types::TaskPendObject},
+};
+
+use super::{
+    core::{check_pend, pend_operation, wake_all},
+    error::BarrierError,
+    global::LatchManager,
+    types::LatchId,
+};
+
+/// 创建倒计数锁存器，计数为0时锁存器一开始就处于打开状态
+pub fn latch_create(count: u32) -> SystemResult<LatchId> {
+    let int_save = disable_interrupts();
+
+    if !LatchManager::has_available_latch() {
+        restore_interrupt_state(int_save);
+        return Err(BarrierError::AllBusy.into());
+    }
+    let id = LatchManager::allocate(count);
+
+    restore_interrupt_state(int_save);
+    Ok(id)
+}
+
+/// 删除倒计数锁存器
+pub fn latch_delete(id: LatchId) -> SystemResult<()> {
+    let latch = LatchManager::get_latch_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = LatchManager::deallocate(latch, id);
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 计数减一，减到0时唤醒所有等待者，可在中断中调用
+///
+/// 计数已为0时不做任何操作。
+pub fn latch_count_down(id: LatchId) -> SystemResult<()> {
+    let latch = LatchManager::get_latch_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    if latch.is_unused() || !latch.matches_id(id) {
+        restore_interrupt_state(int_save);
+        return Err(BarrierError::Invalid.into());
+    }
+
+    let mut woken = false;
+    if latch.count > 0 {
+        latch.count -= 1;
+        if latch.count == 0 {
+            woken = wake_all(&mut latch.wait_list);
+        }
+    }
+
+    restore_interrupt_state(int_save);
+
+    if woken {
+        schedule();
+    }
+
+    Ok(())
+}
+
+/// 等待计数减到0
+pub fn latch_wait(id: LatchId, timeout: u32) -> SystemResult<()> {
+    let latch = LatchManager::get_latch_mut(id)?;
+
+    let mut int_save = disable_interrupts();
+
+    if latch.is_unused() || !latch.matches_id(id) {
+        restore_interrupt_state(int_save);
+        return Err(BarrierError::Invalid.into());
+    }
+
+    let result = if latch.count == 0 {
+        Ok(())
+    } else {
+        check_pend(timeout).and_then(|()| {
+            pend_operation(
+                &mut latch.wait_list,
+                TaskPendObject::Latch(id.into()),
+                timeout,
+                &mut int_save,
+            )
+        })
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 获取倒计数锁存器的剩余计数
+pub fn latch_count_get(id: LatchId) -> SystemResult<u32> {
+    let latch = LatchManager::get_latch_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = if latch.is_unused() || !latch.matches_id(id) {
+        Err(BarrierError::Invalid.into())
+    } else {
+        Ok(latch.count)
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
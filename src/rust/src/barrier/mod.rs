@@ -0,0 +1,5 @@
+pub mod core;
+pub mod error;
+pub mod global;
+pub mod latch;
+pub mod types;
@@ -0,0 +1,256 @@
+//! 屏障与倒计数锁存器相关类型定义
+
+use crate::{container_of, utils::list::LinkedList};
+
+/// 屏障每轮最后到达的任务执行的回调，参数为本轮的代数
+pub type BarrierCallback = Option<extern "C" fn(u32)>;
+
+/// 屏障与锁存器状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SyncState {
+    Unused = 0,
+    Used = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct BarrierId(pub u32);
+
+impl BarrierId {
+    const BARRIER_SPLIT_BIT: u32 = 16;
+
+    pub fn new(count: u16, index: u16) -> Self {
+        let id = ((count as u32) << Self::BARRIER_SPLIT_BIT) | (index as u32);
+        Self(id)
+    }
+
+    /// 获取屏障索引部分
+    pub fn get_index(self) -> u16 {
+        (self.0 & ((1 << Self::BARRIER_SPLIT_BIT) - 1)) as u16
+    }
+
+    /// 获取屏障计数部分
+    pub fn get_count(self) -> u16 {
+        (self.0 >> Self::BARRIER_SPLIT_BIT) as u16
+    }
+
+    /// 增加计数值生成新ID，保持索引不变
+    pub fn increment_count(&self) -> Self {
+        Self::new(self.get_count().wrapping_add(1), self.get_index())
+    }
+}
+
+impl From<u32> for BarrierId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<BarrierId> for u32 {
+    fn from(id: BarrierId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct LatchId(pub u32);
+
+impl LatchId {
+    const LATCH_SPLIT_BIT: u32 = 16;
+
+    pub fn new(count: u16, index: u16) -> Self {
+        let id = ((count as u32) << Self::LATCH_SPLIT_BIT) | (index as u32);
+        Self(id)
+    }
+
+    /// 获取锁存器索引部分
+    pub fn get_index(self) -> u16 {
+        (self.0 & ((1 << Self::LATCH_SPLIT_BIT) - 1)) as u16
+    }
+
+    /// 获取锁存器计数部分
+    pub fn get_count(self) -> u16 {
+        (self.0 >> Self::LATCH_SPLIT_BIT) as u16
+    }
+
+    /// 增加计数值生成新ID，保持索引不变
+    pub fn increment_count(&self) -> Self {
+        Self::new(self.get_count().wrapping_add(1), self.get_index())
+    }
+}
+
+impl From<u32> for LatchId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<LatchId> for u32 {
+    fn from(id: LatchId) -> Self {
+        id.0
+    }
+}
+
+/// 屏障控制块
+#[repr(C)]
+#[derive(Debug)]
+pub struct BarrierControlBlock {
+    /// 已到达并等待的任务列表，未使用时挂入空闲链表
+    pub wait_list: LinkedList,
+
+    /// 每轮需要到达的任务数
+    pub parties: u32,
+
+    /// 已完成的轮数
+    pub generation: u32,
+
+    /// 最后到达的任务执行的回调
+    pub callback: BarrierCallback,
+
+    /// 屏障状态
+    pub state: SyncState,
+
+    /// 屏障ID
+    pub id: BarrierId,
+}
+
+impl BarrierControlBlock {
+    pub const UNINIT: Self = Self {
+        wait_list: LinkedList::new(),
+        parties: 0,
+        generation: 0,
+        callback: None,
+        state: SyncState::Unused,
+        id: BarrierId(0),
+    };
+
+    pub fn is_unused(&self) -> bool {
+        self.state == SyncState::Unused
+    }
+
+    /// 获取屏障ID
+    pub fn get_id(&self) -> BarrierId {
+        self.id
+    }
+
+    /// 检查是否为指定的句柄
+    pub fn matches_id(&self, id: BarrierId) -> bool {
+        self.id == id
+    }
+
+    /// 检查是否有等待的任务
+    pub fn has_waiting_tasks(&self) -> bool {
+        !LinkedList::is_empty(&raw const self.wait_list)
+    }
+
+    /// 本轮已到达并仍在等待的任务数，不超过`parties - 1`
+    pub fn waiting_count(&self) -> u32 {
+        let head = &raw const self.wait_list;
+        let mut count = 0;
+        let mut cur = self.wait_list.next as *const LinkedList;
+        while cur != head {
+            count += 1;
+            cur = unsafe { (*cur).next };
+        }
+        count
+    }
+
+    /// 初始化屏障
+    pub fn initialize(&mut self, parties: u32, callback: BarrierCallback) {
+        LinkedList::init(&raw mut self.wait_list);
+        self.parties = parties;
+        self.generation = 0;
+        self.callback = callback;
+        self.state = SyncState::Used;
+    }
+
+    /// 重置屏障
+    pub fn reset(&mut self) {
+        self.callback = None;
+        self.state = SyncState::Unused;
+        self.id = self.id.increment_count();
+    }
+
+    pub fn from_wait_list(ptr: *mut LinkedList) -> &'static mut BarrierControlBlock {
+        let barrier_ptr = container_of!(ptr, BarrierControlBlock, wait_list);
+        unsafe { &mut *barrier_ptr }
+    }
+}
+
+impl Default for BarrierControlBlock {
+    fn default() -> Self {
+        Self::UNINIT
+    }
+}
+
+/// 倒计数锁存器控制块
+#[repr(C)]
+#[derive(Debug)]
+pub struct LatchControlBlock {
+    /// 等待计数归零的任务列表，未使用时挂入空闲链表
+    pub wait_list: LinkedList,
+
+    /// 剩余计数
+    pub count: u32,
+
+    /// 锁存器状态
+    pub state: SyncState,
+
+    /// 锁存器ID
+    pub id: LatchId,
+}
+
+impl LatchControlBlock {
+    pub const UNINIT: Self = Self {
+        wait_list: LinkedList::new(),
+        count: 0,
+        state: SyncState::Unused,
+        id: LatchId(0),
+    };
+
+    pub fn is_unused(&self) -> bool {
+        self.state == SyncState::Unused
+    }
+
+    /// 获取锁存器ID
+    pub fn get_id(&self) -> LatchId {
+        self.id
+    }
+
+    /// 检查是否为指定的句柄
+    pub fn matches_id(&self, id: LatchId) -> bool {
+        self.id == id
+    }
+
+    /// 检查是否有等待的任务
+    pub fn has_waiting_tasks(&self) -> bool {
+        !LinkedList::is_empty(&raw const self.wait_list)
+    }
+
+    /// 初始化锁存器
+    pub fn initialize(&mut self, count: u32) {
+        LinkedList::init(&raw mut self.wait_list);
+        self.count = count;
+        self.state = SyncState::Used;
+    }
+
+    /// 重置锁存器
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.state = SyncState::Unused;
+        self.id = self.id.increment_count();
+    }
+
+    pub fn from_wait_list(ptr: *mut LinkedList) -> &'static mut LatchControlBlock {
+        let latch_ptr = container_of!(ptr, LatchControlBlock, wait_list);
+        unsafe { &mut *latch_ptr }
+    }
+}
+
+impl Default for LatchControlBlock {
+    fn default() -> Self {
+        Self::UNINIT
+    }
+}
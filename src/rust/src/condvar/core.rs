@@ -0,0 +1,209 @@
+use core::ptr::addr_of;
+
+use crate::{
+    config::WAIT_FOREVER,
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    mutex::{
+        core::{mutex_pend, mutex_release_all, mutex_restore_count},
+        error::MutexError,
+        types::MutexId,
+    },
+    percpu::can_preempt_in_scheduler,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        sched::{schedule, schedule_reschedule},
+        sync::wait::{task_wait, task_wait_interrupted, task_wake},
+        types::{TaskCB, TaskPendObject, TaskStatus},
+    },
+    utils::list::LinkedList,
+};
+
+use super::{
+    error::CondvarError,
+    global::CondvarManager,
+    types::{CondvarControlBlock, CondvarId},
+};
+
+/// 按优先级查找等待位置，同优先级先来先得
+fn find_wait_position<'a>(
+    run_task: &TaskCB,
+    condvar: &'a mut CondvarControlBlock,
+) -> &'a mut LinkedList {
+    let mut cur_task = TaskCB::from_pend_list(condvar.wait_list.next);
+    while addr_of!(cur_task.pend_list) != addr_of!(condvar.wait_list) {
+        if cur_task.priority > run_task.priority {
+            return &mut cur_task.pend_list;
+        }
+        cur_task = TaskCB::from_pend_list(cur_task.pend_list.next);
+    }
+    &mut condvar.wait_list
+}
+
+/// 释放互斥锁并加入等待队列，返回互斥锁的递归加锁次数
+fn pend_operation(
+    run_task: &mut TaskCB,
+    condvar: &mut CondvarControlBlock,
+    id: CondvarId,
+    mutex: MutexId,
+    timeout: u32,
+) -> SystemResult<u16> {
+    if condvar.is_unused() || !condvar.matches_id(id) {
+        return Err(CondvarError::Invalid.into());
+    }
+    if is_interrupt_active() {
+        return Err(CondvarError::PendInterrupt.into());
+    }
+    if !can_preempt_in_scheduler() {
+        return Err(CondvarError::PendInLock.into());
+    }
+    if condvar.has_waiting_tasks() && condvar.mutex != mutex {
+        return Err(CondvarError::MutexMismatch.into());
+    }
+
+    // 先释放互斥锁，按释放后的优先级排队
+    let count = mutex_release_all(run_task, mutex)?;
+    condvar.mutex = mutex;
+    let wait_pos = find_wait_position(run_task, condvar);
+    task_wait(wait_pos, TaskPendObject::Condvar(id.into()), timeout);
+    Ok(count)
+}
+
+/// 条件变量系统初始化
+pub fn condvar_init() {
+    CondvarManager::initialize();
+}
+
+/// 创建条件变量
+pub fn condvar_create() -> SystemResult<CondvarId> {
+    let int_save = disable_interrupts();
+
+    if !CondvarManager::has_available_condvar() {
+        restore_interrupt_state(int_save);
+        return Err(CondvarError::AllBusy.into());
+    }
+    let id = CondvarManager::allocate();
+
+    restore_interrupt_state(int_save);
+    Ok(id)
+}
+
+/// 删除条件变量
+pub fn condvar_delete(id: CondvarId) -> SystemResult<()> {
+    let condvar = CondvarManager::get_condvar_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = CondvarManager::deallocate(condvar, id);
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 等待条件变量
+pub fn condvar_wait(id: CondvarId, mutex: MutexId) -> SystemResult<()> {
+    condvar_timed_wait(id, mutex, WAIT_FOREVER)
+}
+
+/// 在指定的tick数内等待条件变量
+///
+/// 调用者须持有`mutex`，释放互斥锁与开始等待是原子的。被唤醒、超时或被信号打断后都按互斥锁
+/// 的优先级继承规则重新加锁并恢复递归加锁次数，之后才返回等待的结果。重新加锁时被信号打断或
+/// 持有者被删除都会继续加锁；从被删除的持有者手中接管健壮互斥锁时返回[`MutexError::OwnerDied`]，
+/// 此时仍持有互斥锁。互斥锁已被删除或不可恢复时返回[`CondvarError::RelockFailed`]，此时不持有互斥锁。
+///
+/// [`MutexError::OwnerDied`]: crate::mutex::error::MutexError::OwnerDied
+pub fn condvar_timed_wait(id: CondvarId, mutex: MutexId, timeout: u32) -> SystemResult<()> {
+    let condvar = CondvarManager::get_condvar_mut(id)?;
+
+    let mut int_save = disable_interrupts();
+
+    let run_task = get_current_task();
+
+    let count = match pend_operation(run_task, condvar, id, mutex, timeout) {
+        Ok(count) => count,
+        Err(e) => {
+            restore_interrupt_state(int_save);
+            return Err(e);
+        }
+    };
+
+    // 立即调度
+    schedule_reschedule();
+
+    // 解锁并重新加锁
+    restore_interrupt_state(int_save);
+
+    int_save = disable_interrupts();
+
+    let result = if task_wait_interrupted(run_task) {
+        Err(SystemError::Task(TaskError::Interrupted))
+    } else if run_task.task_status.contains(TaskStatus::TIMEOUT) {
+        run_task.task_status.remove(TaskStatus::TIMEOUT);
+        Err(CondvarError::Timeout.into())
+    } else {
+        Ok(())
+    };
+
+    restore_interrupt_state(int_save);
+
+    // 重新获取互斥锁
+    relock(mutex, count).and(result)
+}
+
+/// 等待结束后重新获取互斥锁并恢复递归加锁次数，直到持有互斥锁或互斥锁已无法获取
+fn relock(mutex: MutexId, count: u16) -> SystemResult<()> {
+    loop {
+        match mutex_pend(mutex, WAIT_FOREVER) {
+            Ok(()) => break,
+            Err(e @ SystemError::Mutex(MutexError::OwnerDied)) => {
+                mutex_restore_count(mutex, count);
+                return Err(e);
+            }
+            Err(SystemError::Task(TaskError::Interrupted | TaskError::OwnerDead)) => continue,
+            Err(_) => return Err(CondvarError::RelockFailed.into()),
+        }
+    }
+    mutex_restore_count(mutex, count);
+    Ok(())
+}
+
+/// 唤醒条件变量上的等待者，`all`为真时唤醒全部等待者
+fn wake_operation(id: CondvarId, all: bool) -> SystemResult<()> {
+    let condvar = CondvarManager::get_condvar_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    if condvar.is_unused() || !condvar.matches_id(id) {
+        restore_interrupt_state(int_save);
+        return Err(CondvarError::Invalid.into());
+    }
+
+    let woken = condvar.has_waiting_tasks();
+    while condvar.has_waiting_tasks() {
+        let resumed_task = TaskCB::from_pend_list(condvar.wait_list.next);
+        task_wake(resumed_task);
+        if !all {
+            break;
+        }
+    }
+
+    restore_interrupt_state(int_save);
+
+    if woken {
+        schedule();
+    }
+
+    Ok(())
+}
+
+/// 唤醒优先级最高的一个等待者
+pub fn condvar_signal(id: CondvarId) -> SystemResult<()> {
+    wake_operation(id, false)
+}
+
+/// 唤醒所有等待者
+pub fn condvar_broadcast(id: CondvarId) -> SystemResult<()> {
+    wake_operation(id, true)
+}
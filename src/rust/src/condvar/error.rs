@@ -0,0 +1,87 @@
+//! 条件变量错误码定义
+
+/// 条件变量操作错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CondvarError {
+    /// 条件变量句柄无效
+    Invalid,
+    /// 条件变量指针为空
+    PtrNull,
+    /// 所有条件变量都在使用中
+    AllBusy,
+    /// 在中断中等待条件变量
+    PendInterrupt,
+    /// 在调度锁定状态下等待条件变量
+    PendInLock,
+    /// 等待条件变量超时
+    Timeout,
+    /// 有任务在等待，无法删除
+    Pended,
+    /// 同时等待的任务使用了不同的互斥锁
+    MutexMismatch,
+    /// 等待结束后未能重新获取互斥锁，返回时不持有互斥锁
+    RelockFailed,
+}
+
+impl From<CondvarError> for u32 {
+    fn from(err: CondvarError) -> u32 {
+        match err {
+            CondvarError::Invalid => ERRNO_CONDVAR_INVALID,
+            CondvarError::PtrNull => ERRNO_CONDVAR_PTR_NULL,
+            CondvarError::AllBusy => ERRNO_CONDVAR_ALL_BUSY,
+            CondvarError::PendInterrupt => ERRNO_CONDVAR_PEND_INTERR,
+            CondvarError::PendInLock => ERRNO_CONDVAR_PEND_IN_LOCK,
+            CondvarError::Timeout => ERRNO_CONDVAR_TIMEOUT,
+            CondvarError::Pended => ERRNO_CONDVAR_PENDED,
+            CondvarError::MutexMismatch => ERRNO_CONDVAR_MUTEX_MISMATCH,
+            CondvarError::RelockFailed => ERRNO_CONDVAR_RELOCK_FAILED,
+        }
+    }
+}
+
+impl TryFrom<u32> for CondvarError {
+    type Error = ();
+
+    fn try_from(errno: u32) -> Result<Self, Self::Error> {
+        match errno {
+            ERRNO_CONDVAR_INVALID => Ok(CondvarError::Invalid),
+            ERRNO_CONDVAR_PTR_NULL => Ok(CondvarError::PtrNull),
+            ERRNO_CONDVAR_ALL_BUSY => Ok(CondvarError::AllBusy),
+            ERRNO_CONDVAR_PEND_INTERR => Ok(CondvarError::PendInterrupt),
+            ERRNO_CONDVAR_PEND_IN_LOCK => Ok(CondvarError::PendInLock),
+            ERRNO_CONDVAR_TIMEOUT => Ok(CondvarError::Timeout),
+            ERRNO_CONDVAR_PENDED => Ok(CondvarError::Pended),
+            ERRNO_CONDVAR_MUTEX_MISMATCH => Ok(CondvarError::MutexMismatch),
+            ERRNO_CONDVAR_RELOCK_FAILED => Ok(CondvarError::RelockFailed),
+            _ => Err(()),
+        }
+    }
+}
+
+const ERRNO_CONDVAR_INVALID: u32 = 0x02002001;
+const ERRNO_CONDVAR_PTR_NULL: u32 = 0x02002002;
+const ERRNO_CONDVAR_ALL_BUSY: u32 = 0x02002003;
+const ERRNO_CONDVAR_PEND_INTERR: u32 = 0x02002004;
+const ERRNO_CONDVAR_PEND_IN_LOCK: u32 = 0x02002005;
+const ERRNO_CONDVAR_TIMEOUT: u32 = 0x02002006;
+const ERRNO_CONDVAR_PENDED: u32 = 0x02002007;
+const ERRNO_CONDVAR_MUTEX_MISMATCH: u32 = 0x02002008;
+const ERRNO_CONDVAR_RELOCK_FAILED: u32 = 0x02002009;
+
+impl core::fmt::Display for CondvarError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let desc = match self {
+            Self::Invalid => "Invalid condition variable handle",
+            Self::PtrNull => "Condition variable pointer is null",
+            Self::AllBusy => "All condition variables are busy",
+            Self::PendInterrupt => "Cannot wait for condition variable in interrupt context",
+            Self::PendInLock => "Cannot wait for condition variable in scheduler locked state",
+            Self::Timeout => "Condition variable wait timeout",
+            Self::Pended => "Condition variable is pended and cannot be deleted",
+            Self::MutexMismatch => "Concurrent waiters use different mutexes",
+            Self::RelockFailed => "Failed to reacquire the mutex after waiting",
+        };
+        write!(f, "{}", desc)
+    }
+}
@@ -0,0 +1,73 @@
+//! 条件变量全局变量
+use crate::config::CONDVAR_LIMIT;
+use crate::result::SystemResult;
+use crate::utils::list::LinkedList;
+
+use super::error::CondvarError;
+use super::types::{CondvarControlBlock, CondvarId};
+
+pub static mut CONDVAR_POOL: [CondvarControlBlock; CONDVAR_LIMIT as usize] =
+    [CondvarControlBlock::UNINIT; CONDVAR_LIMIT as usize];
+
+pub static mut UNUSED_CONDVAR_LIST: LinkedList = LinkedList::new();
+
+pub struct CondvarManager;
+
+impl CondvarManager {
+    /// 初始化条件变量池
+    #[inline]
+    pub fn initialize() {
+        LinkedList::init(&raw mut UNUSED_CONDVAR_LIST);
+        for id in 0..CONDVAR_LIMIT {
+            let condvar = Self::get_condvar_by_id(id);
+            condvar.id = id.into();
+            LinkedList::tail_insert(&raw mut UNUSED_CONDVAR_LIST, &raw mut condvar.wait_list);
+        }
+    }
+
+    /// 检查是否有可用的条件变量
+    #[inline]
+    pub fn has_available_condvar() -> bool {
+        !LinkedList::is_empty(&raw const UNUSED_CONDVAR_LIST)
+    }
+
+    // 通过索引获取条件变量
+    #[inline]
+    fn get_condvar_by_id(id: u32) -> &'static mut CondvarControlBlock {
+        unsafe { &mut CONDVAR_POOL[id as usize] }
+    }
+
+    /// 分配一个新的条件变量
+    #[inline]
+    pub fn allocate() -> CondvarId {
+        let node = LinkedList::first(&raw const UNUSED_CONDVAR_LIST);
+        LinkedList::remove(node);
+        let condvar = CondvarControlBlock::from_wait_list(node);
+        condvar.initialize();
+        condvar.get_id()
+    }
+
+    /// 释放条件变量
+    #[inline]
+    pub fn deallocate(condvar: &mut CondvarControlBlock, id: CondvarId) -> SystemResult<()> {
+        if !condvar.matches_id(id) || condvar.is_unused() {
+            return Err(CondvarError::Invalid.into());
+        }
+        if condvar.has_waiting_tasks() {
+            return Err(CondvarError::Pended.into());
+        }
+        condvar.reset();
+        LinkedList::tail_insert(&raw mut UNUSED_CONDVAR_LIST, &raw mut condvar.wait_list);
+        Ok(())
+    }
+
+    /// 获取条件变量
+    #[inline]
+    pub fn get_condvar_mut(id: CondvarId) -> SystemResult<&'static mut CondvarControlBlock> {
+        let index = id.get_index() as u32;
+        if index >= CONDVAR_LIMIT {
+            return Err(CondvarError::Invalid.into());
+        }
+        Ok(Self::get_condvar_by_id(index))
+    }
+}
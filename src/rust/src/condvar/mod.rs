@@ -0,0 +1,4 @@
+pub mod core;
+pub mod error;
+pub mod global;
+pub mod types;
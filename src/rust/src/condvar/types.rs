@@ -0,0 +1,119 @@
+//! 条件变量相关类型定义
+
+use crate::{container_of, mutex::types::MutexId, utils::list::LinkedList};
+
+/// 条件变量状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CondvarState {
+    Unused = 0,
+    Used = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct CondvarId(pub u32);
+
+impl CondvarId {
+    const CONDVAR_SPLIT_BIT: u32 = 16;
+
+    pub fn new(count: u16, index: u16) -> Self {
+        let id = ((count as u32) << Self::CONDVAR_SPLIT_BIT) | (index as u32);
+        Self(id)
+    }
+
+    /// 获取条件变量索引部分
+    pub fn get_index(self) -> u16 {
+        (self.0 & ((1 << Self::CONDVAR_SPLIT_BIT) - 1)) as u16
+    }
+
+    /// 获取条件变量计数部分
+    pub fn get_count(self) -> u16 {
+        (self.0 >> Self::CONDVAR_SPLIT_BIT) as u16
+    }
+
+    /// 增加计数值生成新ID，保持索引不变
+    pub fn increment_count(&self) -> Self {
+        Self::new(self.get_count().wrapping_add(1), self.get_index())
+    }
+}
+
+impl From<u32> for CondvarId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<CondvarId> for u32 {
+    fn from(id: CondvarId) -> Self {
+        id.0
+    }
+}
+
+/// 条件变量控制块
+#[repr(C)]
+#[derive(Debug)]
+pub struct CondvarControlBlock {
+    /// 等待的任务列表，按优先级排序，未使用时挂入空闲链表
+    pub wait_list: LinkedList,
+
+    /// 等待者使用的互斥锁，仅在有任务等待时有效
+    pub mutex: MutexId,
+
+    /// 条件变量状态
+    pub state: CondvarState,
+
+    /// 条件变量ID
+    pub id: CondvarId,
+}
+
+impl CondvarControlBlock {
+    pub const UNINIT: Self = Self {
+        wait_list: LinkedList::new(),
+        mutex: MutexId(0),
+        state: CondvarState::Unused,
+        id: CondvarId(0),
+    };
+
+    pub fn is_unused(&self) -> bool {
+        self.state == CondvarState::Unused
+    }
+
+    /// 获取条件变量ID
+    pub fn get_id(&self) -> CondvarId {
+        self.id
+    }
+
+    /// 检查是否为指定的句柄
+    pub fn matches_id(&self, id: CondvarId) -> bool {
+        self.id == id
+    }
+
+    /// 检查是否有等待的任务
+    pub fn has_waiting_tasks(&self) -> bool {
+        !LinkedList::is_empty(&raw const self.wait_list)
+    }
+
+    /// 初始化条件变量
+    pub fn initialize(&mut self) {
+        LinkedList::init(&raw mut self.wait_list);
+        self.state = CondvarState::Used;
+    }
+
+    /// 重置条件变量
+    pub fn reset(&mut self) {
+        self.state = CondvarState::Unused;
+        self.id = self.id.increment_count();
+    }
+
+    pub fn from_wait_list(ptr: *mut LinkedList) -> &'static mut CondvarControlBlock {
+        let condvar_ptr = container_of!(ptr, CondvarControlBlock, wait_list);
+        unsafe { &mut *condvar_ptr }
+    }
+}
+
+impl Default for CondvarControlBlock {
+    fn default() -> Self {
+        Self::UNINIT
+    }
+}
@@ -7,11 +7,25 @@ pub const KERNEL_TIMESLICE_TIMEOUT: u16 = 2;
 
 pub const STACK_POINT_ALIGN_SIZE: u32 = 8;
 
-pub const TASK_PRIORITY_LOWEST: u16 = 31;
+/// smp
+#[cfg(feature = "smp")]
+pub const KERNEL_CORE_NUM: usize = 4;
+#[cfg(not(feature = "smp"))]
+pub const KERNEL_CORE_NUM: usize = 1;
+/// 全部CPU核的亲和性掩码
+pub const KERNEL_CPU_MASK: u16 = ((1u32 << KERNEL_CORE_NUM) - 1) as u16;
+
+/// 任务优先级数量，开启`priority-256`特性时扩展为256级
+#[cfg(feature = "priority-256")]
+pub const TASK_PRIORITY_NUM: usize = 256;
+#[cfg(not(feature = "priority-256"))]
+pub const TASK_PRIORITY_NUM: usize = 32;
+pub const TASK_PRIORITY_LOWEST: u16 = (TASK_PRIORITY_NUM - 1) as u16;
 pub const TASK_LIMIT: u32 = 64;
 pub const TASK_DEFAULT_STACK_SIZE: u32 = 24576;
 pub const TASK_MIN_STACK_SIZE: u32 = 2048;
 pub const TASK_IDLE_STACK_SIZE: u32 = 2048;
+pub const TASK_NAME_LEN: usize = 32;
 
 // tick
 pub const SYS_CLOCK: u32 = 0x6000000;
@@ -23,6 +37,25 @@ pub const STACK_INIT_PATTERN: usize = 0xCACACACA;
 
 /// mutex
 pub const MUX_LIMIT: u32 = 1024;
+/// 优先级继承沿阻塞链传递的最大层数
+pub const MUX_INHERIT_DEPTH: u32 = 8;
+
+/// rwlock
+pub const RWLOCK_LIMIT: u32 = 1024;
+/// 每个任务可以同时持有的读锁数
+pub const RWLOCK_READ_HOLD_LIMIT: usize = 4;
+
+/// condition variable
+pub const CONDVAR_LIMIT: u32 = 1024;
+
+/// barrier
+pub const BARRIER_LIMIT: u32 = 1024;
+
+/// countdown latch
+pub const LATCH_LIMIT: u32 = 1024;
+
+/// 多对象等待一次最多等待的对象数
+pub const SELECT_OBJECT_LIMIT: u32 = 16;
 
 /// semaphore
 pub const SEM_LIMIT: u32 = 1024;
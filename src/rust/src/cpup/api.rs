@@ -0,0 +1,84 @@
+use super::{
+    error::CpupError,
+    record::{CPUP_HISTORY_CYCLES, CPUP_HISTORY_INDEX, task_cycles},
+    types::{CPUP_HISTORY_NUM, CPUP_PRECISION, CpupMode},
+};
+use crate::{
+    config::{KERNEL_CORE_NUM, TASK_LIMIT},
+    ffi::bindings::hal_clock_get_cycles,
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    percpu::{cpu_online_mask, os_percpu_get_by_id},
+    result::SystemResult,
+    task::{
+        global::get_tcb_from_id,
+        types::{TaskCB, TaskStatus},
+    },
+};
+
+/// 获取统计范围起点的采样位置，启动以来的统计没有对应的采样
+fn history_index(mode: CpupMode) -> Option<usize> {
+    let index = unsafe { CPUP_HISTORY_INDEX };
+    match mode {
+        CpupMode::LastOneSecond => Some(index),
+        CpupMode::LastTenSeconds => Some((index + 1) % CPUP_HISTORY_NUM),
+        CpupMode::AllTime => None,
+    }
+}
+
+/// 计算任务的占用率，须在关中断后调用
+fn task_usage(task_cb: &TaskCB, mode: CpupMode, now: u64) -> u32 {
+    let (task_base, system_base) = match history_index(mode) {
+        Some(index) => (task_cb.cpup.history[index], unsafe {
+            CPUP_HISTORY_CYCLES[index]
+        }),
+        None => (0, 0),
+    };
+
+    // 以所有在线核的总时间为基准
+    let total = now.saturating_sub(system_base) * cpu_online_mask().count_ones() as u64;
+    if total == 0 {
+        return 0;
+    }
+    let run = task_cycles(task_cb, now).saturating_sub(task_base);
+    ((run as u128 * CPUP_PRECISION as u128 / total as u128) as u32).min(CPUP_PRECISION)
+}
+
+/// 获取任务的CPU占用率
+pub fn task_cpu_usage(task_id: u32, mode: CpupMode) -> SystemResult<u32> {
+    if task_id >= TASK_LIMIT {
+        return Err(CpupError::TaskIdInvalid.into());
+    }
+
+    let task_cb = get_tcb_from_id(task_id);
+
+    let int_save = disable_interrupts();
+
+    if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        restore_interrupt_state(int_save);
+        return Err(CpupError::TaskNotCreated.into());
+    }
+    let usage = task_usage(task_cb, mode, hal_clock_get_cycles());
+
+    restore_interrupt_state(int_save);
+
+    Ok(usage)
+}
+
+/// 获取系统的CPU占用率，即非空闲任务的占用率
+pub fn system_cpu_usage(mode: CpupMode) -> u32 {
+    let int_save = disable_interrupts();
+
+    let now = hal_clock_get_cycles();
+    let online = cpu_online_mask();
+    let idle_usage: u32 = (0..KERNEL_CORE_NUM as u32)
+        .filter(|cpuid| online & (1 << cpuid) != 0)
+        .map(|cpuid| {
+            let idle_task = get_tcb_from_id(os_percpu_get_by_id(cpuid).idle_task_id);
+            task_usage(idle_task, mode, now)
+        })
+        .sum();
+
+    restore_interrupt_state(int_save);
+
+    CPUP_PRECISION.saturating_sub(idle_usage)
+}
@@ -0,0 +1,43 @@
+/// CPU占用率统计错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpupError {
+    /// 任务未创建
+    TaskNotCreated,
+    /// 任务ID无效
+    TaskIdInvalid,
+}
+
+impl From<CpupError> for u32 {
+    fn from(err: CpupError) -> u32 {
+        match err {
+            CpupError::TaskNotCreated => ERRNO_CPUP_THREAD_NO_CREATED,
+            CpupError::TaskIdInvalid => ERRNO_CPUP_TSK_ID_INVALID,
+        }
+    }
+}
+
+/// 从u32错误码转换为CpupError
+impl TryFrom<u32> for CpupError {
+    type Error = ();
+
+    fn try_from(errno: u32) -> Result<Self, Self::Error> {
+        match errno {
+            ERRNO_CPUP_THREAD_NO_CREATED => Ok(CpupError::TaskNotCreated),
+            ERRNO_CPUP_TSK_ID_INVALID => Ok(CpupError::TaskIdInvalid),
+            _ => Err(()),
+        }
+    }
+}
+
+impl core::fmt::Display for CpupError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let desc = match self {
+            Self::TaskNotCreated => "Task is not created",
+            Self::TaskIdInvalid => "Task ID is invalid",
+        };
+        write!(f, "{}", desc)
+    }
+}
+
+const ERRNO_CPUP_THREAD_NO_CREATED: u32 = 0x02001e04;
+const ERRNO_CPUP_TSK_ID_INVALID: u32 = 0x02001e05;
@@ -0,0 +1,15 @@
+//! CPU占用率统计
+//!
+//! 任务切换时按周期计数累计每个任务的运行时间，系统Tick每秒采样一次运行时间，
+//! 由最近的采样得到最近1秒、最近10秒的占用率，由累计运行时间得到启动以来的占用率。
+//! 占用率以千分比表示，多核下以所有在线核的总时间为基准。
+
+mod api;
+mod error;
+mod record;
+mod types;
+
+pub use api::{system_cpu_usage, task_cpu_usage};
+pub use error::CpupError;
+pub use record::{cpup_task_start, cpup_task_switch, cpup_tick};
+pub use types::{CPUP_PRECISION, CpupMode, TaskCpup};
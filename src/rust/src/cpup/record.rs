@@ -0,0 +1,74 @@
+use super::types::CPUP_HISTORY_NUM;
+use crate::{
+    config::{TASK_LIMIT, TICK_PER_SECOND},
+    ffi::bindings::{arch_curr_cpuid, hal_clock_get_cycles},
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    task::{
+        global::get_tcb_from_id,
+        types::{TaskCB, TaskStatus},
+    },
+};
+
+/// 每次采样时的系统时间
+pub(super) static mut CPUP_HISTORY_CYCLES: [u64; CPUP_HISTORY_NUM] = [0; CPUP_HISTORY_NUM];
+
+/// 最近一次采样的位置
+pub(super) static mut CPUP_HISTORY_INDEX: usize = 0;
+
+/// 上次采样后经过的Tick数
+static mut CPUP_SAMPLE_TICKS: u32 = 0;
+
+/// 获取任务的累计运行时间，包括正在运行的部分
+pub(super) fn task_cycles(task_cb: &TaskCB, now: u64) -> u64 {
+    let cpup = &task_cb.cpup;
+    if task_cb.task_status.contains(TaskStatus::RUNNING) {
+        cpup.all_cycles + now.saturating_sub(cpup.start_cycles)
+    } else {
+        cpup.all_cycles
+    }
+}
+
+/// 记录核上第一个任务的换入时间
+pub fn cpup_task_start(new_task: &mut TaskCB) {
+    new_task.cpup.start_cycles = hal_clock_get_cycles();
+}
+
+/// 任务切换时累计换出任务的运行时间，并记录换入任务的换入时间
+pub fn cpup_task_switch(run_task: &mut TaskCB, new_task: &mut TaskCB) {
+    let now = hal_clock_get_cycles();
+    run_task.cpup.all_cycles += now.saturating_sub(run_task.cpup.start_cycles);
+    new_task.cpup.start_cycles = now;
+}
+
+/// 系统Tick处理，0号核每秒采样一次所有任务的运行时间
+pub fn cpup_tick() {
+    if arch_curr_cpuid() != 0 {
+        return;
+    }
+
+    unsafe {
+        CPUP_SAMPLE_TICKS += 1;
+        if CPUP_SAMPLE_TICKS < TICK_PER_SECOND {
+            return;
+        }
+        CPUP_SAMPLE_TICKS = 0;
+    }
+
+    let int_save = disable_interrupts();
+
+    let now = hal_clock_get_cycles();
+    let index = unsafe { (CPUP_HISTORY_INDEX + 1) % CPUP_HISTORY_NUM };
+    for task_id in 0..TASK_LIMIT {
+        let task_cb = get_tcb_from_id(task_id);
+        if task_cb.task_status.contains(TaskStatus::UNUSED) {
+            continue;
+        }
+        task_cb.cpup.history[index] = task_cycles(task_cb, now);
+    }
+    unsafe {
+        CPUP_HISTORY_CYCLES[index] = now;
+        CPUP_HISTORY_INDEX = index;
+    }
+
+    restore_interrupt_state(int_save);
+}
@@ -0,0 +1,48 @@
+/// 占用率精度，结果为千分比
+pub const CPUP_PRECISION: u32 = 1000;
+
+/// 历史采样个数，相邻采样间隔1秒
+pub(crate) const CPUP_HISTORY_NUM: usize = 11;
+
+/// 占用率统计时间范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum CpupMode {
+    /// 最近10秒
+    LastTenSeconds = 0,
+    /// 最近1秒
+    LastOneSecond = 1,
+    /// 启动以来
+    AllTime = 0xffff,
+}
+
+/// 未知的模式按启动以来统计
+impl From<u16> for CpupMode {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => CpupMode::LastTenSeconds,
+            1 => CpupMode::LastOneSecond,
+            _ => CpupMode::AllTime,
+        }
+    }
+}
+
+/// 任务运行时间统计，单位为周期
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TaskCpup {
+    /// 本次换入的时间
+    pub start_cycles: u64,
+    /// 累计运行时间，不含本次换入后的部分
+    pub all_cycles: u64,
+    /// 每次采样时的累计运行时间
+    pub history: [u64; CPUP_HISTORY_NUM],
+}
+
+impl TaskCpup {
+    pub const UNINIT: Self = Self {
+        start_cycles: 0,
+        all_cycles: 0,
+        history: [0; CPUP_HISTORY_NUM],
+    };
+}
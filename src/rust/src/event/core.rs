@@ -6,9 +6,10 @@ use crate::ffi::bindings::{arch_int_locked, get_current_task};
 use crate::interrupt::{disable_interrupts, restore_interrupt_state};
 use crate::percpu::can_preempt_in_scheduler;
 use crate::result::{SystemError, SystemResult};
+use crate::task::error::TaskError;
 use crate::task::sched::{schedule, schedule_reschedule};
-use crate::task::sync::wait::{task_wait, task_wake};
-use crate::task::types::{TaskCB, TaskStatus};
+use crate::task::sync::wait::{task_wait, task_wait_interrupted, task_wake};
+use crate::task::types::{TaskCB, TaskPendObject, TaskStatus};
 use crate::utils::list::LinkedList;
 
 use super::error::EventError;
@@ -35,7 +36,7 @@ fn validate_event_params(event_mask: u32, mode: u32) -> SystemResult<()> {
 
 /// 检查事件读取的上下文有效性
 #[inline]
-fn validate_event_read_context() -> SystemResult<()> {
+pub(super) fn validate_event_read_context() -> SystemResult<()> {
     // 检查是否在中断上下文中
     if is_interrupt_active() {
         return Err(SystemError::Event(EventError::ReadInInterrupt));
@@ -95,7 +96,7 @@ fn poll(event_id: &mut u32, event_mask: u32, mode: u32) -> u32 {
 
     // 如果需要清除事件且有匹配的事件
     if result != 0 && EventWaitMode::is_clear(mode) {
-        *event_id = *event_id & !result;
+        *event_id &= !result;
     }
 
     result
@@ -112,10 +113,12 @@ fn read(event_cb: &mut EventCB, event_mask: u32, mode: u32, timeout: u32) -> Sys
     // 如果没有匹配的事件
     if result == 0 {
         if timeout == 0 {
+            restore_interrupt_state(int_save);
             return Ok(result);
         }
 
         if !can_preempt_in_scheduler() {
+            restore_interrupt_state(int_save);
             return Err(SystemError::Event(EventError::ReadInLock));
         }
 
@@ -124,7 +127,8 @@ fn read(event_cb: &mut EventCB, event_mask: u32, mode: u32, timeout: u32) -> Sys
         current_task.event_mode = mode;
 
         // 将任务加入等待队列
-        task_wait(&mut event_cb.wait_list, timeout);
+        let pend_object = TaskPendObject::Event(event_cb as *const EventCB as usize);
+        task_wait(&mut event_cb.wait_list, pend_object, timeout);
 
         // 立即调度
         schedule_reschedule();
@@ -136,8 +140,13 @@ fn read(event_cb: &mut EventCB, event_mask: u32, mode: u32, timeout: u32) -> Sys
         // 检查是否超时
         if current_task.task_status.contains(TaskStatus::TIMEOUT) {
             current_task.task_status.remove(TaskStatus::TIMEOUT);
+            restore_interrupt_state(int_save);
             return Err(SystemError::Event(EventError::ReadTimeout));
         }
+        if task_wait_interrupted(current_task) {
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::Interrupted));
+        }
 
         // 重新轮询事件
         result = poll(&mut event_cb.event_id, event_mask, mode);
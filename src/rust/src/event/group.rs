@@ -0,0 +1,346 @@
+//! 64位事件组实现
+//!
+//! 与`EventCB`不同，事件组的64位全部可用，并支持置位后等待的同步操作和等待位被清除。
+
+use crate::ffi::bindings::{arch_int_locked, get_current_task};
+use crate::interrupt::{disable_interrupts, restore_interrupt_state};
+use crate::percpu::can_preempt_in_scheduler;
+use crate::result::{SystemError, SystemResult};
+use crate::select::{
+    core::{select_notify, select_object_deleted},
+    types::SelectObject,
+};
+use crate::task::error::TaskError;
+use crate::task::sched::{schedule, schedule_reschedule};
+use crate::task::sync::wait::{task_wait, task_wait_interrupted, task_wake};
+use crate::task::types::{TaskCB, TaskPendObject, TaskStatus};
+use crate::utils::list::LinkedList;
+
+use super::core::validate_event_read_context;
+use super::error::EventError;
+use super::types::{EventGroupCB, EventWaitMode};
+
+/// 等待位被清除的内部模式标志，仅记录在任务的事件模式中
+const EVENT_GROUP_WAIT_CLEARED: u32 = 0x08;
+
+/// 检查事件组掩码是否有效
+#[inline]
+fn validate_group_mask(mask: u64) -> SystemResult<()> {
+    if mask == 0 {
+        Err(SystemError::Event(EventError::MaskInvalid))
+    } else {
+        Ok(())
+    }
+}
+
+/// 检查置位等待条件是否满足
+#[inline]
+fn set_satisfied(bits: u64, mask: u64, mode: u32) -> bool {
+    if EventWaitMode::is_or(mode) {
+        bits & mask != 0
+    } else {
+        bits & mask == mask
+    }
+}
+
+/// 检查清除等待条件是否满足
+#[inline]
+fn cleared_satisfied(bits: u64, mask: u64, mode: u32) -> bool {
+    if EventWaitMode::is_or(mode) {
+        bits & mask != mask
+    } else {
+        bits & mask == 0
+    }
+}
+
+/// 唤醒条件已满足的任务，返回是否唤醒了任务
+///
+/// 被唤醒任务的`event_group_bits`改写为满足条件时的事件组值。`clear`中的位在等待置位的任务
+/// 检查完后与它们要求清除的位一起清除，之后再检查等待清除的任务。
+fn wake_waiting_tasks(group: &mut EventGroupCB, clear: u64) -> bool {
+    debug_assert!(arch_int_locked());
+
+    let head = &raw mut group.wait_list;
+    let mut woken = false;
+    let mut bits_to_clear = clear;
+
+    // 先处理等待置位的任务，带清除模式的任务要求清除的位在全部检查完后统一清除
+    let mut cur = group.wait_list.next;
+    while cur != head {
+        let task = TaskCB::from_pend_list(cur);
+        cur = task.pend_list.next;
+
+        let mode = task.event_mode;
+        if mode & EVENT_GROUP_WAIT_CLEARED != 0
+            || !set_satisfied(group.bits, task.event_group_bits, mode)
+        {
+            continue;
+        }
+        if EventWaitMode::is_clear(mode) {
+            bits_to_clear |= task.event_group_bits;
+        }
+        task.event_group_bits = group.bits;
+        task_wake(task);
+        woken = true;
+    }
+    group.bits &= !bits_to_clear;
+
+    // 再处理等待清除的任务
+    cur = group.wait_list.next;
+    while cur != head {
+        let task = TaskCB::from_pend_list(cur);
+        cur = task.pend_list.next;
+
+        let mode = task.event_mode;
+        if mode & EVENT_GROUP_WAIT_CLEARED == 0
+            || !cleared_satisfied(group.bits, task.event_group_bits, mode)
+        {
+            continue;
+        }
+        task.event_group_bits = group.bits;
+        task_wake(task);
+        woken = true;
+    }
+
+    woken
+}
+
+/// 通知多对象等待该事件组的任务，`bits`为置位后、清除前的事件组值，返回是否唤醒了任务
+fn notify_select(group: &mut EventGroupCB, bits: u64) -> bool {
+    select_notify(&raw mut group.select_list, |object| match *object {
+        SelectObject::EventGroup(_, mask) => bits & mask != 0,
+        _ => false,
+    })
+}
+
+/// 阻塞当前任务直到事件组条件满足，须在关中断后调用，返回前恢复中断状态
+fn pend(
+    group: &mut EventGroupCB,
+    mask: u64,
+    mode: u32,
+    timeout: u32,
+    mut int_save: u32,
+) -> SystemResult<u64> {
+    if timeout == 0 {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Event(EventError::ReadTimeout));
+    }
+    if !can_preempt_in_scheduler() {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Event(EventError::ReadInLock));
+    }
+
+    let current_task = get_current_task();
+    current_task.event_group_bits = mask;
+    current_task.event_mode = mode;
+
+    let pend_object = TaskPendObject::EventGroup(group as *const EventGroupCB as usize);
+    task_wait(&mut group.wait_list, pend_object, timeout);
+
+    // 立即调度
+    schedule_reschedule();
+
+    // 解锁并重新加锁
+    restore_interrupt_state(int_save);
+    int_save = disable_interrupts();
+
+    let result = if current_task.task_status.contains(TaskStatus::TIMEOUT) {
+        current_task.task_status.remove(TaskStatus::TIMEOUT);
+        Err(SystemError::Event(EventError::ReadTimeout))
+    } else if task_wait_interrupted(current_task) {
+        Err(SystemError::Task(TaskError::Interrupted))
+    } else {
+        Ok(current_task.event_group_bits)
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 事件组初始化
+pub fn event_group_init(group: &mut EventGroupCB) {
+    let int_save = disable_interrupts();
+
+    group.bits = 0;
+    LinkedList::init(&raw mut group.wait_list);
+    LinkedList::init(&raw mut group.select_list);
+
+    restore_interrupt_state(int_save);
+}
+
+/// 事件组销毁
+///
+/// 有任务在等待事件组时不能销毁；多对象等待该事件组的任务被唤醒并返回[`SelectError::ObjectDeleted`]。
+///
+/// [`SelectError::ObjectDeleted`]: crate::select::error::SelectError::ObjectDeleted
+pub fn event_group_destroy(group: &mut EventGroupCB) -> SystemResult<()> {
+    let int_save = disable_interrupts();
+
+    if !group.is_wait_list_empty() {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Event(EventError::ShouldNotDestroy));
+    }
+    group.bits = 0;
+    let woken = select_object_deleted(&raw mut group.select_list);
+
+    restore_interrupt_state(int_save);
+    if woken {
+        schedule();
+    }
+    Ok(())
+}
+
+/// 获取事件组的当前值
+pub fn event_group_get(group: &EventGroupCB) -> u64 {
+    let int_save = disable_interrupts();
+    let bits = group.bits;
+    restore_interrupt_state(int_save);
+    bits
+}
+
+/// 置位事件组并唤醒条件满足的任务，返回置位后的事件组值，可在中断中调用
+///
+/// 带清除模式的等待任务被唤醒后，其等待的位会被清除，返回值反映清除后的结果。
+pub fn event_group_set(group: &mut EventGroupCB, bits: u64) -> u64 {
+    let int_save = disable_interrupts();
+
+    group.bits |= bits;
+    let mut woken = notify_select(group, group.bits);
+    woken |= wake_waiting_tasks(group, 0);
+    let result = group.bits;
+
+    restore_interrupt_state(int_save);
+
+    if woken {
+        schedule();
+    }
+    result
+}
+
+/// 清除事件组中的指定位并唤醒等待清除的任务，返回清除后的事件组值，可在中断中调用
+///
+/// 与`event_clear`不同，参数为要清除的位而不是要保留的位。
+pub fn event_group_clear(group: &mut EventGroupCB, bits: u64) -> u64 {
+    let int_save = disable_interrupts();
+
+    group.bits &= !bits;
+    let woken = wake_waiting_tasks(group, 0);
+    let result = group.bits;
+
+    restore_interrupt_state(int_save);
+
+    if woken {
+        schedule();
+    }
+    result
+}
+
+/// 等待事件组中掩码内的位被置位，返回满足条件时的事件组值
+///
+/// `mode`与`event_read`相同；清除模式下返回后掩码内的位已被清除。`timeout`为0且条件
+/// 不满足时立即返回`ReadTimeout`。
+pub fn event_group_wait(
+    group: &mut EventGroupCB,
+    mask: u64,
+    mode: u32,
+    timeout: u32,
+) -> SystemResult<u64> {
+    validate_group_mask(mask)?;
+    if !EventWaitMode::validate(mode) {
+        return Err(SystemError::Event(EventError::ModeInvalid));
+    }
+    validate_event_read_context()?;
+
+    let int_save = disable_interrupts();
+
+    if set_satisfied(group.bits, mask, mode) {
+        let result = group.bits;
+        if EventWaitMode::is_clear(mode) {
+            group.bits &= !mask;
+        }
+        restore_interrupt_state(int_save);
+        return Ok(result);
+    }
+
+    pend(group, mask, mode, timeout, int_save)
+}
+
+/// 等待事件组中掩码内的位被清除，返回满足条件时的事件组值
+///
+/// 或模式下掩码内任意一位被清除即满足，与模式下要求掩码内所有位都被清除，不支持清除模式。
+pub fn event_group_wait_cleared(
+    group: &mut EventGroupCB,
+    mask: u64,
+    mode: u32,
+    timeout: u32,
+) -> SystemResult<u64> {
+    validate_group_mask(mask)?;
+    if !EventWaitMode::validate(mode) || EventWaitMode::is_clear(mode) {
+        return Err(SystemError::Event(EventError::ModeInvalid));
+    }
+    validate_event_read_context()?;
+
+    let int_save = disable_interrupts();
+
+    if cleared_satisfied(group.bits, mask, mode) {
+        let result = group.bits;
+        restore_interrupt_state(int_save);
+        return Ok(result);
+    }
+
+    pend(
+        group,
+        mask,
+        mode | EVENT_GROUP_WAIT_CLEARED,
+        timeout,
+        int_save,
+    )
+}
+
+/// 置位`set_bits`后等待`wait_bits`全部置位，用于多个任务在同一点会合
+///
+/// 条件满足时`wait_bits`被清除，所有参与的任务都返回清除前的事件组值。超时返回时已置位的
+/// `set_bits`不会撤销。
+pub fn event_group_sync(
+    group: &mut EventGroupCB,
+    set_bits: u64,
+    wait_bits: u64,
+    timeout: u32,
+) -> SystemResult<u64> {
+    validate_group_mask(wait_bits)?;
+    validate_event_read_context()?;
+
+    let int_save = disable_interrupts();
+
+    let original = group.bits;
+    group.bits |= set_bits;
+    let result = original | set_bits;
+    let selected = notify_select(group, result);
+    let mode = EventWaitMode::And as u32 | EventWaitMode::Clear as u32;
+
+    // 最后到达的任务负责清除会合位并放行其他任务
+    if result & wait_bits == wait_bits {
+        let woken = wake_waiting_tasks(group, wait_bits) | selected;
+        restore_interrupt_state(int_save);
+        if woken {
+            schedule();
+        }
+        return Ok(result);
+    }
+
+    // 置位可能满足了其他任务的等待条件，当前任务阻塞时的调度会让它们运行
+    let woken = wake_waiting_tasks(group, 0) | selected;
+    if timeout != 0 && can_preempt_in_scheduler() {
+        return pend(group, wait_bits, mode, timeout, int_save);
+    }
+
+    restore_interrupt_state(int_save);
+    if woken {
+        schedule();
+    }
+    if timeout == 0 {
+        Err(SystemError::Event(EventError::ReadTimeout))
+    } else {
+        Err(SystemError::Event(EventError::ReadInLock))
+    }
+}
@@ -83,3 +83,37 @@ impl Default for EventCB {
         Self::new()
     }
 }
+
+/// 64位事件组控制块，所有位均可使用
+#[repr(C)]
+#[derive(Debug)]
+pub struct EventGroupCB {
+    /// 事件组的当前值
+    pub bits: u64,
+    /// 等待此事件组的任务列表
+    pub wait_list: LinkedList,
+    /// 多对象等待此事件组的等待节点列表
+    pub select_list: LinkedList,
+}
+
+impl EventGroupCB {
+    /// 创建新的事件组控制块
+    pub const fn new() -> Self {
+        Self {
+            bits: 0,
+            wait_list: LinkedList::new(),
+            select_list: LinkedList::new(),
+        }
+    }
+
+    /// 检查等待列表是否为空
+    pub fn is_wait_list_empty(&self) -> bool {
+        LinkedList::is_empty(&raw const self.wait_list)
+    }
+}
+
+impl Default for EventGroupCB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
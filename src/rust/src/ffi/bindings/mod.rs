@@ -36,6 +36,26 @@ unsafe extern "C" {
     #[link_name = "ArchIrqInit"]
     unsafe fn c_arch_irq_init();
 
+    #[cfg(feature = "smp")]
+    #[link_name = "ArchCurrCpuid"]
+    unsafe fn c_arch_curr_cpuid() -> u32;
+
+    #[cfg(feature = "smp")]
+    #[link_name = "HalIrqSendIpi"]
+    unsafe fn c_hal_irq_send_ipi(target: u32, ipi: u32);
+
+    #[cfg(feature = "smp")]
+    #[link_name = "ArchSpinLock"]
+    unsafe fn c_arch_spin_lock(lock: *mut u32);
+
+    #[cfg(feature = "smp")]
+    #[link_name = "ArchSpinUnlock"]
+    unsafe fn c_arch_spin_unlock(lock: *mut u32);
+
+    #[cfg(feature = "smp")]
+    #[link_name = "ArchSpinTrylock"]
+    unsafe fn c_arch_spin_trylock(lock: *mut u32) -> i32;
+
     #[link_name = "HalClockInit"]
     unsafe fn c_hal_clock_init();
 
@@ -48,6 +68,11 @@ unsafe extern "C" {
     #[link_name = "HalDelayUs"]
     unsafe fn c_hal_delay_us(usecs: u32);
 
+    #[cfg(feature = "tickless")]
+    #[link_name = "HalClockTickTimerReload"]
+    unsafe fn c_hal_clock_tick_timer_reload(cycles: u32);
+
+    #[cfg(not(feature = "sim"))]
     #[link_name = "dprintf"]
     unsafe fn c_dprintf(fmt: *const c_char, ...);
 }
@@ -72,6 +97,13 @@ pub fn hal_delay_us(usecs: u32) {
     unsafe { c_hal_delay_us(usecs) }
 }
 
+/// 重新设置当前核的Tick定时器，下一次Tick中断在指定周期数后产生，之后恢复原有的周期
+#[cfg(feature = "tickless")]
+#[inline]
+pub fn hal_clock_tick_timer_reload(cycles: u32) {
+    unsafe { c_hal_clock_tick_timer_reload(cycles) }
+}
+
 #[inline]
 pub fn get_current_task() -> &'static mut TaskCB {
     unsafe { c_curr_task_get().as_mut().expect("Current task is null") }
@@ -122,7 +154,55 @@ pub fn arch_irq_init() {
     unsafe { c_arch_irq_init() }
 }
 
+/// 获取当前CPU核号
+#[cfg(feature = "smp")]
+#[inline]
+pub fn arch_curr_cpuid() -> u32 {
+    unsafe { c_arch_curr_cpuid() }
+}
+
+#[cfg(not(feature = "smp"))]
 #[inline]
+pub const fn arch_curr_cpuid() -> u32 {
+    0
+}
+
+/// 向目标CPU集合发送核间中断
+#[cfg(feature = "smp")]
+#[inline]
+pub fn hal_irq_send_ipi(target: u32, ipi: u32) {
+    unsafe { c_hal_irq_send_ipi(target, ipi) }
+}
+
+#[cfg(feature = "smp")]
+#[inline]
+pub fn arch_spin_lock(lock: *mut u32) {
+    unsafe { c_arch_spin_lock(lock) }
+}
+
+#[cfg(feature = "smp")]
+#[inline]
+pub fn arch_spin_unlock(lock: *mut u32) {
+    unsafe { c_arch_spin_unlock(lock) }
+}
+
+/// 尝试获取自旋锁，成功返回0
+#[cfg(feature = "smp")]
+#[inline]
+pub fn arch_spin_trylock(lock: *mut u32) -> i32 {
+    unsafe { c_arch_spin_trylock(lock) }
+}
+
+#[inline]
+#[cfg(not(feature = "sim"))]
 pub fn dprintf(fmt: *const c_char) {
     unsafe { c_dprintf(fmt) }
 }
+
+// 宿主libc中的dprintf签名不同，仿真环境下直接写到标准输出
+#[cfg(feature = "sim")]
+#[inline]
+pub fn dprintf(fmt: *const c_char) {
+    let s = unsafe { core::ffi::CStr::from_ptr(fmt) };
+    std::print!("{}", s.to_string_lossy());
+}
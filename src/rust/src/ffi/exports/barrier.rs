@@ -0,0 +1,117 @@
+use crate::{
+    barrier::{
+        core::{barrier_create, barrier_delete, barrier_init, barrier_wait},
+        error::BarrierError,
+        latch::{latch_count_down, latch_count_get, latch_create, latch_delete, latch_wait},
+        types::BarrierCallback,
+    },
+    config::OK,
+};
+
+#[unsafe(export_name = "OsBarrierInit")]
+pub extern "C" fn os_barrier_init() {
+    barrier_init();
+}
+
+#[unsafe(export_name = "LOS_BarrierCreate")]
+pub extern "C" fn los_barrier_create(
+    barrier_handle: *mut u32,
+    parties: u32,
+    callback: BarrierCallback,
+) -> u32 {
+    if barrier_handle.is_null() {
+        return BarrierError::PtrNull.into();
+    }
+
+    match barrier_create(parties, callback) {
+        Ok(handle) => {
+            unsafe {
+                *barrier_handle = handle.into();
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_BarrierDelete")]
+pub extern "C" fn los_barrier_delete(barrier_handle: u32) -> u32 {
+    match barrier_delete(barrier_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+/// 等待屏障，`is_last`不为空时写入当前任务是否为本轮最后到达的任务
+#[unsafe(export_name = "LOS_BarrierWait")]
+pub extern "C" fn los_barrier_wait(barrier_handle: u32, timeout: u32, is_last: *mut bool) -> u32 {
+    match barrier_wait(barrier_handle.into(), timeout) {
+        Ok(last) => {
+            if !is_last.is_null() {
+                unsafe {
+                    *is_last = last;
+                }
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_LatchCreate")]
+pub extern "C" fn los_latch_create(latch_handle: *mut u32, count: u32) -> u32 {
+    if latch_handle.is_null() {
+        return BarrierError::PtrNull.into();
+    }
+
+    match latch_create(count) {
+        Ok(handle) => {
+            unsafe {
+                *latch_handle = handle.into();
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_LatchDelete")]
+pub extern "C" fn los_latch_delete(latch_handle: u32) -> u32 {
+    match latch_delete(latch_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_LatchCountDown")]
+pub extern "C" fn los_latch_count_down(latch_handle: u32) -> u32 {
+    match latch_count_down(latch_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_LatchWait")]
+pub extern "C" fn los_latch_wait(latch_handle: u32, timeout: u32) -> u32 {
+    match latch_wait(latch_handle.into(), timeout) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_LatchCountGet")]
+pub extern "C" fn los_latch_count_get(latch_handle: u32, count: *mut u32) -> u32 {
+    if count.is_null() {
+        return BarrierError::PtrNull.into();
+    }
+
+    match latch_count_get(latch_handle.into()) {
+        Ok(value) => {
+            unsafe {
+                *count = value;
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
@@ -3,20 +3,16 @@ use crate::utils::bitmap::{clear_bit, get_highest_bit, get_lowest_bit, set_bit};
 /// 设置位图中的指定位置
 #[unsafe(export_name = "LOS_BitmapSet")]
 pub extern "C" fn los_bitmap_set(bitmap: *mut u32, pos: u16) {
-    match unsafe { bitmap.as_mut() } {
-        Some(b) => set_bit(b, pos),
-        None => {}
+    if let Some(b) = unsafe { bitmap.as_mut() } {
+        set_bit(b, pos)
     }
 }
 
 /// 清除位图中的指定位置
 #[unsafe(export_name = "LOS_BitmapClr")]
 pub extern "C" fn los_bitmap_clr(bitmap: *mut u32, pos: u16) {
-    match unsafe { bitmap.as_mut() } {
-        Some(b) => {
-            clear_bit(b, pos);
-        }
-        None => {}
+    if let Some(b) = unsafe { bitmap.as_mut() } {
+        clear_bit(b, pos);
     }
 }
 
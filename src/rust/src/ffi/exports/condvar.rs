@@ -0,0 +1,76 @@
+use crate::{
+    condvar::{
+        core::{
+            condvar_broadcast, condvar_create, condvar_delete, condvar_init, condvar_signal,
+            condvar_timed_wait, condvar_wait,
+        },
+        error::CondvarError,
+    },
+    config::OK,
+};
+
+#[unsafe(export_name = "OsCondvarInit")]
+pub extern "C" fn os_condvar_init() {
+    condvar_init();
+}
+
+#[unsafe(export_name = "LOS_CondvarCreate")]
+pub extern "C" fn los_condvar_create(condvar_handle: *mut u32) -> u32 {
+    if condvar_handle.is_null() {
+        return CondvarError::PtrNull.into();
+    }
+
+    match condvar_create() {
+        Ok(handle) => {
+            unsafe {
+                *condvar_handle = handle.into();
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_CondvarDelete")]
+pub extern "C" fn los_condvar_delete(condvar_handle: u32) -> u32 {
+    match condvar_delete(condvar_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_CondvarWait")]
+pub extern "C" fn los_condvar_wait(condvar_handle: u32, mux_handle: u32) -> u32 {
+    match condvar_wait(condvar_handle.into(), mux_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_CondvarTimedWait")]
+pub extern "C" fn los_condvar_timed_wait(
+    condvar_handle: u32,
+    mux_handle: u32,
+    timeout: u32,
+) -> u32 {
+    match condvar_timed_wait(condvar_handle.into(), mux_handle.into(), timeout) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_CondvarSignal")]
+pub extern "C" fn los_condvar_signal(condvar_handle: u32) -> u32 {
+    match condvar_signal(condvar_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_CondvarBroadcast")]
+pub extern "C" fn los_condvar_broadcast(condvar_handle: u32) -> u32 {
+    match condvar_broadcast(condvar_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,16 @@
+use crate::cpup::{CpupMode, system_cpu_usage, task_cpu_usage};
+
+/// C兼容的系统CPU占用率获取函数，返回千分比
+#[unsafe(export_name = "LOS_HistorySysCpuUsage")]
+pub extern "C" fn los_history_sys_cpu_usage(mode: u16) -> u32 {
+    system_cpu_usage(CpupMode::from(mode))
+}
+
+/// C兼容的任务CPU占用率获取函数，成功返回千分比，失败返回错误码
+#[unsafe(export_name = "LOS_HistoryTaskCpuUsage")]
+pub extern "C" fn los_history_task_cpu_usage(task_id: u32, mode: u16) -> u32 {
+    match task_cpu_usage(task_id, CpupMode::from(mode)) {
+        Ok(usage) => usage,
+        Err(err) => err.into(),
+    }
+}
@@ -3,9 +3,13 @@ use crate::{
     event::{
         core::{event_clear, event_destroy, event_init, event_poll, event_read, event_write},
         error::EventError,
-        types::EventCB,
+        group::{
+            event_group_clear, event_group_destroy, event_group_get, event_group_init,
+            event_group_set, event_group_sync, event_group_wait, event_group_wait_cleared,
+        },
+        types::{EventCB, EventGroupCB},
     },
-    result::SystemError,
+    result::{SystemError, SystemResult},
 };
 
 // C兼容接口
@@ -92,3 +96,129 @@ pub extern "C" fn los_event_clear(event_cb: *mut EventCB, events: u32) -> u32 {
     }
     OK
 }
+
+/// 将事件组操作结果写回调用者，`bits`为空时忽略事件组值
+fn event_group_output(result: SystemResult<u64>, bits: *mut u64) -> u32 {
+    match result {
+        Ok(value) => {
+            if !bits.is_null() {
+                unsafe { *bits = value };
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_EventGroupInit")]
+pub extern "C" fn los_event_group_init(group: *mut EventGroupCB) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+    unsafe {
+        event_group_init(&mut *group);
+    }
+    OK
+}
+
+#[unsafe(export_name = "LOS_EventGroupDestroy")]
+pub extern "C" fn los_event_group_destroy(group: *mut EventGroupCB) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    unsafe {
+        match event_group_destroy(&mut *group) {
+            Ok(()) => OK,
+            Err(e) => e.into(),
+        }
+    }
+}
+
+#[unsafe(export_name = "LOS_EventGroupGet")]
+pub extern "C" fn los_event_group_get(group: *mut EventGroupCB, bits: *mut u64) -> u32 {
+    if group.is_null() || bits.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    unsafe {
+        *bits = event_group_get(&*group);
+    }
+    OK
+}
+
+#[unsafe(export_name = "LOS_EventGroupSet")]
+pub extern "C" fn los_event_group_set(
+    group: *mut EventGroupCB,
+    set_bits: u64,
+    bits: *mut u64,
+) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    let result = unsafe { event_group_set(&mut *group, set_bits) };
+    event_group_output(Ok(result), bits)
+}
+
+#[unsafe(export_name = "LOS_EventGroupClear")]
+pub extern "C" fn los_event_group_clear(
+    group: *mut EventGroupCB,
+    clear_bits: u64,
+    bits: *mut u64,
+) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    let result = unsafe { event_group_clear(&mut *group, clear_bits) };
+    event_group_output(Ok(result), bits)
+}
+
+#[unsafe(export_name = "LOS_EventGroupWait")]
+pub extern "C" fn los_event_group_wait(
+    group: *mut EventGroupCB,
+    mask: u64,
+    mode: u32,
+    timeout: u32,
+    bits: *mut u64,
+) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    let result = unsafe { event_group_wait(&mut *group, mask, mode, timeout) };
+    event_group_output(result, bits)
+}
+
+#[unsafe(export_name = "LOS_EventGroupWaitCleared")]
+pub extern "C" fn los_event_group_wait_cleared(
+    group: *mut EventGroupCB,
+    mask: u64,
+    mode: u32,
+    timeout: u32,
+    bits: *mut u64,
+) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    let result = unsafe { event_group_wait_cleared(&mut *group, mask, mode, timeout) };
+    event_group_output(result, bits)
+}
+
+#[unsafe(export_name = "LOS_EventGroupSync")]
+pub extern "C" fn los_event_group_sync(
+    group: *mut EventGroupCB,
+    set_bits: u64,
+    wait_bits: u64,
+    timeout: u32,
+    bits: *mut u64,
+) -> u32 {
+    if group.is_null() {
+        return SystemError::Event(EventError::PtrNull).into();
+    }
+
+    let result = unsafe { event_group_sync(&mut *group, set_bits, wait_bits, timeout) };
+    event_group_output(result, bits)
+}
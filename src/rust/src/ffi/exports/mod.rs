@@ -1,9 +1,15 @@
+pub mod barrier;
 pub mod bitmap;
+pub mod condvar;
+#[cfg(feature = "cpup")]
+pub mod cpup;
 pub mod event;
 pub mod hwi;
 pub mod misc;
 pub mod mutex;
 pub mod queue;
+pub mod rwlock;
+pub mod select;
 pub mod semaphore;
 pub mod stack;
 pub mod task;
@@ -1,13 +1,18 @@
 use crate::{
     config::OK,
     mutex::{
-        core::{mutex_create, mutex_delete, mutex_init, mutex_pend, mutex_post},
+        core::{
+            mutex_consistent, mutex_create, mutex_create_with_attr, mutex_delete, mutex_init,
+            mutex_owner_policy_set, mutex_pend, mutex_post, mutex_wait_mode_set,
+        },
         error::MutexError,
+        types::{MutexAttr, MutexWaitMode},
     },
+    task::resource::OwnerDeadPolicy,
 };
 
 #[unsafe(export_name = "OsMuxInit")]
-pub extern "C" fn os_mux_init()  {
+pub extern "C" fn os_mux_init() {
     mutex_init();
 }
 
@@ -51,3 +56,53 @@ pub extern "C" fn los_mux_post(mux_handle: u32) -> u32 {
         Err(e) => e.into(),
     }
 }
+
+#[unsafe(export_name = "LOS_MuxCreateWithAttr")]
+pub extern "C" fn los_mux_create_with_attr(mux_handle: *mut u32, attr: *const MutexAttr) -> u32 {
+    if mux_handle.is_null() {
+        return MutexError::PtrNull.into();
+    }
+
+    // 属性为空时使用默认属性
+    let attr = unsafe { attr.as_ref() }.copied().unwrap_or_default();
+    match mutex_create_with_attr(&attr) {
+        Ok(handle) => {
+            unsafe {
+                *mux_handle = handle.into();
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_MuxConsistent")]
+pub extern "C" fn los_mux_consistent(mux_handle: u32) -> u32 {
+    match mutex_consistent(mux_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_MuxWaitModeSet")]
+pub extern "C" fn los_mux_wait_mode_set(mux_handle: u32, fifo: bool) -> u32 {
+    let mode = if fifo {
+        MutexWaitMode::Fifo
+    } else {
+        MutexWaitMode::Priority
+    };
+    match mutex_wait_mode_set(mux_handle.into(), mode) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_MuxOwnerPolicySet")]
+pub extern "C" fn los_mux_owner_policy_set(mux_handle: u32, policy: u32) -> u32 {
+    match OwnerDeadPolicy::try_from(policy)
+        .and_then(|policy| mutex_owner_policy_set(mux_handle.into(), policy))
+    {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
@@ -5,10 +5,14 @@ use crate::{
     queue::{
         error::QueueError,
         info::get_queue_info,
-        management::{create_queue, delete_queue, init_queue_system},
+        management::{
+            create_queue, create_queue_static, delete_queue, init_queue_system,
+            queue_owner_policy_set,
+        },
         operation::{queue_read, queue_write, queue_write_head},
         types::{QueueId, QueueInfo},
     },
+    task::resource::OwnerDeadPolicy,
 };
 
 #[unsafe(export_name = "OsQueueInit")]
@@ -35,6 +39,33 @@ pub extern "C" fn los_queue_create(len: u16, queue_id: *mut u32, max_msg_size: u
     }
 }
 
+/// 使用调用者提供的存储区域创建队列的FFI导出函数
+///
+/// 存储区域在队列删除前必须保持有效，删除队列时不会释放。
+#[unsafe(export_name = "LOS_QueueCreateStatic")]
+pub extern "C" fn los_queue_create_static(
+    len: u16,
+    queue_id: *mut u32,
+    max_msg_size: u16,
+    queue_mem: *mut u8,
+    mem_size: u32,
+) -> u32 {
+    // 检查指针是否为空
+    if queue_id.is_null() || queue_mem.is_null() {
+        return QueueError::CreatePtrNull.into();
+    }
+
+    let mem = unsafe { core::slice::from_raw_parts_mut(queue_mem, mem_size as usize) };
+    match create_queue_static(len as usize, max_msg_size as usize, mem) {
+        Ok(id) => {
+            // 创建成功，将ID写入输出参数
+            unsafe { *queue_id = id.into() };
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
 /// 删除队列的FFI导出函数
 #[unsafe(export_name = "LOS_QueueDelete")]
 pub extern "C" fn los_queue_delete(queue_id: u32) -> u32 {
@@ -128,3 +159,13 @@ pub extern "C" fn los_queue_write(
         }
     }
 }
+
+#[unsafe(export_name = "LOS_QueueOwnerPolicySet")]
+pub extern "C" fn los_queue_owner_policy_set(queue_id: u32, policy: u32) -> u32 {
+    match OwnerDeadPolicy::try_from(policy)
+        .and_then(|policy| queue_owner_policy_set(QueueId(queue_id), policy))
+    {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    config::OK,
+    rwlock::{
+        core::{
+            rwlock_create, rwlock_delete, rwlock_init, rwlock_rdlock, rwlock_unlock, rwlock_wrlock,
+        },
+        error::RwlockError,
+        types::RwlockPolicy,
+    },
+};
+
+#[unsafe(export_name = "OsRwlockInit")]
+pub extern "C" fn os_rwlock_init() {
+    rwlock_init();
+}
+
+/// 创建读写锁，`policy`为0时读者优先，为1时写者优先
+#[unsafe(export_name = "LOS_RwlockCreate")]
+pub extern "C" fn los_rwlock_create(rwlock_handle: *mut u32, policy: u32) -> u32 {
+    if rwlock_handle.is_null() {
+        return RwlockError::PtrNull.into();
+    }
+    let policy = match RwlockPolicy::try_from(policy) {
+        Ok(policy) => policy,
+        Err(e) => return e.into(),
+    };
+
+    match rwlock_create(policy) {
+        Ok(handle) => {
+            unsafe {
+                *rwlock_handle = handle.into();
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_RwlockDelete")]
+pub extern "C" fn los_rwlock_delete(rwlock_handle: u32) -> u32 {
+    match rwlock_delete(rwlock_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_RwlockRdLock")]
+pub extern "C" fn los_rwlock_rdlock(rwlock_handle: u32, timeout: u32) -> u32 {
+    match rwlock_rdlock(rwlock_handle.into(), timeout) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_RwlockWrLock")]
+pub extern "C" fn los_rwlock_wrlock(rwlock_handle: u32, timeout: u32) -> u32 {
+    match rwlock_wrlock(rwlock_handle.into(), timeout) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_RwlockUnlock")]
+pub extern "C" fn los_rwlock_unlock(rwlock_handle: u32) -> u32 {
+    match rwlock_unlock(rwlock_handle.into()) {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
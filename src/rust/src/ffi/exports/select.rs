@@ -0,0 +1,28 @@
+use crate::{
+    config::OK,
+    select::{core::wait_any, error::SelectError, types::SelectObject},
+};
+
+/// 等待`objects`中任意一个对象就绪，`index`返回就绪对象的下标
+#[unsafe(export_name = "LOS_WaitAny")]
+pub extern "C" fn los_wait_any(
+    objects: *const SelectObject,
+    count: u32,
+    timeout: u32,
+    index: *mut u32,
+) -> u32 {
+    if objects.is_null() || index.is_null() {
+        return SelectError::PtrNull.into();
+    }
+
+    let objects = unsafe { core::slice::from_raw_parts(objects, count as usize) };
+    match wait_any(objects, timeout) {
+        Ok(ready) => {
+            unsafe {
+                *index = ready as u32;
+            }
+            OK
+        }
+        Err(e) => e.into(),
+    }
+}
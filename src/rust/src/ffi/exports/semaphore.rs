@@ -5,10 +5,11 @@ use crate::{
     semaphore::{
         core::{
             create_binary_semaphore, create_semaphore, delete_semaphore, init_semaphore_system,
-            semaphore_pend, semaphore_post,
+            semaphore_owner_policy_set, semaphore_pend, semaphore_post,
         },
         error::SemaphoreError,
     },
+    task::resource::OwnerDeadPolicy,
 };
 
 /// 初始化信号量模块
@@ -125,3 +126,24 @@ pub extern "C" fn los_sem_post(sem_handle: u32) -> u32 {
         Err(e) => e.into(),
     }
 }
+
+/// 设置信号量创建者被删除时的处理策略
+///
+/// # 参数
+/// * `sem_handle` - 信号量句柄
+/// * `policy` - 处理策略，0为保留，1为唤醒等待者，2为删除信号量
+///
+/// # 返回值
+/// * `LOS_OK` - 成功
+/// * 其他错误码 - 失败原因
+///
+/// 对应C函数: LOS_SemOwnerPolicySet
+#[unsafe(export_name = "LOS_SemOwnerPolicySet")]
+pub extern "C" fn los_sem_owner_policy_set(sem_handle: u32, policy: u32) -> u32 {
+    match OwnerDeadPolicy::try_from(policy)
+        .and_then(|policy| semaphore_owner_policy_set(sem_handle.into(), policy))
+    {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
@@ -1,20 +1,39 @@
+#[cfg(feature = "edf")]
+use crate::task::edf::{TaskEdfParam, TaskEdfStats, task_edf_stats_get, task_edf_wait_next_period};
 #[cfg(feature = "task_monitor")]
-use crate::task::monitor::{TaskSwitchHook, register_task_switch_hook};
+use crate::task::monitor::{
+    TaskSwitchHandler, TaskSwitchHook, register_task_switch_hook, task_switch_handler_register,
+    task_switch_handler_unregister,
+};
+#[cfg(feature = "task_signal")]
+use crate::task::user_signal::{
+    SignalHandler, signal_block, signal_handler_set, signal_return, signal_send, signal_unblock,
+};
+#[cfg(feature = "time_slice")]
+use crate::task::{
+    manager::timeslice::{
+        priority_time_slice_get, priority_time_slice_set, task_time_slice_get, task_time_slice_set,
+    },
+    types::TASK_TIME_SLICE_DEFAULT,
+};
 use crate::{
     config::OK,
     result::SystemError,
     task::{
         error::TaskError,
         idle::idle_task_create,
-        info::get_current_task_id,
+        info::{TaskInfo, get_current_task_id, task_info_iter},
         manager::{
+            affinity::{task_cpu_affi_get, task_cpu_affi_set},
             create::{task_create, task_create_only, task_create_only_static, task_create_static},
             delay::{task_delay, task_yield},
             delete::task_delete,
+            exit::{task_detach, task_exit, task_join},
             init::init_task_system,
             priority::{get_task_priority, set_current_task_priority, set_task_priority},
             suspend::{task_resume, task_suspend},
         },
+        notify::{NotifyAction, task_notify, task_notify_clear, task_notify_take},
         signal::process_task_signals,
         sync::lock::{task_lock, task_unlock},
         types::{TaskEntryFunc, TaskInitParam},
@@ -31,6 +50,7 @@ pub struct CTaskInitParam {
     pub args: *mut c_void,
     pub stack_size: u32,
     pub name: *const c_char,
+    pub resved: u32,
 }
 
 impl From<CTaskInitParam> for TaskInitParam {
@@ -41,6 +61,11 @@ impl From<CTaskInitParam> for TaskInitParam {
             args: c_task_init_param.args,
             stack_size: c_task_init_param.stack_size,
             name: c_task_init_param.name,
+            resved: c_task_init_param.resved,
+            #[cfg(feature = "time_slice")]
+            time_slice: TASK_TIME_SLICE_DEFAULT,
+            #[cfg(feature = "edf")]
+            edf: Default::default(),
         }
     }
 }
@@ -53,6 +78,7 @@ impl From<TaskInitParam> for CTaskInitParam {
             args: rust_param.args,
             stack_size: rust_param.stack_size,
             name: rust_param.name,
+            resved: rust_param.resved,
         }
     }
 }
@@ -77,6 +103,62 @@ pub extern "C" fn los_task_create(task_id: *mut u32, c_init_param: *mut CTaskIni
     }
 }
 
+/// C兼容的指定时间片长度的任务创建函数
+///
+/// `time_slice`为0时任务在同优先级内先进先出，为0xFFFF时使用所在优先级的默认值。
+#[cfg(feature = "time_slice")]
+#[unsafe(export_name = "LOS_TaskCreateEx")]
+pub extern "C" fn los_task_create_ex(
+    task_id: *mut u32,
+    c_init_param: *mut CTaskInitParam,
+    time_slice: u16,
+) -> u32 {
+    if task_id.is_null() {
+        return SystemError::Task(TaskError::InvalidId).into();
+    }
+    if c_init_param.is_null() {
+        return SystemError::Task(TaskError::ParamNull).into();
+    }
+    unsafe {
+        let task_id_ref = &mut *task_id;
+        let c_param_ref = &*c_init_param;
+
+        let mut init_param: TaskInitParam = (*c_param_ref).into();
+        init_param.time_slice = time_slice;
+        match task_create(task_id_ref, &mut init_param) {
+            Ok(()) => OK,
+            Err(err) => err.into(),
+        }
+    }
+}
+
+/// C兼容的EDF任务创建函数
+#[cfg(feature = "edf")]
+#[unsafe(export_name = "LOS_TaskCreateEdf")]
+pub extern "C" fn los_task_create_edf(
+    task_id: *mut u32,
+    c_init_param: *mut CTaskInitParam,
+    edf_param: *const TaskEdfParam,
+) -> u32 {
+    if task_id.is_null() {
+        return SystemError::Task(TaskError::InvalidId).into();
+    }
+    if c_init_param.is_null() || edf_param.is_null() {
+        return SystemError::Task(TaskError::ParamNull).into();
+    }
+    unsafe {
+        let task_id_ref = &mut *task_id;
+        let c_param_ref = &*c_init_param;
+
+        let mut init_param: TaskInitParam = (*c_param_ref).into();
+        init_param.edf = *edf_param;
+        match task_create(task_id_ref, &mut init_param) {
+            Ok(()) => OK,
+            Err(err) => err.into(),
+        }
+    }
+}
+
 #[unsafe(export_name = "LOS_TaskCreateOnly")]
 pub extern "C" fn los_task_create_only(
     task_id: *mut u32,
@@ -171,6 +253,71 @@ pub extern "C" fn los_task_delete(task_id: u32) -> u32 {
     }
 }
 
+#[unsafe(export_name = "LOS_TaskExit")]
+pub extern "C" fn los_task_exit(exit_code: u32) -> ! {
+    task_exit(exit_code)
+}
+
+/// C兼容的任务等待函数，`exit_code`可以为空
+#[unsafe(export_name = "LOS_TaskJoin")]
+pub extern "C" fn los_task_join(task_id: u32, exit_code: *mut u32, timeout: u32) -> u32 {
+    match task_join(task_id, timeout) {
+        Ok(code) => {
+            if !exit_code.is_null() {
+                unsafe { *exit_code = code };
+            }
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_TaskDetach")]
+pub extern "C" fn los_task_detach(task_id: u32) -> u32 {
+    match task_detach(task_id) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_TaskNotify")]
+pub extern "C" fn los_task_notify(task_id: u32, value: u32, action: u32) -> u32 {
+    let action = match NotifyAction::try_from(action) {
+        Ok(action) => action,
+        Err(err) => return err.into(),
+    };
+    match task_notify(task_id, value, action) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_TaskNotifyTake")]
+pub extern "C" fn los_task_notify_take(clear: bool, timeout: u32, value: *mut u32) -> u32 {
+    match task_notify_take(clear, timeout) {
+        Ok(notified) => {
+            if !value.is_null() {
+                unsafe { *value = notified };
+            }
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
+#[unsafe(export_name = "LOS_TaskNotifyClear")]
+pub extern "C" fn los_task_notify_clear(task_id: u32, value: *mut u32) -> u32 {
+    match task_notify_clear(task_id) {
+        Ok(cleared) => {
+            if !value.is_null() {
+                unsafe { *value = cleared };
+            }
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
 #[unsafe(export_name = "LOS_TaskResume")]
 pub extern "C" fn los_task_resume(task_id: u32) -> u32 {
     match task_resume(task_id) {
@@ -206,10 +353,7 @@ pub extern "C" fn los_task_yield() -> u32 {
 /// C兼容的任务优先级获取函数
 #[unsafe(export_name = "LOS_TaskPriGet")]
 pub extern "C" fn los_task_pri_get(task_id: u32) -> u16 {
-    match get_task_priority(task_id) {
-        Ok(priority) => priority,
-        Err(_) => u16::MAX,
-    }
+    get_task_priority(task_id).unwrap_or(u16::MAX)
 }
 
 #[unsafe(export_name = "LOS_TaskPriSet")]
@@ -228,6 +372,164 @@ pub extern "C" fn los_cur_task_pri_set(task_prio: u16) -> u32 {
     }
 }
 
+#[unsafe(export_name = "LOS_TaskCpuAffiSet")]
+pub extern "C" fn los_task_cpu_affi_set(task_id: u32, cpu_affi_mask: u16) -> u32 {
+    match task_cpu_affi_set(task_id, cpu_affi_mask) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务CPU亲和性掩码获取函数，失败时返回0
+#[unsafe(export_name = "LOS_TaskCpuAffiGet")]
+pub extern "C" fn los_task_cpu_affi_get(task_id: u32) -> u16 {
+    task_cpu_affi_get(task_id).unwrap_or(0)
+}
+
+/// C兼容的任务信息获取函数
+///
+/// 按任务ID顺序向`infos`写入至多`max_num`个已创建任务的信息，实际写入的个数由`count`返回。
+#[unsafe(export_name = "LOS_TaskInfoGet")]
+pub extern "C" fn los_task_info_get(infos: *mut TaskInfo, max_num: u32, count: *mut u32) -> u32 {
+    if infos.is_null() || count.is_null() {
+        return TaskError::ParamNull.into();
+    }
+    let mut num = 0;
+    for info in task_info_iter().take(max_num as usize) {
+        unsafe { infos.add(num).write(info) };
+        num += 1;
+    }
+    unsafe { *count = num as u32 };
+    OK
+}
+
+/// C兼容的任务时间片长度设置函数
+#[cfg(feature = "time_slice")]
+#[unsafe(export_name = "LOS_TaskTimeSliceSet")]
+pub extern "C" fn los_task_time_slice_set(task_id: u32, ticks: u16) -> u32 {
+    match task_time_slice_set(task_id, ticks) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务时间片长度获取函数
+#[cfg(feature = "time_slice")]
+#[unsafe(export_name = "LOS_TaskTimeSliceGet")]
+pub extern "C" fn los_task_time_slice_get(task_id: u32, ticks: *mut u16) -> u32 {
+    if ticks.is_null() {
+        return TaskError::ParamNull.into();
+    }
+    match task_time_slice_get(task_id) {
+        Ok(len) => {
+            unsafe { *ticks = len };
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的优先级默认时间片长度设置函数
+#[cfg(feature = "time_slice")]
+#[unsafe(export_name = "LOS_PriorityTimeSliceSet")]
+pub extern "C" fn los_priority_time_slice_set(priority: u16, ticks: u16) -> u32 {
+    match priority_time_slice_set(priority, ticks) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的优先级默认时间片长度获取函数
+#[cfg(feature = "time_slice")]
+#[unsafe(export_name = "LOS_PriorityTimeSliceGet")]
+pub extern "C" fn los_priority_time_slice_get(priority: u16, ticks: *mut u16) -> u32 {
+    if ticks.is_null() {
+        return TaskError::ParamNull.into();
+    }
+    match priority_time_slice_get(priority) {
+        Ok(len) => {
+            unsafe { *ticks = len };
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
+#[cfg(feature = "edf")]
+#[unsafe(export_name = "LOS_TaskEdfWaitNextPeriod")]
+pub extern "C" fn los_task_edf_wait_next_period() -> u32 {
+    match task_edf_wait_next_period() {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的EDF任务运行统计获取函数
+#[cfg(feature = "edf")]
+#[unsafe(export_name = "LOS_TaskEdfStatsGet")]
+pub extern "C" fn los_task_edf_stats_get(task_id: u32, stats: *mut TaskEdfStats) -> u32 {
+    if stats.is_null() {
+        return TaskError::ParamNull.into();
+    }
+    match task_edf_stats_get(task_id) {
+        Ok(result) => {
+            unsafe { *stats = result };
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务信号处理函数设置函数
+#[cfg(feature = "task_signal")]
+#[unsafe(export_name = "LOS_TaskSignalHandlerSet")]
+pub extern "C" fn los_task_signal_handler_set(signo: u32, handler: SignalHandler) -> u32 {
+    match signal_handler_set(signo, handler) {
+        Ok(_) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务信号发送函数
+#[cfg(feature = "task_signal")]
+#[unsafe(export_name = "LOS_TaskSignalSend")]
+pub extern "C" fn los_task_signal_send(task_id: u32, signo: u32) -> u32 {
+    match signal_send(task_id, signo) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务信号屏蔽函数，`old_set`不为空时返回原来的屏蔽掩码
+#[cfg(feature = "task_signal")]
+#[unsafe(export_name = "LOS_TaskSignalBlock")]
+pub extern "C" fn los_task_signal_block(set: u32, old_set: *mut u32) -> u32 {
+    match signal_block(set) {
+        Ok(old) => {
+            if !old_set.is_null() {
+                unsafe { *old_set = old };
+            }
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务信号解除屏蔽函数，`old_set`不为空时返回原来的屏蔽掩码
+#[cfg(feature = "task_signal")]
+#[unsafe(export_name = "LOS_TaskSignalUnblock")]
+pub extern "C" fn los_task_signal_unblock(set: u32, old_set: *mut u32) -> u32 {
+    match signal_unblock(set) {
+        Ok(old) => {
+            if !old_set.is_null() {
+                unsafe { *old_set = old };
+            }
+            OK
+        }
+        Err(err) => err.into(),
+    }
+}
+
 #[unsafe(export_name = "LOS_TaskLock")]
 pub extern "C" fn los_task_lock() {
     task_lock();
@@ -243,12 +545,45 @@ pub extern "C" fn os_task_proc_signal() -> u32 {
     process_task_signals()
 }
 
+/// 体系结构回到开中断的任务上下文后调用，递送当前任务的用户信号
+#[cfg(feature = "task_signal")]
+#[unsafe(export_name = "OsTaskSignalReturn")]
+pub extern "C" fn os_task_signal_return() {
+    signal_return();
+}
+
 #[cfg(feature = "task_monitor")]
 #[unsafe(export_name = "LOS_TaskSwitchHookReg")]
 pub extern "C" fn los_task_switch_hook_reg(hook: TaskSwitchHook) {
     register_task_switch_hook(hook);
 }
 
+/// C兼容的任务切换处理函数注册函数
+#[cfg(feature = "task_monitor")]
+#[unsafe(export_name = "LOS_TaskSwitchHandlerRegister")]
+pub extern "C" fn los_task_switch_handler_register(handler: Option<TaskSwitchHandler>) -> u32 {
+    let Some(handler) = handler else {
+        return TaskError::ParamNull.into();
+    };
+    match task_switch_handler_register(handler) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// C兼容的任务切换处理函数注销函数
+#[cfg(feature = "task_monitor")]
+#[unsafe(export_name = "LOS_TaskSwitchHandlerUnregister")]
+pub extern "C" fn los_task_switch_handler_unregister(handler: Option<TaskSwitchHandler>) -> u32 {
+    let Some(handler) = handler else {
+        return TaskError::ParamNull.into();
+    };
+    match task_switch_handler_unregister(handler) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
 /// C兼容的当前任务ID获取函数
 #[unsafe(export_name = "LOS_CurTaskIDGet")]
 pub extern "C" fn los_cur_task_idget() -> u32 {
@@ -1,7 +1,12 @@
 use crate::tick::{
-    delay_microseconds, delay_milliseconds, get_cpu_cycles, get_current_nanoseconds, get_cycles_per_tick, get_tick_count, handle_tick, initialize_tick, milliseconds_to_ticks, start_tick, ticks_to_milliseconds
+    delay_microseconds, delay_milliseconds, get_cpu_cycles, get_current_nanoseconds,
+    get_cycles_per_tick, get_tick_count, handle_tick, initialize_tick, milliseconds_to_ticks,
+    start_tick, ticks_to_milliseconds,
 };
 
+#[cfg(feature = "tickless")]
+use crate::tick::tickless::{tickless_disable, tickless_enable};
+
 #[unsafe(export_name = "OsTickInit")]
 pub extern "C" fn os_tick_init() {
     initialize_tick();
@@ -17,6 +22,18 @@ pub extern "C" fn os_tick_handler() {
     handle_tick();
 }
 
+#[cfg(feature = "tickless")]
+#[unsafe(export_name = "LOS_TicklessEnable")]
+pub extern "C" fn los_tickless_enable() {
+    tickless_enable();
+}
+
+#[cfg(feature = "tickless")]
+#[unsafe(export_name = "LOS_TicklessDisable")]
+pub extern "C" fn los_tickless_disable() {
+    tickless_disable();
+}
+
 #[unsafe(export_name = "LOS_TickCountGet")]
 pub extern "C" fn los_tick_count_get() -> u64 {
     get_tick_count()
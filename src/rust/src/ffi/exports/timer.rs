@@ -1,6 +1,10 @@
 use crate::{
     config::OK,
-    timer::{timer_create, timer_delete, timer_init, timer_start, timer_stop, timer_time_get, TimerError, TimerHandler, TimerMode},
+    task::resource::OwnerDeadPolicy,
+    timer::{
+        TimerError, TimerHandler, TimerMode, timer_create, timer_delete, timer_init,
+        timer_owner_policy_set, timer_start, timer_stop, timer_time_get,
+    },
 };
 
 #[unsafe(export_name = "OsSwtmrInit")]
@@ -76,3 +80,13 @@ pub fn los_swtmr_time_get(timer_id: u32, tick: *mut u32) -> u32 {
         Err(e) => e.into(), // 错误转换为对应的错误码
     }
 }
+
+#[unsafe(export_name = "LOS_SwtmrOwnerPolicySet")]
+pub extern "C" fn los_swtmr_owner_policy_set(timer_id: u32, policy: u32) -> u32 {
+    match OwnerDeadPolicy::try_from(policy)
+        .and_then(|policy| timer_owner_policy_set(timer_id.into(), policy))
+    {
+        Ok(()) => OK,
+        Err(e) => e.into(),
+    }
+}
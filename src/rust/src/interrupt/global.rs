@@ -1,4 +1,6 @@
-use crate::interrupt::types::InterruptController;
+use crate::{
+    config::KERNEL_CORE_NUM, ffi::bindings::arch_curr_cpuid, interrupt::types::InterruptController,
+};
 use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
 
 /// 全局中断控制器操作接口（使用原子指针确保线程安全）
@@ -20,7 +22,8 @@ pub fn get_interrupt_controller() -> Option<&'static InterruptController> {
 }
 
 #[unsafe(export_name = "g_intCount")]
-pub static IRQ_NESTING_COUNTS: [AtomicU32; 1] = [AtomicU32::new(0)];
+pub static IRQ_NESTING_COUNTS: [AtomicU32; KERNEL_CORE_NUM] =
+    [const { AtomicU32::new(0) }; KERNEL_CORE_NUM];
 
 /// 获取当前CPU的中断嵌套计数
 ///
@@ -28,7 +31,7 @@ pub static IRQ_NESTING_COUNTS: [AtomicU32; 1] = [AtomicU32::new(0)];
 /// 当前CPU的中断嵌套层数
 #[inline]
 pub fn irq_nesting_count_get() -> u32 {
-    IRQ_NESTING_COUNTS[0].load(Ordering::Acquire)
+    IRQ_NESTING_COUNTS[arch_curr_cpuid() as usize].load(Ordering::Acquire)
 }
 
 /// 设置当前CPU的中断嵌套计数
@@ -37,7 +40,7 @@ pub fn irq_nesting_count_get() -> u32 {
 /// * `val` - 要设置的计数值
 #[inline]
 pub fn irq_nesting_count_set(val: u32) {
-    IRQ_NESTING_COUNTS[0].store(val, Ordering::Release);
+    IRQ_NESTING_COUNTS[arch_curr_cpuid() as usize].store(val, Ordering::Release);
 }
 
 /// 增加当前CPU的中断嵌套计数
@@ -46,7 +49,7 @@ pub fn irq_nesting_count_set(val: u32) {
 /// 增加后的计数值
 #[inline]
 pub fn irq_nesting_count_inc() {
-    IRQ_NESTING_COUNTS[0].fetch_add(1, Ordering::AcqRel);
+    IRQ_NESTING_COUNTS[arch_curr_cpuid() as usize].fetch_add(1, Ordering::AcqRel);
 }
 
 /// 减少当前CPU的中断嵌套计数
@@ -55,5 +58,5 @@ pub fn irq_nesting_count_inc() {
 /// 减少后的计数值
 #[inline]
 pub fn irq_nesting_count_dec() {
-    IRQ_NESTING_COUNTS[0].fetch_sub(1, Ordering::AcqRel);
+    IRQ_NESTING_COUNTS[arch_curr_cpuid() as usize].fetch_sub(1, Ordering::AcqRel);
 }
@@ -15,16 +15,23 @@ pub mod types;
 
 #[inline]
 pub fn disable_interrupts() -> u32 {
-    arch_int_lock()
+    let int_save = arch_int_lock();
+    #[cfg(feature = "smp")]
+    crate::spinlock::kernel_lock();
+    int_save
 }
 
 #[inline]
 pub fn enable_interrupts() -> u32 {
+    #[cfg(feature = "smp")]
+    crate::spinlock::kernel_unlock_all();
     arch_int_unlock()
 }
 
 #[inline]
 pub fn restore_interrupt_state(int_save: u32) {
+    #[cfg(feature = "smp")]
+    crate::spinlock::kernel_unlock();
     arch_int_restore(int_save);
 }
 
@@ -105,7 +112,7 @@ pub fn register_interrupt(
         Err(err) => {
             // 如果设置优先级失败，清理已创建的中断
             let _ = unregister_interrupt_handler(hwi_form, hwi_num);
-            return Err(err);
+            Err(err)
         }
     }
 }
@@ -167,6 +174,10 @@ pub fn handle_interrupt(_hwi_num: u32, hwi_form: &mut InterruptHandler) {
     // 增加中断嵌套计数
     irq_nesting_count_inc();
 
+    // 结束Tickless休眠，补偿经过的Tick
+    #[cfg(feature = "tickless")]
+    crate::tick::tickless::tickless_update();
+
     // 增加响应计数
     hwi_form.increment_count();
 
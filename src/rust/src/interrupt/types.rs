@@ -130,6 +130,7 @@ impl InterruptController {
     }
 
     /// 获取中断处理表单
+    #[allow(clippy::mut_from_ref)]
     pub fn get_handle_form_with_check(&self, hwi_num: u32) -> SystemResult<&mut InterruptHandler> {
         match self.get_handle_form {
             Some(func) => match unsafe { func(hwi_num).as_mut() } {
@@ -142,11 +143,8 @@ impl InterruptController {
 
     /// 安全处理中断
     pub fn handle_irq_with_check(&self) {
-        match self.handle_irq {
-            Some(func) => {
-                func();
-            }
-            None => {}
+        if let Some(func) = self.handle_irq {
+            func();
         }
     }
 }
@@ -1,27 +1,46 @@
 #![no_std]
 #![no_main]
+// C接口及内核内部函数按LiteOS的约定直接接收裸指针
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+#[cfg(feature = "sim")]
+extern crate std;
+// 仿真环境下以std的打印宏代替semihosting
+#[cfg(feature = "sim")]
+extern crate std as semihosting;
 
 use semihosting::println;
 
 use crate::interrupt::{disable_interrupts, restore_interrupt_state};
 extern crate alloc;
 
-mod config;
-mod event;
-mod ffi;
+pub mod barrier;
+pub mod condvar;
+pub mod config;
+#[cfg(feature = "cpup")]
+pub mod cpup;
+pub mod event;
+pub mod ffi;
 mod interrupt;
-mod memory;
-mod mutex;
+pub mod memory;
+#[cfg(feature = "smp")]
+mod mp;
+pub mod mutex;
 mod percpu;
-mod queue;
-mod result;
-mod semaphore;
+pub mod queue;
+pub mod result;
+pub mod rwlock;
+pub mod select;
+pub mod semaphore;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "smp")]
+mod spinlock;
 mod stack;
-mod task;
-mod tick;
-mod timer;
+pub mod task;
+pub mod tick;
+pub mod timer;
 mod utils;
-mod ramfs;
 
 #[unsafe(export_name = "HelloRust")]
 pub extern "C" fn hello_rust() {
@@ -33,8 +52,7 @@ critical_section::set_impl!(MyCriticalSection);
 
 unsafe impl critical_section::Impl for MyCriticalSection {
     unsafe fn acquire() -> critical_section::RawRestoreState {
-        let state = disable_interrupts();
-        state
+        disable_interrupts()
     }
 
     unsafe fn release(restore_state: critical_section::RawRestoreState) {
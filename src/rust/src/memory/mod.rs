@@ -1,32 +1,65 @@
 use core::alloc::Layout;
 use core::ffi::c_void;
-use core::ptr::{addr_of, null_mut};
+#[cfg(not(feature = "sim"))]
+use core::ptr::addr_of;
+use core::ptr::null_mut;
+
+use crate::{
+    config::OK,
+    container_of,
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        global::get_tcb_from_id,
+        resource::{OWNER_NONE, OwnerDeadPolicy, current_owner},
+        types::TaskCB,
+    },
+    utils::list::LinkedList,
+};
 
 use linked_list_allocator::LockedHeap;
+#[cfg(not(feature = "sim"))]
 use semihosting::println;
 
 #[repr(C)]
 struct MemHeader {
-    size: usize,  // 用户请求的内存大小 (不包含 Header 本身)
-    align: usize, // 用户请求的对齐边界
+    size: usize,                   // 用户请求的内存大小 (不包含 Header 本身)
+    align: usize,                  // 用户请求的对齐边界
+    owner: u32,                    // 分配该内存块的任务
+    owner_policy: OwnerDeadPolicy, // 拥有者被删除时的处理策略
+    owner_node: LinkedList,        // 挂入拥有者的 mem_list
 }
 
 const HEADER_SIZE: usize = core::mem::size_of::<MemHeader>();
 const MIN_HEADER_ALIGN: usize = core::mem::align_of::<MemHeader>();
-const ALIGNED_HEADER_SIZE: usize = (HEADER_SIZE + MIN_HEADER_ALIGN - 1) & !(MIN_HEADER_ALIGN - 1);
+
+// Header 紧贴在用户指针之前，用户数据按对齐边界放置
+#[inline]
+const fn header_offset(align: usize) -> usize {
+    let actual_align = if align > MIN_HEADER_ALIGN {
+        align
+    } else {
+        MIN_HEADER_ALIGN
+    };
+    HEADER_SIZE.next_multiple_of(actual_align)
+}
 
 #[unsafe(export_name = "g_sys_mem_addr_end")]
 pub static mut G_SYS_MEM_ADDR_END: usize = 0;
 
+#[cfg(not(feature = "sim"))]
 unsafe extern "C" {
     pub static __heap_start: u8;
 }
 
+#[cfg(not(feature = "sim"))]
 #[inline]
 pub const fn os_sys_mem_addr() -> *mut c_void {
     addr_of!(__heap_start) as *mut c_void
 }
 
+#[cfg(not(feature = "sim"))]
 #[inline]
 pub fn os_sys_mem_size() -> usize {
     let sys_mem_end = unsafe { G_SYS_MEM_ADDR_END };
@@ -34,34 +67,38 @@ pub fn os_sys_mem_size() -> usize {
     sys_mem_end - aligned_heap_start
 }
 
+// 仿真环境下内存由宿主分配器提供，这里只给出名义上的堆大小
+#[cfg(feature = "sim")]
+#[inline]
+pub fn os_sys_mem_size() -> usize {
+    crate::sim::SIM_HEAP_SIZE
+}
+
 // 全局分配器
-#[global_allocator]
+#[cfg_attr(not(feature = "sim"), global_allocator)]
 static ALLOCATOR: LockedHeap = LockedHeap::empty();
 
 // 初始化分配器
 #[unsafe(export_name = "OsMemSystemInit")]
 pub extern "C" fn init_allocator() -> u32 {
-    println!("Initializing allocator with static heap...");
-    unsafe {
-        ALLOCATOR
-            .lock()
-            .init(os_sys_mem_addr() as *mut u8, os_sys_mem_size());
+    #[cfg(not(feature = "sim"))]
+    {
+        println!("Initializing allocator with static heap...");
+        unsafe {
+            ALLOCATOR
+                .lock()
+                .init(os_sys_mem_addr() as *mut u8, os_sys_mem_size());
+        }
     }
     0
 }
 
-// 分配内存的 C 接口
-#[unsafe(export_name = "LOS_MemAlloc")]
-pub extern "C" fn malloc(size: usize) -> *mut c_void {
-    if size == 0 {
-        return null_mut();
-    }
-
-    let requested_align = 8;
+fn alloc_with_header(size: usize, requested_align: usize) -> *mut c_void {
     let actual_align = core::cmp::max(MIN_HEADER_ALIGN, requested_align);
 
     // 总共需要分配的内存大小：Header 空间 + 用户数据空间
-    let total_alloc_size = ALIGNED_HEADER_SIZE + size;
+    let offset = header_offset(requested_align);
+    let total_alloc_size = offset + size;
 
     let layout = match Layout::from_size_align(total_alloc_size, actual_align) {
         Ok(l) => l,
@@ -74,21 +111,35 @@ pub extern "C" fn malloc(size: usize) -> *mut c_void {
             return null_mut(); // 分配失败
         }
 
-        // 在分配的内存起始处写入 Header
-        let header_ptr = alloc_ptr as *mut MemHeader;
+        // 在用户数据之前写入 Header
+        let user_ptr = alloc_ptr.add(offset);
+        let header_ptr = user_ptr.sub(HEADER_SIZE) as *mut MemHeader;
         core::ptr::write(
             header_ptr,
             MemHeader {
-                size: size,
+                size,
                 align: requested_align,
+                owner: current_owner(),
+                owner_policy: OwnerDeadPolicy::Orphan,
+                owner_node: LinkedList::UNINIT,
             },
         );
 
         // 返回给 C 的指针是跳过 Header 的地址
-        alloc_ptr.add(ALIGNED_HEADER_SIZE) as *mut c_void
+        user_ptr as *mut c_void
     }
 }
 
+// 分配内存的 C 接口
+#[unsafe(export_name = "LOS_MemAlloc")]
+pub extern "C" fn malloc(size: usize) -> *mut c_void {
+    if size == 0 {
+        return null_mut();
+    }
+
+    alloc_with_header(size, 8)
+}
+
 #[unsafe(export_name = "LOS_MemAllocAlign")]
 pub extern "C" fn memalign(size: usize, boundary: usize) -> *mut c_void {
     if size == 0 {
@@ -99,35 +150,7 @@ pub extern "C" fn memalign(size: usize, boundary: usize) -> *mut c_void {
         return null_mut();
     }
 
-    let requested_align = boundary;
-    let actual_align = core::cmp::max(MIN_HEADER_ALIGN, requested_align);
-
-    let total_alloc_size = ALIGNED_HEADER_SIZE + size;
-
-    let layout = match Layout::from_size_align(total_alloc_size, actual_align) {
-        Ok(l) => l,
-        Err(_) => return null_mut(),
-    };
-
-    unsafe {
-        let alloc_ptr = alloc::alloc::alloc(layout);
-        if alloc_ptr.is_null() {
-            return null_mut();
-        }
-
-        // 在分配的内存起始处写入 Header
-        let header_ptr = alloc_ptr as *mut MemHeader;
-        core::ptr::write(
-            header_ptr,
-            MemHeader {
-                size: size,
-                align: requested_align,
-            },
-        );
-
-        // 返回给 C 的指针是跳过 Header 的地址
-        alloc_ptr.add(ALIGNED_HEADER_SIZE) as *mut c_void
-    }
+    alloc_with_header(size, boundary)
 }
 
 // 释放内存的 C 接口
@@ -137,16 +160,24 @@ pub extern "C" fn free(ptr: *mut c_void) {
         return;
     }
     unsafe {
-        // 通过用户指针回溯到实际分配的起始地址 (包含 Header)
-        let actual_alloc_ptr = (ptr as *mut u8).sub(ALIGNED_HEADER_SIZE);
-        let header_ptr = actual_alloc_ptr as *mut MemHeader;
+        // 通过用户指针回溯到 Header
+        let header_ptr = (ptr as *mut u8).sub(HEADER_SIZE) as *mut MemHeader;
 
         // 读取 Header 信息以构造正确的 Layout
-        let header = header_ptr.as_ref().unwrap();
+        let header = header_ptr.as_mut().unwrap();
         let original_user_size = header.size;
         let original_user_align = header.align;
 
-        let total_size = ALIGNED_HEADER_SIZE + original_user_size;
+        // 从拥有者的 mem_list 中摘除
+        if header.owner_policy == OwnerDeadPolicy::Destroy {
+            let int_save = disable_interrupts();
+            LinkedList::remove(&raw mut header.owner_node);
+            restore_interrupt_state(int_save);
+        }
+
+        let offset = header_offset(original_user_align);
+        let actual_alloc_ptr = (ptr as *mut u8).sub(offset);
+        let total_size = offset + original_user_size;
         let total_align = core::cmp::max(MIN_HEADER_ALIGN, original_user_align);
 
         let layout = match Layout::from_size_align(total_size, total_align) {
@@ -157,6 +188,58 @@ pub extern "C" fn free(ptr: *mut c_void) {
     }
 }
 
+/// 设置内存块的拥有者被删除时的处理策略，默认为[`OwnerDeadPolicy::Orphan`]
+///
+/// 策略为[`OwnerDeadPolicy::Destroy`]的内存块在分配它的任务被删除时自动释放，不支持
+/// [`OwnerDeadPolicy::Release`]。
+pub fn mem_owner_policy_set(ptr: *mut c_void, policy: OwnerDeadPolicy) -> SystemResult<()> {
+    if ptr.is_null() {
+        return Err(SystemError::Task(TaskError::ParamNull));
+    }
+    if policy == OwnerDeadPolicy::Release {
+        return Err(SystemError::Task(TaskError::OwnerPolicyInvalid));
+    }
+
+    let header = unsafe { &mut *((ptr as *mut u8).sub(HEADER_SIZE) as *mut MemHeader) };
+
+    // 不属于任何任务的内存块无法随任务释放
+    if policy == OwnerDeadPolicy::Destroy && header.owner == OWNER_NONE {
+        return Err(SystemError::Task(TaskError::OwnerPolicyInvalid));
+    }
+
+    let int_save = disable_interrupts();
+    if header.owner_policy == OwnerDeadPolicy::Destroy {
+        LinkedList::remove(&raw mut header.owner_node);
+    }
+    header.owner_policy = policy;
+    if policy == OwnerDeadPolicy::Destroy {
+        let owner = get_tcb_from_id(header.owner);
+        LinkedList::tail_insert(&raw mut owner.mem_list, &raw mut header.owner_node);
+    }
+    restore_interrupt_state(int_save);
+
+    Ok(())
+}
+
+#[unsafe(export_name = "LOS_MemOwnerPolicySet")]
+pub extern "C" fn los_mem_owner_policy_set(ptr: *mut c_void, policy: u32) -> u32 {
+    match OwnerDeadPolicy::try_from(policy).and_then(|policy| mem_owner_policy_set(ptr, policy)) {
+        Ok(()) => OK,
+        Err(err) => err.into(),
+    }
+}
+
+/// 释放被删除任务的内存块，须在关中断后调用
+pub(crate) fn mem_owner_dead(task_cb: &mut TaskCB, count: &mut u16) {
+    let list = &raw mut task_cb.mem_list;
+    while !LinkedList::is_empty(list) {
+        let header = container_of!(LinkedList::first(list), MemHeader, owner_node);
+        let ptr = unsafe { (header as *mut u8).add(HEADER_SIZE) };
+        free(ptr as *mut c_void);
+        *count += 1;
+    }
+}
+
 #[unsafe(export_name = "LOS_MemTotalSizeGet")]
 pub extern "C" fn get_total_size() -> usize {
     os_sys_mem_size()
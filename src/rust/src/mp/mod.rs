@@ -0,0 +1,27 @@
+//! 多核协作：核间中断
+
+use crate::{
+    ffi::bindings::{arch_curr_cpuid, hal_irq_send_ipi},
+    percpu::{cpu_online_mask, os_percpu_get},
+};
+
+/// 核间中断：唤醒
+#[allow(dead_code)]
+pub const MP_IPI_WAKEUP: u32 = 0;
+/// 核间中断：调度
+pub const MP_IPI_SCHEDULE: u32 = 1;
+
+/// 通知目标CPU集合重新调度，当前核与离线核被忽略
+pub fn mp_schedule(target: u32) {
+    let target = target & cpu_online_mask() & !(1 << arch_curr_cpuid());
+    if target != 0 {
+        hal_irq_send_ipi(target, MP_IPI_SCHEDULE);
+    }
+}
+
+/// 调度核间中断处理函数
+#[unsafe(export_name = "OsMpScheduleHandler")]
+pub extern "C" fn mp_schedule_handler() {
+    // 中断退出时进行调度
+    os_percpu_get().needs_reschedule = 1;
+}
@@ -1,22 +1,30 @@
 use crate::{
-    config::WAIT_FOREVER,
+    config::{TASK_PRIORITY_LOWEST, WAIT_FOREVER},
     ffi::bindings::get_current_task,
     interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
     percpu::can_preempt_in_scheduler,
     println_debug,
-    result::SystemResult,
+    result::{SystemError, SystemResult},
     task::{
+        error::TaskError,
+        resource::OwnerDeadPolicy,
         sched::{schedule, schedule_reschedule},
-        sync::wait::{task_wait, task_wake},
-        types::{TaskCB, TaskStatus},
+        sync::wait::{
+            task_wait, task_wait_interrupted, task_wait_owner_dead, task_wake,
+            task_wake_all_owner_dead,
+        },
+        types::{TaskCB, TaskPendObject, TaskStatus},
     },
+    utils::list::LinkedList,
 };
 
 use super::{
     error::MutexError,
     global::MutexManager,
-    priority::PriorityInheritance,
-    types::{MutexControlBlock, MutexId},
+    priority::{PriorityCeiling, PriorityInheritance},
+    types::{
+        MutexAttr, MutexConsistency, MutexControlBlock, MutexId, MutexProtocol, MutexWaitMode,
+    },
     wait::WaitManager,
 };
 
@@ -27,10 +35,11 @@ fn pend_operation(
     int_save: &mut u32,
 ) -> SystemResult<()> {
     // 找到合适的等待位置
+    let mutex_id = mutex.get_id();
     let wait_pos = WaitManager::find_wait_position(run_task, mutex);
 
     // 将任务加入等待队列
-    task_wait(wait_pos, timeout);
+    task_wait(wait_pos, TaskPendObject::Mutex(mutex_id.into()), timeout);
 
     // 立即调度
     schedule_reschedule();
@@ -40,19 +49,38 @@ fn pend_operation(
 
     *int_save = disable_interrupts();
 
+    // 持有者被删除，互斥锁已被释放或删除
+    if task_wait_owner_dead(run_task) {
+        // 健壮互斥锁未被标记一致便已解锁
+        if mutex.consistency == MutexConsistency::NotRecoverable {
+            return Err(MutexError::NotRecoverable.into());
+        }
+        return Err(SystemError::Task(TaskError::OwnerDead));
+    }
+
+    // 被信号打断时与超时一样撤销对持有者的优先级继承
+    if task_wait_interrupted(run_task) {
+        PriorityInheritance::restore_priority_on_timeout(mutex.get_owner());
+        return Err(SystemError::Task(TaskError::Interrupted));
+    }
+
     // 检查是否超时
     if run_task.task_status.contains(TaskStatus::TIMEOUT) {
         run_task.task_status.remove(TaskStatus::TIMEOUT);
         // 如果不是永久等待，需要恢复优先级
         if timeout != WAIT_FOREVER {
-            PriorityInheritance::restore_priority_on_timeout(run_task, mutex.get_owner());
+            PriorityInheritance::restore_priority_on_timeout(mutex.get_owner());
         }
-        return Err(MutexError::Timeout.into());
+        Err(MutexError::Timeout.into())
     } else {
         if timeout != WAIT_FOREVER {
-            PriorityInheritance::restore_priority_on_timeout(run_task, mutex.get_owner());
+            PriorityInheritance::restore_priority_on_timeout(mutex.get_owner());
         }
-        return Ok(());
+        // 从被删除的持有者手中接管了健壮互斥锁
+        if mutex.consistency == MutexConsistency::OwnerDied {
+            return Err(MutexError::OwnerDied.into());
+        }
+        Ok(())
     }
 }
 
@@ -72,13 +100,74 @@ fn post_operation(run_task: &mut TaskCB, mutex: &mut MutexControlBlock) -> bool
     // 设置新的所有者
     mutex.set_count(1);
     mutex.set_owner(resumed_task);
+    if mutex.is_protect() {
+        PriorityCeiling::raise(resumed_task);
+    }
 
     // 唤醒任务
     task_wake(resumed_task);
+    PriorityInheritance::inherit_from_waiters(resumed_task);
 
     true
 }
 
+/// 健壮互斥锁未被标记一致便解锁，唤醒所有等待者并使其不可恢复
+fn not_recoverable_operation(run_task: &mut TaskCB, mutex: &mut MutexControlBlock) -> bool {
+    mutex.consistency = MutexConsistency::NotRecoverable;
+    let woken = task_wake_all_owner_dead(&mut mutex.mux_list);
+
+    // 撤销等待者带来的优先级继承
+    if !run_task.priority_bitmap.is_empty() {
+        PriorityInheritance::restore_priority_complex(run_task, mutex);
+    }
+    mutex.clear_owner();
+
+    woken
+}
+
+/// 计数减到0后释放互斥锁，须在关中断后调用，返回是否需要调度
+fn release_operation(run_task: &mut TaskCB, mutex: &mut MutexControlBlock) -> bool {
+    // 执行释放操作
+    let woken = if mutex.consistency == MutexConsistency::OwnerDied {
+        not_recoverable_operation(run_task, mutex)
+    } else {
+        post_operation(run_task, mutex)
+    };
+
+    // 交出互斥锁后再撤销天花板提升，已释放的互斥锁不再参与计算
+    let lowered = mutex.is_protect() && PriorityCeiling::restore(run_task);
+    lowered || woken
+}
+
+/// 完全释放当前任务持有的互斥锁，须在关中断后调用
+///
+/// 返回释放前的递归加锁次数，由调用者在重新加锁后恢复。
+pub(crate) fn mutex_release_all(run_task: &mut TaskCB, id: MutexId) -> SystemResult<u16> {
+    let mutex = MutexManager::get_mutex_mut(id)?;
+    if mutex.is_unused() || !mutex.matches_id(id) {
+        return Err(MutexError::Invalid.into());
+    }
+    if mutex.get_count() == 0 || !mutex.is_owner(run_task) {
+        return Err(MutexError::Invalid.into());
+    }
+    let count = mutex.get_count();
+    mutex.set_count(0);
+    release_operation(run_task, mutex);
+    Ok(count)
+}
+
+/// 恢复重新获取的互斥锁的递归加锁次数
+pub(crate) fn mutex_restore_count(id: MutexId, count: u16) {
+    let int_save = disable_interrupts();
+    if let Ok(mutex) = MutexManager::get_mutex_mut(id) {
+        let run_task = get_current_task();
+        if !mutex.is_unused() && mutex.matches_id(id) && mutex.is_owner(run_task) {
+            mutex.set_count(count);
+        }
+    }
+    restore_interrupt_state(int_save);
+}
+
 /// 互斥锁系统初始化
 pub fn mutex_init() {
     MutexManager::initialize();
@@ -86,6 +175,23 @@ pub fn mutex_init() {
 
 /// 创建互斥锁
 pub fn mutex_create() -> SystemResult<MutexId> {
+    mutex_create_with_attr(&MutexAttr::default())
+}
+
+/// 按指定属性创建互斥锁
+///
+/// 使用[`MutexProtocol::Protect`](super::types::MutexProtocol::Protect)时天花板优先级在创建后
+/// 不能修改，基础优先级高于天花板的任务加锁时返回[`MutexError::CeilingViolated`]。
+pub fn mutex_create_with_attr(attr: &MutexAttr) -> SystemResult<MutexId> {
+    let mut attr = *attr;
+    if attr.protocol == MutexProtocol::Protect {
+        if attr.ceiling > TASK_PRIORITY_LOWEST {
+            return Err(MutexError::CeilingInvalid.into());
+        }
+    } else {
+        attr.ceiling = 0;
+    }
+
     let int_save = disable_interrupts();
 
     if !MutexManager::has_available_mutex() {
@@ -93,6 +199,9 @@ pub fn mutex_create() -> SystemResult<MutexId> {
         return Err(MutexError::AllBusy.into());
     };
     let id = MutexManager::allocate();
+    if let Ok(mutex) = MutexManager::get_mutex_mut(id) {
+        mutex.apply_attr(&attr);
+    }
 
     restore_interrupt_state(int_save);
     Ok(id)
@@ -114,11 +223,92 @@ pub fn mutex_delete(id: MutexId) -> SystemResult<()> {
         }
         Err(e) => {
             restore_interrupt_state(int_save);
-            return Err(e);
+            Err(e)
         }
     }
 }
 
+/// 设置持有者被删除时的处理策略，默认为[`OwnerDeadPolicy::Release`]
+///
+/// 持有者被删除时等待者都以[`TaskError::OwnerDead`]返回，`Release`使互斥锁回到未锁定状态，
+/// `Destroy`删除互斥锁。互斥锁不支持`Orphan`，健壮互斥锁不使用该策略。
+pub fn mutex_owner_policy_set(id: MutexId, policy: OwnerDeadPolicy) -> SystemResult<()> {
+    if policy == OwnerDeadPolicy::Orphan {
+        return Err(SystemError::Task(TaskError::OwnerPolicyInvalid));
+    }
+    let mutex = MutexManager::get_mutex_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    if mutex.is_unused() || !mutex.matches_id(id) {
+        restore_interrupt_state(int_save);
+        return Err(MutexError::Invalid.into());
+    }
+    mutex.owner_policy = policy;
+
+    restore_interrupt_state(int_save);
+    Ok(())
+}
+
+/// 设置互斥锁等待队列的排序方式，有任务等待时不能修改
+pub fn mutex_wait_mode_set(id: MutexId, mode: MutexWaitMode) -> SystemResult<()> {
+    let mutex = MutexManager::get_mutex_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = if mutex.is_unused() || !mutex.matches_id(id) {
+        Err(MutexError::Invalid.into())
+    } else if mutex.has_waiting_tasks() {
+        Err(MutexError::Pended.into())
+    } else {
+        mutex.wait_mode = mode;
+        Ok(())
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 处理被删除任务持有的互斥锁，须在关中断后调用，返回是否唤醒了任务
+pub(crate) fn mutex_owner_dead(task_cb: &mut TaskCB, count: &mut u16) -> bool {
+    let mut woken = false;
+    // 每个分支都会把互斥锁移出任务的held_mutexes
+    let list = &raw mut task_cb.held_mutexes;
+    while !LinkedList::is_empty(list) {
+        let mutex = MutexControlBlock::from_held_node(LinkedList::first(list));
+        *count += 1;
+
+        // 健壮互斥锁交给第一个等待者，由其决定所保护的状态能否恢复
+        if mutex.robust {
+            mutex.consistency = MutexConsistency::OwnerDied;
+            if mutex.has_waiting_tasks() {
+                let resumed_task = TaskCB::from_pend_list(mutex.mux_list.next);
+                mutex.set_count(1);
+                mutex.set_owner(resumed_task);
+                if mutex.is_protect() {
+                    PriorityCeiling::raise(resumed_task);
+                }
+                task_wake(resumed_task);
+                PriorityInheritance::inherit_from_waiters(resumed_task);
+                woken = true;
+            } else {
+                mutex.set_count(0);
+                mutex.clear_owner();
+            }
+            continue;
+        }
+
+        woken |= task_wake_all_owner_dead(&mut mutex.mux_list);
+        mutex.set_count(0);
+        mutex.clear_owner();
+        if mutex.owner_policy == OwnerDeadPolicy::Destroy {
+            let id = mutex.get_id();
+            let _ = MutexManager::deallocate(mutex, id);
+        }
+    }
+    woken
+}
+
 /// 获取互斥锁（加锁）
 pub fn mutex_pend(id: MutexId, timeout: u32) -> SystemResult<()> {
     let mutex = MutexManager::get_mutex_mut(id)?;
@@ -132,18 +322,38 @@ pub fn mutex_pend(id: MutexId, timeout: u32) -> SystemResult<()> {
     }
 
     if mutex.is_unused() || !mutex.matches_id(id) {
+        restore_interrupt_state(int_save);
         return Err(MutexError::Invalid.into());
     }
 
     if is_interrupt_active() {
+        restore_interrupt_state(int_save);
         return Err(MutexError::PendInterrupt.into());
     }
 
+    // 不可恢复的健壮互斥锁不能再被获取
+    if mutex.consistency == MutexConsistency::NotRecoverable {
+        restore_interrupt_state(int_save);
+        return Err(MutexError::NotRecoverable.into());
+    }
+
+    // 天花板协议不允许基础优先级更高的任务加锁
+    if mutex.is_protect() && PriorityCeiling::base_priority(run_task) < mutex.ceiling {
+        restore_interrupt_state(int_save);
+        return Err(MutexError::CeilingViolated.into());
+    }
+
     // 如果互斥锁未被锁定
     if mutex.get_count() == 0 {
         mutex.increment_count();
         mutex.set_owner(run_task);
+        if mutex.is_protect() {
+            PriorityCeiling::raise(run_task);
+        }
         restore_interrupt_state(int_save);
+        if mutex.consistency == MutexConsistency::OwnerDied {
+            return Err(MutexError::OwnerDied.into());
+        }
         return Ok(());
     }
 
@@ -187,10 +397,12 @@ pub fn mutex_post(id: MutexId) -> SystemResult<()> {
 
     // 参数检查
     if mutex.is_unused() || !mutex.matches_id(id) {
+        restore_interrupt_state(int_save);
         return Err(MutexError::Invalid.into());
     }
 
     if is_interrupt_active() {
+        restore_interrupt_state(int_save);
         return Err(MutexError::PendInterrupt.into());
     }
 
@@ -206,8 +418,7 @@ pub fn mutex_post(id: MutexId) -> SystemResult<()> {
         return Ok(());
     }
 
-    // 执行释放操作
-    let need_schedule = post_operation(run_task, mutex);
+    let need_schedule = release_operation(run_task, mutex);
 
     restore_interrupt_state(int_save);
 
@@ -218,3 +429,28 @@ pub fn mutex_post(id: MutexId) -> SystemResult<()> {
 
     Ok(())
 }
+
+/// 将健壮互斥锁所保护的状态标记为一致
+///
+/// 以[`MutexError::OwnerDied`]获得互斥锁的任务在修复状态后调用，之后互斥锁恢复正常使用；
+/// 未标记便解锁时互斥锁变为不可恢复，等待者和后续的获取都返回[`MutexError::NotRecoverable`]。
+pub fn mutex_consistent(id: MutexId) -> SystemResult<()> {
+    let mutex = MutexManager::get_mutex_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let run_task = get_current_task();
+    let result = if mutex.is_unused()
+        || !mutex.matches_id(id)
+        || mutex.consistency != MutexConsistency::OwnerDied
+        || !mutex.is_owner(run_task)
+    {
+        Err(MutexError::Invalid.into())
+    } else {
+        mutex.consistency = MutexConsistency::Consistent;
+        Ok(())
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
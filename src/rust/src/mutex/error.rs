@@ -20,6 +20,14 @@ pub enum MutexError {
     Timeout,
     /// 互斥锁被挂起，无法删除
     Pended,
+    /// 健壮互斥锁的前一个持有者被删除，当前任务已获得互斥锁
+    OwnerDied,
+    /// 健壮互斥锁保护的状态不可恢复
+    NotRecoverable,
+    /// 天花板优先级无效
+    CeilingInvalid,
+    /// 任务的基础优先级高于互斥锁的天花板优先级
+    CeilingViolated,
 }
 
 impl From<MutexError> for u32 {
@@ -33,6 +41,10 @@ impl From<MutexError> for u32 {
             MutexError::PendInLock => ERRNO_MUX_PEND_IN_LOCK,
             MutexError::Timeout => ERRNO_MUX_TIMEOUT,
             MutexError::Pended => ERRNO_MUX_PENDED,
+            MutexError::OwnerDied => ERRNO_MUX_OWNER_DIED,
+            MutexError::NotRecoverable => ERRNO_MUX_NOT_RECOVERABLE,
+            MutexError::CeilingInvalid => ERRNO_MUX_CEILING_INVALID,
+            MutexError::CeilingViolated => ERRNO_MUX_CEILING_VIOLATED,
         }
     }
 }
@@ -50,6 +62,10 @@ impl TryFrom<u32> for MutexError {
             ERRNO_MUX_PEND_IN_LOCK => Ok(MutexError::PendInLock),
             ERRNO_MUX_TIMEOUT => Ok(MutexError::Timeout),
             ERRNO_MUX_PENDED => Ok(MutexError::Pended),
+            ERRNO_MUX_OWNER_DIED => Ok(MutexError::OwnerDied),
+            ERRNO_MUX_NOT_RECOVERABLE => Ok(MutexError::NotRecoverable),
+            ERRNO_MUX_CEILING_INVALID => Ok(MutexError::CeilingInvalid),
+            ERRNO_MUX_CEILING_VIOLATED => Ok(MutexError::CeilingViolated),
             _ => Err(()),
         }
     }
@@ -63,6 +79,10 @@ const ERRNO_MUX_PEND_INTERR: u32 = 0x02001d05;
 const ERRNO_MUX_PEND_IN_LOCK: u32 = 0x02001d06;
 const ERRNO_MUX_TIMEOUT: u32 = 0x02001d07;
 const ERRNO_MUX_PENDED: u32 = 0x02001d09;
+const ERRNO_MUX_OWNER_DIED: u32 = 0x02001d0a;
+const ERRNO_MUX_NOT_RECOVERABLE: u32 = 0x02001d0b;
+const ERRNO_MUX_CEILING_INVALID: u32 = 0x02001d0c;
+const ERRNO_MUX_CEILING_VIOLATED: u32 = 0x02001d0d;
 
 impl core::fmt::Display for MutexError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -75,6 +95,10 @@ impl core::fmt::Display for MutexError {
             Self::PendInLock => "Cannot wait for mutex in scheduler locked state",
             Self::Timeout => "Mutex wait timeout",
             Self::Pended => "Mutex is pended and cannot be deleted",
+            Self::OwnerDied => "Previous mutex owner was deleted while holding it",
+            Self::NotRecoverable => "State protected by the mutex is not recoverable",
+            Self::CeilingInvalid => "Invalid mutex ceiling priority",
+            Self::CeilingViolated => "Task base priority is higher than the mutex ceiling",
         };
         write!(f, "{}", desc)
     }
@@ -3,11 +3,20 @@
 use core::ptr::addr_of;
 
 use crate::{
-    task::{manager::priority::modify_task_priority_raw, types::TaskCB},
-    utils::bitmap::{clear_bit, get_highest_bit, get_lowest_bit, set_bit},
+    config::MUX_INHERIT_DEPTH,
+    rwlock::core::rwlock_waiter_priority,
+    task::{
+        manager::priority::modify_task_priority_raw,
+        types::{TaskCB, TaskPendObject, TaskStatus},
+    },
+    utils::{bitmap::PriorityBitmap, list::LinkedList},
 };
 
-use super::types::MutexControlBlock;
+use super::{
+    global::MutexManager,
+    types::{MutexControlBlock, MutexWaitMode},
+    wait::WaitManager,
+};
 
 /// 优先级继承管理器
 pub struct PriorityInheritance;
@@ -21,9 +30,11 @@ impl PriorityInheritance {
         // 如果等待任务的优先级高于所有者，进行优先级继承
         if owner_priority > waiting_priority {
             // 记录原始优先级
-            set_bit(&mut owner_task.priority_bitmap, owner_priority);
+            owner_task.priority_bitmap.set(owner_priority);
             // 提升所有者优先级
             modify_task_priority_raw(owner_task, waiting_priority);
+            // 所有者阻塞在其他互斥锁上时继续向下传递
+            Self::update_chain(owner_task);
         }
     }
 
@@ -33,63 +44,183 @@ impl PriorityInheritance {
         resumed_task: &TaskCB,
         mutex: &mut MutexControlBlock,
     ) {
-
-        #[cfg(feature = "mutex-waitmode-prio")]
-        {
-            if resumed_task.priority > run_task.priority {
-                // 检查是否需要清除位图中的优先级记录
-                if get_highest_bit(run_task.priority_bitmap) != resumed_task.priority {
-                    clear_bit(&mut run_task.priority_bitmap, resumed_task.priority);
+        match mutex.wait_mode {
+            MutexWaitMode::Priority => {
+                if resumed_task.priority > run_task.priority {
+                    // 检查是否需要清除位图中的优先级记录
+                    if run_task.priority_bitmap.last() != Some(resumed_task.priority) {
+                        run_task.priority_bitmap.clear(resumed_task.priority);
+                    }
+                } else if !run_task.priority_bitmap.is_empty() {
+                    Self::restore_priority_complex(run_task, mutex);
                 }
-            } else if run_task.priority_bitmap != 0 {
-                Self::restore_priority_complex(run_task, mutex);
             }
-        }
-
-        #[cfg(not(feature = "mutex-waitmode-prio"))]
-        {
-            if run_task.priority_bitmap != 0 {
-                Self::restore_priority_complex(run_task, mutex);
+            MutexWaitMode::Fifo => {
+                if !run_task.priority_bitmap.is_empty() {
+                    Self::restore_priority_complex(run_task, mutex);
+                }
             }
         }
     }
 
     /// 复杂的优先级恢复处理
-    fn restore_priority_complex(run_task: &mut TaskCB, mutex: &mut MutexControlBlock) {
+    pub fn restore_priority_complex(run_task: &mut TaskCB, mutex: &mut MutexControlBlock) {
         if mutex.has_waiting_tasks() {
-            let priority = get_highest_bit(run_task.priority_bitmap);
+            let priority = run_task.priority_bitmap.last();
 
             // 在中间查找合适位置
             let mut cur_task = TaskCB::from_pend_list(mutex.mux_list.next);
 
             while addr_of!(cur_task.pend_list) != addr_of!(mutex.mux_list) {
-                if priority != cur_task.priority {
+                if priority != Some(cur_task.priority) {
                     // 清除不再需要的优先级记录
-                    clear_bit(&mut run_task.priority_bitmap, cur_task.priority);
+                    run_task.priority_bitmap.clear(cur_task.priority);
                 }
                 cur_task = TaskCB::from_pend_list(cur_task.pend_list.next);
             }
         }
 
         // 恢复到最高的必要优先级
-        let priority = get_lowest_bit(run_task.priority_bitmap);
-        clear_bit(&mut run_task.priority_bitmap, priority);
-        modify_task_priority_raw(mutex.get_owner(), priority);
+        if let Some(priority) = run_task.priority_bitmap.first() {
+            run_task.priority_bitmap.clear(priority);
+            modify_task_priority_raw(mutex.get_owner(), priority);
+        }
     }
 
     /// 恢复任务的原始优先级（在超时时调用）
-    pub fn restore_priority_on_timeout(run_task: &mut TaskCB, owner_task: &mut TaskCB) {
-        if owner_task.priority >= run_task.priority {
-            let priority = get_lowest_bit(owner_task.priority_bitmap);
-            if priority != 32 {
-                clear_bit(&mut owner_task.priority_bitmap, priority);
-                modify_task_priority_raw(owner_task, priority);
+    ///
+    /// 等待者已离开等待队列，按剩余的等待者重新计算持有者的优先级，并沿阻塞链撤销提升。
+    pub fn restore_priority_on_timeout(owner_task: &mut TaskCB) {
+        if Self::recompute(owner_task) {
+            Self::update_chain(owner_task);
+        }
+    }
+
+    /// 设置阻塞中任务的基础优先级，重新计算它继承的优先级并沿阻塞链传递，返回是否有持有者的
+    /// 优先级改变
+    pub fn set_blocked_priority(task: &mut TaskCB, priority: u16) -> bool {
+        task.priority_bitmap = PriorityBitmap::new();
+        task.priority = priority;
+        Self::recompute(task);
+        Self::update_chain(task)
+    }
+
+    /// 设置未阻塞任务的基础优先级，按所持互斥锁和写锁重新计算继承和天花板带来的提升
+    pub fn set_base_priority(task: &mut TaskCB, priority: u16) {
+        task.priority_bitmap = PriorityBitmap::new();
+        modify_task_priority_raw(task, priority);
+        Self::recompute(task);
+    }
+
+    /// 沿阻塞链传递任务的优先级变化，返回是否有持有者的优先级改变
+    ///
+    /// 任务阻塞在互斥锁上时先按新优先级调整它在等待队列中的位置，再重新计算该锁持有者的优先级；
+    /// 持有者的优先级改变且同样阻塞在互斥锁上时继续向下传递，最多[`MUX_INHERIT_DEPTH`]层。
+    pub fn update_chain(task: &mut TaskCB) -> bool {
+        let mut changed = false;
+        let mut cur_task = task as *mut TaskCB;
+        for _ in 0..MUX_INHERIT_DEPTH {
+            let task = unsafe { &mut *cur_task };
+            let Some(mutex) = Self::blocking_mutex(task) else {
+                break;
+            };
+
+            // 调整等待位置
+            LinkedList::remove(&raw mut task.pend_list);
+            let wait_pos = WaitManager::find_wait_position(task, mutex);
+            LinkedList::tail_insert(wait_pos, &raw mut task.pend_list);
+
+            if mutex.owner.is_null() {
+                break;
+            }
+            let owner_task = mutex.get_owner();
+            if !Self::recompute(owner_task) {
+                break;
+            }
+            changed = true;
+            cur_task = owner_task;
+        }
+        changed
+    }
+
+    /// 新持有者继承仍在等待的任务的优先级，先进先出方式下等待者可能比新持有者优先级更高
+    pub fn inherit_from_waiters(owner_task: &mut TaskCB) {
+        Self::recompute(owner_task);
+    }
+
+    /// 获取任务正在等待的互斥锁
+    fn blocking_mutex(task: &TaskCB) -> Option<&'static mut MutexControlBlock> {
+        if !task.task_status.contains(TaskStatus::PEND) {
+            return None;
+        }
+        let TaskPendObject::Mutex(id) = task.pend_object else {
+            return None;
+        };
+        let mutex = MutexManager::get_mutex_mut(id.into()).ok()?;
+        (!mutex.is_unused() && mutex.matches_id(id.into())).then_some(mutex)
+    }
+
+    /// 按基础优先级、所持互斥锁和写锁的等待者以及天花板重新计算任务的优先级，返回优先级是否改变
+    pub fn recompute(owner_task: &mut TaskCB) -> bool {
+        let base = PriorityCeiling::base_priority(owner_task);
+        let mut priority = rwlock_waiter_priority(owner_task).map_or(base, |p| p.min(base));
+
+        let held_list = addr_of!(owner_task.held_mutexes);
+        let mut node = owner_task.held_mutexes.next;
+        while !core::ptr::eq(node, held_list) {
+            let mutex = MutexControlBlock::from_held_node(node);
+            if mutex.is_protect() {
+                priority = priority.min(mutex.ceiling);
             }
-        } else {
-            // 检查是否需要清除当前任务的优先级记录
-            if get_highest_bit(owner_task.priority_bitmap) != run_task.priority {
-                clear_bit(&mut owner_task.priority_bitmap, run_task.priority);
+            if let Some(waiter_priority) = waiter_priority(&raw const mutex.mux_list) {
+                priority = priority.min(waiter_priority);
             }
+            node = mutex.held_node.next;
+        }
+
+        if priority == owner_task.priority {
+            return false;
         }
+        // 只保留基础优先级的记录
+        owner_task.priority_bitmap = PriorityBitmap::new();
+        if priority != base {
+            owner_task.priority_bitmap.set(base);
+        }
+        modify_task_priority_raw(owner_task, priority);
+        true
+    }
+}
+
+/// 获取等待队列中任务的最高优先级
+pub(crate) fn waiter_priority(list: *const LinkedList) -> Option<u16> {
+    let mut priority: Option<u16> = None;
+    let mut node = unsafe { (*list).next };
+    while !core::ptr::eq(node, list) {
+        let waiter = TaskCB::from_pend_list(node);
+        priority = Some(priority.map_or(waiter.priority, |p| p.min(waiter.priority)));
+        node = waiter.pend_list.next;
+    }
+    priority
+}
+
+/// 立即天花板协议管理器
+pub struct PriorityCeiling;
+
+impl PriorityCeiling {
+    /// 任务优先级继承和天花板提升前的基础优先级
+    pub fn base_priority(task: &TaskCB) -> u16 {
+        task.priority_bitmap.last().unwrap_or(task.priority)
+    }
+
+    /// 新持有者加锁时提升到天花板优先级
+    pub fn raise(owner_task: &mut TaskCB) {
+        PriorityInheritance::recompute(owner_task);
+    }
+
+    /// 持有者交出互斥锁后撤销天花板提升，返回优先级是否改变
+    ///
+    /// 按基础优先级和仍持有的互斥锁重新计算，解锁顺序与加锁顺序不同时也能恢复到正确的优先级。
+    pub fn restore(owner_task: &mut TaskCB) -> bool {
+        PriorityInheritance::recompute(owner_task)
     }
 }
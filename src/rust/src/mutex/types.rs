@@ -3,6 +3,7 @@
 use core::sync::atomic::{AtomicU16, Ordering};
 
 use crate::container_of;
+use crate::task::resource::OwnerDeadPolicy;
 use crate::task::types::TaskCB;
 use crate::utils::list::LinkedList;
 
@@ -14,12 +15,71 @@ pub enum MutexState {
     Used = 1,
 }
 
+/// 健壮互斥锁所保护状态的一致性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MutexConsistency {
+    /// 状态一致
+    Consistent = 0,
+    /// 持有者被删除，新持有者尚未标记状态一致
+    OwnerDied = 1,
+    /// 状态不可恢复，互斥锁只能被删除
+    NotRecoverable = 2,
+}
+
+/// 互斥锁的优先级协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum MutexProtocol {
+    /// 优先级继承，持有者继承更高优先级等待者的优先级
+    #[default]
+    Inherit = 0,
+    /// 立即天花板协议，持有者在加锁时被提升到天花板优先级
+    Protect = 1,
+}
+
+/// 互斥锁等待队列的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MutexWaitMode {
+    /// 按优先级排序，同优先级按到达顺序
+    Priority = 0,
+    /// 按到达顺序排序
+    Fifo = 1,
+}
+
+impl MutexWaitMode {
+    /// 由`mutex-waitmode-prio`和`mutex-waitmode-fifo`特性选择的默认方式，两者都启用时按优先级
+    #[cfg(feature = "mutex-waitmode-prio")]
+    pub const DEFAULT: Self = Self::Priority;
+    #[cfg(not(feature = "mutex-waitmode-prio"))]
+    pub const DEFAULT: Self = Self::Fifo;
+}
+
+impl Default for MutexWaitMode {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// 互斥锁属性
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MutexAttr {
+    /// 健壮互斥锁，持有者被删除时由下一个等待者接管
+    pub robust: bool,
+    /// 优先级协议
+    pub protocol: MutexProtocol,
+    /// 天花板优先级，仅用于[`MutexProtocol::Protect`]
+    pub ceiling: u16,
+    /// 等待队列的排序方式
+    pub wait_mode: MutexWaitMode,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct MutexId(pub u32);
 
-/// 句柄分割位数
-
 /// 为MutexHandle实现扩展trait
 impl MutexId {
     /// 设置互斥锁ID
@@ -83,15 +143,44 @@ pub struct MutexControlBlock {
     /// 互斥锁ID
     /// 对应C结构体中的muxId字段
     pub mux_id: MutexId,
+
+    /// 持有者被删除时的处理策略
+    pub owner_policy: OwnerDeadPolicy,
+
+    /// 是否为健壮互斥锁
+    pub robust: bool,
+
+    /// 健壮互斥锁所保护状态的一致性
+    pub consistency: MutexConsistency,
+
+    /// 优先级协议
+    pub protocol: MutexProtocol,
+
+    /// 天花板优先级
+    pub ceiling: u16,
+
+    /// 等待队列的排序方式
+    pub wait_mode: MutexWaitMode,
+
+    /// 挂入持有者的held_mutexes
+    pub held_node: LinkedList,
 }
 
 impl MutexControlBlock {
+    #[allow(clippy::declare_interior_mutable_const)]
     pub const UNINIT: Self = Self {
         mux_list: LinkedList::new(),
         owner: core::ptr::null_mut(),
         mux_count: AtomicU16::new(0),
         mux_stat: MutexState::Unused,
         mux_id: MutexId(0),
+        owner_policy: OwnerDeadPolicy::Release,
+        robust: false,
+        consistency: MutexConsistency::Consistent,
+        protocol: MutexProtocol::Inherit,
+        ceiling: 0,
+        wait_mode: MutexWaitMode::DEFAULT,
+        held_node: LinkedList::new(),
     };
 
     /// 获取锁定计数
@@ -129,8 +218,12 @@ impl MutexControlBlock {
         task_cb == self.owner
     }
 
-    /// 设置所有者
+    /// 设置所有者，并把互斥锁从原持有者的held_mutexes移到新持有者的链表中
     pub fn set_owner(&mut self, task: *mut TaskCB) {
+        self.clear_owner();
+        if let Some(task_cb) = unsafe { task.as_mut() } {
+            LinkedList::tail_insert(&raw mut task_cb.held_mutexes, &raw mut self.held_node);
+        }
         self.owner = task;
     }
 
@@ -141,6 +234,9 @@ impl MutexControlBlock {
 
     /// 清除所有者
     pub fn clear_owner(&mut self) {
+        if !self.owner.is_null() {
+            LinkedList::remove(&raw mut self.held_node);
+        }
         self.owner = core::ptr::null_mut();
     }
 
@@ -178,10 +274,29 @@ impl MutexControlBlock {
         self.get_id() == id
     }
 
+    /// 应用互斥锁属性
+    pub fn apply_attr(&mut self, attr: &MutexAttr) {
+        self.robust = attr.robust;
+        self.protocol = attr.protocol;
+        self.ceiling = attr.ceiling;
+        self.wait_mode = attr.wait_mode;
+    }
+
+    /// 是否使用天花板协议
+    pub fn is_protect(&self) -> bool {
+        self.protocol == MutexProtocol::Protect
+    }
+
     /// 初始化互斥锁
     pub fn initialize(&mut self) {
         self.set_count(0);
         self.clear_owner();
+        self.owner_policy = OwnerDeadPolicy::Release;
+        self.robust = false;
+        self.consistency = MutexConsistency::Consistent;
+        self.protocol = MutexProtocol::Inherit;
+        self.ceiling = 0;
+        self.wait_mode = MutexWaitMode::DEFAULT;
         LinkedList::init(&raw mut self.mux_list);
         self.set_state(MutexState::Used);
     }
@@ -198,6 +313,11 @@ impl MutexControlBlock {
         let mutex_ptr = container_of!(ptr, MutexControlBlock, mux_list);
         unsafe { &mut *mutex_ptr }
     }
+
+    pub fn from_held_node(ptr: *mut LinkedList) -> &'static mut MutexControlBlock {
+        let mutex_ptr = container_of!(ptr, MutexControlBlock, held_node);
+        unsafe { &mut *mutex_ptr }
+    }
 }
 
 impl Default for MutexControlBlock {
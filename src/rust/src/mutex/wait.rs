@@ -1,6 +1,8 @@
 //! 等待队列管理
 
-use crate::mutex::types::MutexControlBlock;
+use core::ptr::addr_of;
+
+use crate::mutex::types::{MutexControlBlock, MutexWaitMode};
 use crate::task::types::TaskCB;
 use crate::utils::list::LinkedList;
 
@@ -8,14 +10,22 @@ use crate::utils::list::LinkedList;
 pub struct WaitManager;
 
 impl WaitManager {
-    /// 根据优先级查找等待位置
-    #[cfg(feature = "mutex-waitmode-prio")]
+    /// 按互斥锁的等待方式查找等待位置，任务插入到返回的节点之前
     pub fn find_wait_position<'a>(
         run_task: &mut TaskCB,
         mutex: &'a mut MutexControlBlock,
     ) -> &'a mut LinkedList {
-        use core::ptr::addr_of;
+        match mutex.wait_mode {
+            MutexWaitMode::Priority => Self::find_priority_position(run_task, mutex),
+            MutexWaitMode::Fifo => &mut mutex.mux_list,
+        }
+    }
 
+    /// 根据优先级查找等待位置
+    fn find_priority_position<'a>(
+        run_task: &mut TaskCB,
+        mutex: &'a mut MutexControlBlock,
+    ) -> &'a mut LinkedList {
         if LinkedList::is_empty(&raw const mutex.mux_list) {
             return &mut mutex.mux_list;
         }
@@ -47,10 +57,4 @@ impl WaitManager {
         }
         &mut mutex.mux_list
     }
-
-    /// 简单的FIFO等待（不考虑优先级）
-    #[cfg(not(feature = "mutex-waitmode-prio"))]
-    pub fn find_wait_position(_task: *mut TaskCB, mutex: &MutexCB) -> *mut LinkedList {
-        &mutex.mux_list as *const _ as *mut _
-    }
 }
@@ -1,10 +1,13 @@
+#[cfg(feature = "smp")]
+use crate::task::types::TaskCB;
 use crate::{
+    config::KERNEL_CORE_NUM,
+    ffi::bindings::arch_curr_cpuid,
     interrupt::{disable_interrupts, restore_interrupt_state},
     queue::types::QueueId,
     utils::sortlink::SortLinkAttribute,
 };
-
-const LOSCFG_KERNEL_CORE_NUM: usize = 1;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// 每个CPU核心的特定数据结构
 #[repr(C)]
@@ -30,6 +33,14 @@ pub struct Percpu {
 
     /// 调度器标志位
     pub needs_reschedule: u32,
+
+    /// 内核锁在当前核上的嵌套深度
+    #[cfg(feature = "smp")]
+    pub kernel_lock_depth: u32,
+
+    /// 当前核上正在运行的任务
+    #[cfg(feature = "smp")]
+    pub run_task: *mut TaskCB,
 }
 
 impl Percpu {
@@ -41,6 +52,10 @@ impl Percpu {
         swtmr_handler_queue: 0,
         timer_task_id: 0,
         needs_reschedule: 0,
+        #[cfg(feature = "smp")]
+        kernel_lock_depth: 0,
+        #[cfg(feature = "smp")]
+        run_task: core::ptr::null_mut(),
     };
 
     #[inline]
@@ -60,11 +75,35 @@ impl Percpu {
 }
 
 #[unsafe(export_name = "g_percpu")]
-pub static mut PERCPU: [Percpu; LOSCFG_KERNEL_CORE_NUM] = [Percpu::UNINIT; LOSCFG_KERNEL_CORE_NUM];
+pub static mut PERCPU: [Percpu; KERNEL_CORE_NUM] = [Percpu::UNINIT; KERNEL_CORE_NUM];
+
+/// 已启动调度的CPU集合
+static CPU_ONLINE_MASK: AtomicU32 = AtomicU32::new(0);
 
+/// 获取当前CPU的percpu结构
 #[inline]
 pub fn os_percpu_get() -> &'static mut Percpu {
-    unsafe { &mut PERCPU[0] }
+    os_percpu_get_by_id(arch_curr_cpuid())
+}
+
+/// 获取指定CPU的percpu结构
+#[inline]
+pub fn os_percpu_get_by_id(cpuid: u32) -> &'static mut Percpu {
+    debug_assert!((cpuid as usize) < KERNEL_CORE_NUM);
+    unsafe { &mut *(&raw mut PERCPU).cast::<Percpu>().add(cpuid as usize) }
+}
+
+/// 将当前CPU标记为在线
+#[inline]
+pub fn set_cpu_online() {
+    CPU_ONLINE_MASK.fetch_or(1 << arch_curr_cpuid(), Ordering::AcqRel);
+}
+
+/// 获取在线CPU集合
+#[cfg(any(feature = "smp", feature = "cpup", feature = "sim"))]
+#[inline]
+pub fn cpu_online_mask() -> u32 {
+    CPU_ONLINE_MASK.load(Ordering::Acquire)
 }
 
 #[inline]
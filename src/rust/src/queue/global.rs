@@ -1,11 +1,12 @@
 use crate::{config::QUEUE_LIMIT, queue::types::QueueControlBlock};
-use alloc::collections::LinkedList;
 use core::cell::RefCell;
 use critical_section::Mutex;
+use heapless::Deque;
 
 pub static QUEUE_POOL: Mutex<RefCell<[QueueControlBlock; QUEUE_LIMIT as usize]>> = Mutex::new(
     RefCell::new([QueueControlBlock::UNINIT; QUEUE_LIMIT as usize]),
 );
 
-pub static UNUSED_QUEUE_LIST: Mutex<RefCell<LinkedList<usize>>> =
-    Mutex::new(RefCell::new(LinkedList::new()));
+/// 未使用的队列控制块下标，固定容量，创建和删除队列时不使用堆内存
+pub static UNUSED_QUEUE_LIST: Mutex<RefCell<Deque<usize, { QUEUE_LIMIT as usize }>>> =
+    Mutex::new(RefCell::new(Deque::new()));
@@ -34,7 +34,6 @@ pub fn get_queue_info(queue_id: QueueId, queue_info: &mut QueueInfo) -> SystemRe
 }
 
 /// 获取当前使用的消息队列数量
-#[inline]
 #[unsafe(export_name = "OsUsedQueueCountGet")]
 pub fn get_used_count() -> usize {
     with(|cs| {
@@ -47,7 +46,6 @@ pub fn get_used_count() -> usize {
 }
 
 /// 打印当前使用的消息队列信息
-#[inline]
 #[unsafe(export_name = "OsUsedQueueInfoPrint")]
 pub fn print_used_info() {
     with(|cs| {
@@ -4,9 +4,15 @@ use crate::{
     queue::{
         error::QueueError,
         global::{QUEUE_POOL, UNUSED_QUEUE_LIST},
-        types::QueueId,
+        types::{QueueControlBlock, QueueId},
     },
     result::SystemResult,
+    select::core::select_object_deleted,
+    task::{
+        resource::{OWNER_NONE, OwnerDeadPolicy},
+        sched::schedule,
+        sync::wait::task_wake_all_owner_dead,
+    },
 };
 use critical_section::with;
 
@@ -21,13 +27,17 @@ pub fn init_queue_system() {
             .enumerate()
             .for_each(|(index, queue)| {
                 queue.set_id(QueueId(index as u32));
-                unused_list.push_back(index);
+                let _ = unused_list.push_back(index);
             });
     })
 }
 
-/// 内部队列创建函数
-fn create_queue_internal(capacity: usize, slot_size: usize) -> SystemResult<QueueId> {
+/// 内部队列创建函数，`static_mem`为空时从堆上分配存储区域
+fn create_queue_internal(
+    capacity: usize,
+    slot_size: usize,
+    static_mem: Option<&'static mut [u8]>,
+) -> SystemResult<QueueId> {
     // 临界区开始
     with(|cs| {
         // 检查是否有可用队列控制块
@@ -35,16 +45,18 @@ fn create_queue_internal(capacity: usize, slot_size: usize) -> SystemResult<Queu
         let index = unused_list.pop_front().ok_or(QueueError::Unavailable)?;
         let mut queue_pool = QUEUE_POOL.borrow_ref_mut(cs);
         let queue = queue_pool.get_mut(index).unwrap();
-        queue.initialize(capacity, slot_size);
+        match static_mem {
+            Some(mem) => queue.initialize_static(capacity, slot_size, mem),
+            None => queue.initialize(capacity, slot_size),
+        }
         let queue_id = queue.get_id();
         Ok(queue_id)
     })
 }
 
-/// 创建动态内存队列
-pub fn create_queue(capacity: usize, message_size: usize) -> SystemResult<QueueId> {
-    // 参数检查
-    if message_size > (usize::MAX - 4) {
+/// 检查队列创建参数，返回每条消息占用的槽位大小
+fn check_queue_param(capacity: usize, message_size: usize) -> SystemResult<usize> {
+    if message_size > (usize::MAX - QueueControlBlock::MESSAGE_LEN_BYTES) {
         return Err(QueueError::SizeTooBig.into());
     }
 
@@ -52,16 +64,46 @@ pub fn create_queue(capacity: usize, message_size: usize) -> SystemResult<QueueI
         return Err(QueueError::ParaIsZero.into());
     }
 
-    let slot_size = message_size + 4;
+    Ok(message_size + QueueControlBlock::MESSAGE_LEN_BYTES)
+}
+
+/// 创建动态内存队列
+pub fn create_queue(capacity: usize, message_size: usize) -> SystemResult<QueueId> {
+    // 参数检查
+    let slot_size = check_queue_param(capacity, message_size)?;
 
     // 调用内部创建函数
-    match create_queue_internal(capacity, slot_size) {
-        Ok(queue_id) => Ok(queue_id),
-        Err(err) => Err(err),
+    create_queue_internal(capacity, slot_size, None)
+}
+
+/// 静态内存队列所需的存储区域大小
+pub const fn queue_static_mem_size(capacity: usize, message_size: usize) -> usize {
+    capacity * (message_size + QueueControlBlock::MESSAGE_LEN_BYTES)
+}
+
+/// 使用调用者提供的存储区域创建队列，创建和删除都不使用堆内存
+///
+/// 存储区域至少为[`queue_static_mem_size`]字节。存储区域交给内核后不再归还，创建失败或删除队列
+/// 时都不会释放，也不能再用于创建其他队列。
+pub fn create_queue_static(
+    capacity: usize,
+    message_size: usize,
+    mem: &'static mut [u8],
+) -> SystemResult<QueueId> {
+    // 参数检查
+    let slot_size = check_queue_param(capacity, message_size)?;
+    match capacity.checked_mul(slot_size) {
+        Some(size) if size <= mem.len() => {}
+        _ => return Err(QueueError::CreateNoMemory.into()),
     }
+
+    // 调用内部创建函数
+    create_queue_internal(capacity, slot_size, Some(mem))
 }
 
-/// 删除消息队列
+/// 删除消息队列，多对象等待该队列的任务返回[`SelectError::ObjectDeleted`]
+///
+/// [`SelectError::ObjectDeleted`]: crate::select::error::SelectError::ObjectDeleted
 pub fn delete_queue(queue_id: QueueId) -> SystemResult<()> {
     // 检查队列索引是否有效
     let index = queue_id.get_index();
@@ -90,11 +132,69 @@ pub fn delete_queue(queue_id: QueueId) -> SystemResult<()> {
         }
 
         // 回收队列资源
+        let woken = select_object_deleted(&raw mut queue.select_list);
         queue.reset();
         let mut unused_list = UNUSED_QUEUE_LIST.borrow_ref_mut(cs);
         // 将队列索引添加到未使用列表
-        unused_list.push_back(index as usize);
+        let _ = unused_list.push_back(index as usize);
 
+        Ok(woken)
+    })
+    .map(|woken| {
+        // 唤醒多对象等待该队列的任务
+        if woken {
+            schedule();
+        }
+    })
+}
+
+/// 设置创建者被删除时的处理策略，默认为[`OwnerDeadPolicy::Orphan`]
+///
+/// `Release`唤醒所有读写等待者并保留队列，`Destroy`唤醒所有读写等待者并删除队列及其中的消息，
+/// 被唤醒的等待者返回[`TaskError::OwnerDead`]。
+///
+/// [`TaskError::OwnerDead`]: crate::task::error::TaskError::OwnerDead
+pub fn queue_owner_policy_set(queue_id: QueueId, policy: OwnerDeadPolicy) -> SystemResult<()> {
+    // 检查队列索引是否有效
+    let index = queue_id.get_index();
+    if index as u32 >= QUEUE_LIMIT {
+        return Err(QueueError::NotFound.into());
+    }
+
+    with(|cs| {
+        let mut queue_pool = QUEUE_POOL.borrow_ref_mut(cs);
+        let queue = queue_pool.get_mut(index as usize).unwrap();
+        if !queue.matches_id(queue_id) || queue.is_unused() {
+            return Err(QueueError::NotCreate.into());
+        }
+        queue.owner_policy = policy;
         Ok(())
     })
 }
+
+/// 处理被删除任务创建的队列，须在关中断后调用，返回是否唤醒了任务
+pub(crate) fn queue_owner_dead(task_id: u32, count: &mut u16) -> bool {
+    with(|cs| {
+        let mut queue_pool = QUEUE_POOL.borrow_ref_mut(cs);
+        let mut woken = false;
+        for (index, queue) in queue_pool.iter_mut().enumerate() {
+            if queue.is_unused() || queue.owner != task_id {
+                continue;
+            }
+
+            queue.owner = OWNER_NONE;
+            if queue.owner_policy == OwnerDeadPolicy::Orphan {
+                continue;
+            }
+            woken |= task_wake_all_owner_dead(&mut queue.read_waiting_list);
+            woken |= task_wake_all_owner_dead(&mut queue.write_waiting_list);
+            if queue.owner_policy == OwnerDeadPolicy::Destroy {
+                woken |= select_object_deleted(&raw mut queue.select_list);
+                queue.reset();
+                let _ = UNUSED_QUEUE_LIST.borrow_ref_mut(cs).push_back(index);
+            }
+            *count += 1;
+        }
+        woken
+    })
+}
@@ -6,10 +6,12 @@ use crate::percpu::can_preempt_in_scheduler;
 use crate::queue::error::QueueError;
 use crate::queue::global::QUEUE_POOL;
 use crate::queue::types::{QueueControlBlock, QueueId, QueueOperationType};
-use crate::result::SystemResult;
+use crate::result::{SystemError, SystemResult};
+use crate::select::core::select_notify;
+use crate::task::error::TaskError;
 use crate::task::sched::{schedule, schedule_reschedule};
-use crate::task::sync::wait::{task_wait, task_wake};
-use crate::task::types::{TaskCB, TaskStatus};
+use crate::task::sync::wait::{task_wait, task_wait_interrupted, task_wait_owner_dead, task_wake};
+use crate::task::types::{TaskCB, TaskPendObject, TaskStatus};
 use crate::utils::list::LinkedList;
 use critical_section::with;
 
@@ -185,7 +187,7 @@ fn queue_operate(
             // 让当前任务等待队列
             let wait_list = queue.get_wait_list(operate_type);
 
-            task_wait(wait_list, timeout);
+            task_wait(wait_list, TaskPendObject::Queue(queue_id.into()), timeout);
             drop(queue_pool);
 
             // 重新调度
@@ -197,6 +199,12 @@ fn queue_operate(
                 task.task_status.remove(TaskStatus::TIMEOUT);
                 return Err(QueueError::Timeout.into());
             }
+            if task_wait_interrupted(task) {
+                return Err(SystemError::Task(TaskError::Interrupted));
+            }
+            if task_wait_owner_dead(task) {
+                return Err(SystemError::Task(TaskError::OwnerDead));
+            }
             queue_pool = QUEUE_POOL.borrow_ref_mut(cs);
             queue = queue_pool.get_mut(index as usize).unwrap();
         }
@@ -214,7 +222,8 @@ fn queue_operate(
         } else {
             // 增加对应的可读/可写计数
             queue.increment_opposite_resource_count(operate_type);
-            Ok(false)
+            // 写入消息后通知等待该队列的多对象等待任务
+            Ok(operate_type.is_write() && select_notify(&raw mut queue.select_list, |_| true))
         }
     });
     match res {
@@ -1,6 +1,8 @@
 //! 消息队列类型定义
+use crate::task::resource::{OWNER_NONE, OwnerDeadPolicy, current_owner};
 use crate::utils::list::LinkedList;
 use alloc::{boxed::Box, vec::Vec};
+use core::ops::{Deref, DerefMut};
 use semihosting::println;
 
 /// 队列操作类型
@@ -82,11 +84,40 @@ impl From<QueueId> for u32 {
     }
 }
 
+/// 队列数据存储区域
+#[derive(Debug)]
+pub enum QueueMemory {
+    /// 创建时从堆上分配，删除队列时释放
+    Heap(Box<[u8]>),
+    /// 调用者提供的静态内存，删除队列时不释放
+    Static(&'static mut [u8]),
+}
+
+impl Deref for QueueMemory {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Heap(mem) => mem,
+            Self::Static(mem) => mem,
+        }
+    }
+}
+
+impl DerefMut for QueueMemory {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            Self::Heap(mem) => mem,
+            Self::Static(mem) => mem,
+        }
+    }
+}
+
 /// 队列控制块
 #[derive(Debug)]
 pub struct QueueControlBlock {
     /// 队列数据存储区域
-    pub queue_mem: Option<Box<[u8]>>,
+    pub queue_mem: Option<QueueMemory>,
 
     /// 队列长度（最大消息数量）
     pub capacity: usize,
@@ -117,6 +148,21 @@ pub struct QueueControlBlock {
 
     /// 写等待链表
     pub write_waiting_list: LinkedList,
+
+    /// 创建队列的任务
+    pub owner: u32,
+
+    /// 创建者被删除时的处理策略
+    pub owner_policy: OwnerDeadPolicy,
+
+    /// 多对象等待此队列的等待节点列表
+    pub select_list: LinkedList,
+}
+
+impl Default for QueueControlBlock {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl QueueControlBlock {
@@ -135,6 +181,9 @@ impl QueueControlBlock {
         writable_count: 0,
         read_waiting_list: LinkedList::new(),
         write_waiting_list: LinkedList::new(),
+        owner: OWNER_NONE,
+        owner_policy: OwnerDeadPolicy::Orphan,
+        select_list: LinkedList::new(),
     };
 
     /// 创建一个新的未初始化队列控制块
@@ -152,6 +201,9 @@ impl QueueControlBlock {
             writable_count: 0,
             read_waiting_list: LinkedList::new(),
             write_waiting_list: LinkedList::new(),
+            owner: OWNER_NONE,
+            owner_policy: OwnerDeadPolicy::Orphan,
+            select_list: LinkedList::new(),
         }
     }
 
@@ -351,10 +403,18 @@ impl QueueControlBlock {
     pub fn initialize(&mut self, capacity: usize, slot_size: usize) {
         // 为队列分配内存
         let total_size = capacity * slot_size;
-        let mut queue_data: Vec<u8> = Vec::with_capacity(total_size);
-        queue_data.resize(total_size, 0);
+        let queue_data: Vec<u8> = alloc::vec![0; total_size];
         let queue_mem = queue_data.into_boxed_slice();
 
+        self.initialize_with(capacity, slot_size, QueueMemory::Heap(queue_mem));
+    }
+
+    /// 使用调用者提供的内存初始化队列，内存大小须不小于`capacity * slot_size`
+    pub fn initialize_static(&mut self, capacity: usize, slot_size: usize, mem: &'static mut [u8]) {
+        self.initialize_with(capacity, slot_size, QueueMemory::Static(mem));
+    }
+
+    fn initialize_with(&mut self, capacity: usize, slot_size: usize, queue_mem: QueueMemory) {
         self.queue_mem = Some(queue_mem);
         self.set_state(QueueState::Used);
         self.capacity = capacity;
@@ -365,9 +425,14 @@ impl QueueControlBlock {
         self.writable_count = capacity;
         LinkedList::init(&raw mut self.read_waiting_list);
         LinkedList::init(&raw mut self.write_waiting_list);
+        self.owner = current_owner();
+        self.owner_policy = OwnerDeadPolicy::Orphan;
+        LinkedList::init(&raw mut self.select_list);
     }
 
     /// 重置信号量
+    ///
+    /// 堆上分配的存储区域随之释放，静态内存只解除引用。
     #[inline]
     pub fn reset(&mut self) {
         self.set_state(QueueState::Unused);
@@ -390,7 +455,7 @@ impl QueueControlBlock {
 
         // 2. 将消息长度（u16）编码并存储到槽位的末尾
         let message_len = message_data.len();
-        let len_bytes = message_len.to_le_bytes(); // 或 to_be_bytes()，根据你的字节序需求选择
+        let len_bytes = (message_len as u32).to_le_bytes(); // 或 to_be_bytes()，根据你的字节序需求选择
 
         let len_start_idx = self.slot_size - Self::MESSAGE_LEN_BYTES;
         current_slot[len_start_idx..].copy_from_slice(&len_bytes);
@@ -416,7 +481,7 @@ impl QueueControlBlock {
 
         // 2. 将消息长度（u16）编码并存储到槽位的末尾
         let message_len = message_data.len();
-        let len_bytes = message_len.to_le_bytes(); // 或 to_be_bytes()，根据你的字节序需求选择
+        let len_bytes = (message_len as u32).to_le_bytes(); // 或 to_be_bytes()，根据你的字节序需求选择
 
         let len_start_idx = self.slot_size - Self::MESSAGE_LEN_BYTES;
         current_slot[len_start_idx..].copy_from_slice(&len_bytes);
@@ -433,7 +498,7 @@ impl QueueControlBlock {
 
         let len_start_idx = self.slot_size - Self::MESSAGE_LEN_BYTES;
         let len_bytes_slice = &current_slot[len_start_idx..];
-        let message_len = usize::from_le_bytes(len_bytes_slice.try_into().unwrap());
+        let message_len = u32::from_le_bytes(len_bytes_slice.try_into().unwrap()) as usize;
         // 将数据从队列槽位复制到调用者提供的缓冲区
         buffer[0..message_len].copy_from_slice(&current_slot[0..message_len]);
         *buffer_size = message_len;
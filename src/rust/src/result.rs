@@ -1,7 +1,10 @@
+#[cfg(feature = "cpup")]
+use crate::cpup::CpupError;
 use crate::{
-    event::error::EventError, interrupt::error::InterruptError, mutex::error::MutexError,
-    queue::error::QueueError, semaphore::error::SemaphoreError, stack::error::StackError,
-    task::error::TaskError, timer::TimerError,
+    barrier::error::BarrierError, condvar::error::CondvarError, event::error::EventError,
+    interrupt::error::InterruptError, mutex::error::MutexError, queue::error::QueueError,
+    rwlock::error::RwlockError, select::error::SelectError, semaphore::error::SemaphoreError,
+    stack::error::StackError, task::error::TaskError, timer::TimerError,
 };
 
 pub type SystemResult<T> = Result<T, SystemError>;
@@ -19,12 +22,23 @@ pub enum SystemError {
     Event(EventError),
     /// 互斥锁相关错误
     Mutex(MutexError),
+    /// 读写锁相关错误
+    Rwlock(RwlockError),
+    /// 条件变量相关错误
+    Condvar(CondvarError),
+    /// 屏障与倒计数锁存器相关错误
+    Barrier(BarrierError),
+    /// 多对象等待相关错误
+    Select(SelectError),
     /// 信号量相关错误
     Semaphore(SemaphoreError),
     /// 消息队列相关错误
     Queue(QueueError),
     /// 定时器相关错误
     Timer(TimerError),
+    /// CPU占用率统计相关错误
+    #[cfg(feature = "cpup")]
+    Cpup(CpupError),
     /// 未知错误码
     Unknown(u32),
 }
@@ -59,6 +73,30 @@ impl From<MutexError> for SystemError {
     }
 }
 
+impl From<RwlockError> for SystemError {
+    fn from(err: RwlockError) -> Self {
+        SystemError::Rwlock(err)
+    }
+}
+
+impl From<CondvarError> for SystemError {
+    fn from(err: CondvarError) -> Self {
+        SystemError::Condvar(err)
+    }
+}
+
+impl From<BarrierError> for SystemError {
+    fn from(err: BarrierError) -> Self {
+        SystemError::Barrier(err)
+    }
+}
+
+impl From<SelectError> for SystemError {
+    fn from(err: SelectError) -> Self {
+        SystemError::Select(err)
+    }
+}
+
 impl From<SemaphoreError> for SystemError {
     fn from(err: SemaphoreError) -> Self {
         SystemError::Semaphore(err)
@@ -77,6 +115,13 @@ impl From<TimerError> for SystemError {
     }
 }
 
+#[cfg(feature = "cpup")]
+impl From<CpupError> for SystemError {
+    fn from(err: CpupError) -> Self {
+        SystemError::Cpup(err)
+    }
+}
+
 impl From<SystemError> for u32 {
     fn from(error: SystemError) -> Self {
         match error {
@@ -85,9 +130,15 @@ impl From<SystemError> for u32 {
             SystemError::Stack(err) => u32::from(err),
             SystemError::Event(err) => u32::from(err),
             SystemError::Mutex(err) => u32::from(err),
+            SystemError::Rwlock(err) => u32::from(err),
+            SystemError::Condvar(err) => u32::from(err),
+            SystemError::Barrier(err) => u32::from(err),
+            SystemError::Select(err) => u32::from(err),
             SystemError::Semaphore(err) => u32::from(err),
             SystemError::Queue(err) => u32::from(err),
             SystemError::Timer(err) => u32::from(err),
+            #[cfg(feature = "cpup")]
+            SystemError::Cpup(err) => u32::from(err),
             SystemError::Unknown(errno) => errno,
         }
     }
@@ -101,9 +152,15 @@ impl core::fmt::Display for SystemError {
             SystemError::Stack(err) => write!(f, "Stack error: {}", err),
             SystemError::Event(err) => write!(f, "Event error: {}", err),
             SystemError::Mutex(err) => write!(f, "Mutex error: {}", err),
+            SystemError::Rwlock(err) => write!(f, "Rwlock error: {}", err),
+            SystemError::Condvar(err) => write!(f, "Condvar error: {}", err),
+            SystemError::Barrier(err) => write!(f, "Barrier error: {}", err),
+            SystemError::Select(err) => write!(f, "Select error: {}", err),
             SystemError::Semaphore(err) => write!(f, "Semaphore error: {}", err),
             SystemError::Queue(err) => write!(f, "Queue error: {}", err),
             SystemError::Timer(err) => write!(f, "Timer error: {}", err),
+            #[cfg(feature = "cpup")]
+            SystemError::Cpup(err) => write!(f, "Cpup error: {}", err),
             SystemError::Unknown(code) => write!(f, "Unknown error: 0x{:08x}", code),
         }
     }
@@ -127,6 +184,14 @@ impl From<ErrorCode> for SystemResult<()> {
                 Err(SystemError::Event(event_error))
             } else if let Ok(mutex_error) = MutexError::try_from(errno) {
                 Err(SystemError::Mutex(mutex_error))
+            } else if let Ok(rwlock_error) = RwlockError::try_from(errno) {
+                Err(SystemError::Rwlock(rwlock_error))
+            } else if let Ok(condvar_error) = CondvarError::try_from(errno) {
+                Err(SystemError::Condvar(condvar_error))
+            } else if let Ok(barrier_error) = BarrierError::try_from(errno) {
+                Err(SystemError::Barrier(barrier_error))
+            } else if let Ok(select_error) = SelectError::try_from(errno) {
+                Err(SystemError::Select(select_error))
             } else if let Ok(semaphore_error) = SemaphoreError::try_from(errno) {
                 Err(SystemError::Semaphore(semaphore_error))
             } else if let Ok(queue_error) = QueueError::try_from(errno) {
@@ -134,6 +199,10 @@ impl From<ErrorCode> for SystemResult<()> {
             } else if let Ok(timer_error) = TimerError::try_from(errno) {
                 Err(SystemError::Timer(timer_error))
             } else {
+                #[cfg(feature = "cpup")]
+                if let Ok(cpup_error) = CpupError::try_from(errno) {
+                    return Err(SystemError::Cpup(cpup_error));
+                }
                 Err(SystemError::Unknown(errno))
             }
         }
@@ -0,0 +1,381 @@
+use core::ptr::addr_of;
+
+use crate::{
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    mutex::priority::{PriorityInheritance, waiter_priority},
+    percpu::can_preempt_in_scheduler,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        sched::{schedule, schedule_reschedule},
+        sync::wait::{
+            task_wait, task_wait_interrupted, task_wait_owner_dead, task_wake,
+            task_wake_all_owner_dead,
+        },
+        types::{TaskCB, TaskPendObject, TaskStatus},
+    },
+    utils::list::LinkedList,
+};
+
+use super::{
+    error::RwlockError,
+    global::RwlockManager,
+    types::{RwlockControlBlock, RwlockId, RwlockPolicy, RwlockReadHold},
+};
+
+/// 查找任务在指定读写锁上的读锁记录
+fn find_read_hold(task: &mut TaskCB, id: RwlockId) -> Option<&mut RwlockReadHold> {
+    task.rwlock_reads
+        .iter_mut()
+        .find(|hold| hold.count != 0 && hold.id == id)
+}
+
+/// 任务是否还能再持有一个读锁
+fn can_hold_read(task: &mut TaskCB, id: RwlockId) -> bool {
+    find_read_hold(task, id).is_some() || task.rwlock_reads.iter().any(|hold| hold.count == 0)
+}
+
+/// 记录任务获取了一次读锁，调用前需确认[`can_hold_read`]
+fn add_read_hold(task: &mut TaskCB, id: RwlockId) {
+    if let Some(hold) = find_read_hold(task, id) {
+        hold.count += 1;
+        return;
+    }
+    if let Some(hold) = task.rwlock_reads.iter_mut().find(|hold| hold.count == 0) {
+        *hold = RwlockReadHold { id, count: 1 };
+    }
+}
+
+/// 按优先级查找写者的等待位置，同优先级先来先得
+fn find_write_position<'a>(
+    run_task: &TaskCB,
+    rwlock: &'a mut RwlockControlBlock,
+) -> &'a mut LinkedList {
+    let mut cur_task = TaskCB::from_pend_list(rwlock.write_list.next);
+    while addr_of!(cur_task.pend_list) != addr_of!(rwlock.write_list) {
+        if cur_task.priority > run_task.priority {
+            return &mut cur_task.pend_list;
+        }
+        cur_task = TaskCB::from_pend_list(cur_task.pend_list.next);
+    }
+    &mut rwlock.write_list
+}
+
+/// 把读写锁交给等待的任务，返回是否唤醒了任务
+fn grant(rwlock: &mut RwlockControlBlock) -> bool {
+    if !rwlock.writer.is_null() {
+        return false;
+    }
+    let writer_waiting = !LinkedList::is_empty(&raw const rwlock.write_list);
+    let reader_waiting = !LinkedList::is_empty(&raw const rwlock.read_list);
+
+    // 读锁全部释放后交给优先级最高的写者
+    if rwlock.read_count == 0
+        && writer_waiting
+        && (rwlock.policy == RwlockPolicy::WriterPreferred || !reader_waiting)
+    {
+        let resumed_task = TaskCB::from_pend_list(rwlock.write_list.next);
+        rwlock.set_writer(resumed_task);
+        rwlock.write_count = 1;
+        task_wake(resumed_task);
+        // 新的写者继承仍在等待的任务的优先级
+        PriorityInheritance::recompute(resumed_task);
+        return true;
+    }
+
+    // 写者优先时有写者等待便不再接纳读者
+    if !reader_waiting || (rwlock.policy == RwlockPolicy::WriterPreferred && writer_waiting) {
+        return false;
+    }
+    let rwlock_id = rwlock.get_id();
+    while !LinkedList::is_empty(&raw const rwlock.read_list) {
+        let resumed_task = TaskCB::from_pend_list(rwlock.read_list.next);
+        rwlock.read_count += 1;
+        // 读者等待前已确认有空闲的记录位置
+        add_read_hold(resumed_task, rwlock_id);
+        task_wake(resumed_task);
+    }
+    true
+}
+
+fn pend_operation(
+    run_task: &mut TaskCB,
+    rwlock: &mut RwlockControlBlock,
+    write: bool,
+    timeout: u32,
+    int_save: &mut u32,
+) -> SystemResult<()> {
+    // 如果不等待
+    if timeout == 0 {
+        return Err(RwlockError::Unavailable.into());
+    }
+
+    // 检查是否可以调度
+    if !can_preempt_in_scheduler() {
+        return Err(RwlockError::PendInLock.into());
+    }
+
+    // 写锁被持有时由写者继承等待任务的优先级
+    if !rwlock.writer.is_null() {
+        PriorityInheritance::handle_mutex_pend(run_task, unsafe { &mut *rwlock.writer });
+    }
+
+    let rwlock_id = rwlock.get_id();
+    let wait_pos = if write {
+        find_write_position(run_task, rwlock)
+    } else {
+        &mut rwlock.read_list
+    };
+    task_wait(wait_pos, TaskPendObject::Rwlock(rwlock_id.into()), timeout);
+
+    // 立即调度
+    schedule_reschedule();
+
+    // 解锁并重新加锁
+    restore_interrupt_state(*int_save);
+
+    *int_save = disable_interrupts();
+
+    // 持有者被删除，读写锁已被释放
+    if task_wait_owner_dead(run_task) {
+        return Err(SystemError::Task(TaskError::OwnerDead));
+    }
+
+    let interrupted = task_wait_interrupted(run_task);
+    let timed_out = run_task.task_status.contains(TaskStatus::TIMEOUT);
+    run_task.task_status.remove(TaskStatus::TIMEOUT);
+    if !interrupted && !timed_out {
+        return Ok(());
+    }
+
+    // 撤销对写者的优先级继承，离开等待队列后可能有其他任务可以加锁
+    if !rwlock.writer.is_null() {
+        PriorityInheritance::restore_priority_on_timeout(unsafe { &mut *rwlock.writer });
+    }
+    if grant(rwlock) {
+        schedule_reschedule();
+    }
+
+    if interrupted {
+        Err(SystemError::Task(TaskError::Interrupted))
+    } else {
+        Err(RwlockError::Timeout.into())
+    }
+}
+
+/// 加锁前的公共检查
+fn check_rwlock(rwlock: &RwlockControlBlock, id: RwlockId) -> SystemResult<()> {
+    if rwlock.is_unused() || !rwlock.matches_id(id) {
+        return Err(RwlockError::Invalid.into());
+    }
+    if is_interrupt_active() {
+        return Err(RwlockError::PendInterrupt.into());
+    }
+    Ok(())
+}
+
+/// 读写锁系统初始化
+pub fn rwlock_init() {
+    RwlockManager::initialize();
+}
+
+/// 创建读写锁
+pub fn rwlock_create(policy: RwlockPolicy) -> SystemResult<RwlockId> {
+    let int_save = disable_interrupts();
+
+    if !RwlockManager::has_available_rwlock() {
+        restore_interrupt_state(int_save);
+        return Err(RwlockError::AllBusy.into());
+    }
+    let id = RwlockManager::allocate(policy);
+
+    restore_interrupt_state(int_save);
+    Ok(id)
+}
+
+/// 删除读写锁
+pub fn rwlock_delete(id: RwlockId) -> SystemResult<()> {
+    let rwlock = RwlockManager::get_rwlock_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let result = RwlockManager::deallocate(rwlock, id);
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 获取读锁
+///
+/// 写锁未被持有时直接加锁；[`RwlockPolicy::WriterPreferred`]下有写者等待时新的读者也要等待。
+/// 已持有读锁的任务可以递归加锁，不受等待的写者影响。持有写锁的任务再加读锁返回
+/// [`RwlockError::Deadlock`]，同时持有的读锁超过[`RWLOCK_READ_HOLD_LIMIT`]个时返回
+/// [`RwlockError::ReadHoldFull`]。
+///
+/// [`RWLOCK_READ_HOLD_LIMIT`]: crate::config::RWLOCK_READ_HOLD_LIMIT
+pub fn rwlock_rdlock(id: RwlockId, timeout: u32) -> SystemResult<()> {
+    let rwlock = RwlockManager::get_rwlock_mut(id)?;
+
+    let mut int_save = disable_interrupts();
+
+    let run_task = get_current_task();
+
+    let result = check_rwlock(rwlock, id).and_then(|()| {
+        if rwlock.is_writer(run_task) {
+            return Err(RwlockError::Deadlock.into());
+        }
+        if !can_hold_read(run_task, id) {
+            return Err(RwlockError::ReadHoldFull.into());
+        }
+        if rwlock.can_read() || find_read_hold(run_task, id).is_some() {
+            rwlock.read_count += 1;
+            add_read_hold(run_task, id);
+            return Ok(());
+        }
+        pend_operation(run_task, rwlock, false, timeout, &mut int_save)
+    });
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 获取写锁
+///
+/// 持有写锁的任务可以递归加锁。持有读锁的任务再加写锁会等待自己释放读锁，返回
+/// [`RwlockError::Deadlock`]。
+pub fn rwlock_wrlock(id: RwlockId, timeout: u32) -> SystemResult<()> {
+    let rwlock = RwlockManager::get_rwlock_mut(id)?;
+
+    let mut int_save = disable_interrupts();
+
+    let run_task = get_current_task();
+
+    let result = check_rwlock(rwlock, id).and_then(|()| {
+        // 递归加锁
+        if rwlock.is_writer(run_task) {
+            rwlock.write_count += 1;
+            return Ok(());
+        }
+        if find_read_hold(run_task, id).is_some() {
+            return Err(RwlockError::Deadlock.into());
+        }
+        if !rwlock.is_locked() {
+            rwlock.set_writer(run_task);
+            rwlock.write_count = 1;
+            return Ok(());
+        }
+        pend_operation(run_task, rwlock, true, timeout, &mut int_save)
+    });
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 释放读锁或写锁
+///
+/// 读写锁被持有但当前任务既不是写者也没有持有读锁时返回[`RwlockError::NotOwner`]。
+pub fn rwlock_unlock(id: RwlockId) -> SystemResult<()> {
+    let rwlock = RwlockManager::get_rwlock_mut(id)?;
+
+    let int_save = disable_interrupts();
+
+    let run_task = get_current_task();
+
+    if let Err(e) = check_rwlock(rwlock, id) {
+        restore_interrupt_state(int_save);
+        return Err(e);
+    }
+
+    let mut lowered = false;
+    if rwlock.is_writer(run_task) {
+        rwlock.write_count -= 1;
+        if rwlock.write_count != 0 {
+            restore_interrupt_state(int_save);
+            return Ok(());
+        }
+        rwlock.clear_writer();
+        // 撤销等待者带来的优先级继承
+        lowered = PriorityInheritance::recompute(run_task);
+    } else if let Some(hold) = find_read_hold(run_task, id) {
+        hold.count -= 1;
+        rwlock.read_count -= 1;
+    } else {
+        restore_interrupt_state(int_save);
+        return Err(if rwlock.is_locked() {
+            RwlockError::NotOwner
+        } else {
+            RwlockError::NotLocked
+        }
+        .into());
+    }
+
+    let need_schedule = grant(rwlock) || lowered;
+
+    restore_interrupt_state(int_save);
+
+    if need_schedule {
+        schedule();
+    }
+
+    Ok(())
+}
+
+/// 唤醒读写锁上的所有等待者，等待者返回[`TaskError::OwnerDead`]，返回是否唤醒了任务
+fn wake_all_owner_dead(rwlock: &mut RwlockControlBlock) -> bool {
+    let read_woken = task_wake_all_owner_dead(&mut rwlock.read_list);
+    task_wake_all_owner_dead(&mut rwlock.write_list) || read_woken
+}
+
+/// 释放被删除任务持有的读锁和写锁，须在关中断后调用，返回是否唤醒了任务
+///
+/// 读写锁没有拥有者策略，被删除任务持有的读写锁总是被释放，其上的所有等待者返回
+/// [`TaskError::OwnerDead`]，可以重新加锁。
+pub(crate) fn rwlock_owner_dead(task_cb: &mut TaskCB, count: &mut u16) -> bool {
+    let mut woken = false;
+
+    // 读锁记录在任务自己的控制块中
+    for hold in task_cb.rwlock_reads.iter_mut() {
+        if hold.count == 0 {
+            continue;
+        }
+        let (id, held) = (hold.id, hold.count);
+        *hold = RwlockReadHold::UNINIT;
+        let Ok(rwlock) = RwlockManager::get_rwlock_mut(id) else {
+            continue;
+        };
+        if rwlock.is_unused() || !rwlock.matches_id(id) {
+            continue;
+        }
+        rwlock.read_count -= held as u32;
+        woken |= wake_all_owner_dead(rwlock);
+        *count += 1;
+    }
+
+    let list = &raw mut task_cb.held_rwlocks;
+    while !LinkedList::is_empty(list) {
+        let rwlock = RwlockControlBlock::from_held_node(LinkedList::first(list));
+        rwlock.clear_writer();
+        rwlock.write_count = 0;
+        woken |= wake_all_owner_dead(rwlock);
+        *count += 1;
+    }
+    woken
+}
+
+/// 获取任务作为写者所持读写锁上等待者的最高优先级
+pub(crate) fn rwlock_waiter_priority(owner_task: &TaskCB) -> Option<u16> {
+    let mut priority = None;
+    let held_list = addr_of!(owner_task.held_rwlocks);
+    let mut node = owner_task.held_rwlocks.next;
+    while !core::ptr::eq(node, held_list) {
+        let rwlock = RwlockControlBlock::from_held_node(node);
+        for list in [&raw const rwlock.read_list, &raw const rwlock.write_list] {
+            if let Some(waiter_priority) = waiter_priority(list) {
+                priority = Some(priority.map_or(waiter_priority, |p: u16| p.min(waiter_priority)));
+            }
+        }
+        node = rwlock.held_node.next;
+    }
+    priority
+}
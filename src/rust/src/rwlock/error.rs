@@ -0,0 +1,111 @@
+//! 读写锁错误码定义
+
+/// 读写锁操作错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RwlockError {
+    /// 读写锁句柄无效
+    Invalid,
+    /// 读写锁指针为空
+    PtrNull,
+    /// 所有读写锁都在使用中
+    AllBusy,
+    /// 读写锁不可用
+    Unavailable,
+    /// 在中断中操作读写锁
+    PendInterrupt,
+    /// 在调度锁定状态下等待读写锁
+    PendInLock,
+    /// 等待读写锁超时
+    Timeout,
+    /// 读写锁被持有或有任务等待，无法删除
+    Pended,
+    /// 读写锁未被持有
+    NotLocked,
+    /// 持有写锁的任务加读锁
+    Deadlock,
+    /// 读写锁策略无效
+    PolicyInvalid,
+    /// 解锁的任务没有持有读写锁
+    NotOwner,
+    /// 任务持有的读锁数达到上限
+    ReadHoldFull,
+}
+
+impl From<RwlockError> for u32 {
+    fn from(err: RwlockError) -> u32 {
+        match err {
+            RwlockError::Invalid => ERRNO_RWLOCK_INVALID,
+            RwlockError::PtrNull => ERRNO_RWLOCK_PTR_NULL,
+            RwlockError::AllBusy => ERRNO_RWLOCK_ALL_BUSY,
+            RwlockError::Unavailable => ERRNO_RWLOCK_UNAVAILABLE,
+            RwlockError::PendInterrupt => ERRNO_RWLOCK_PEND_INTERR,
+            RwlockError::PendInLock => ERRNO_RWLOCK_PEND_IN_LOCK,
+            RwlockError::Timeout => ERRNO_RWLOCK_TIMEOUT,
+            RwlockError::Pended => ERRNO_RWLOCK_PENDED,
+            RwlockError::NotLocked => ERRNO_RWLOCK_NOT_LOCKED,
+            RwlockError::Deadlock => ERRNO_RWLOCK_DEADLOCK,
+            RwlockError::PolicyInvalid => ERRNO_RWLOCK_POLICY_INVALID,
+            RwlockError::NotOwner => ERRNO_RWLOCK_NOT_OWNER,
+            RwlockError::ReadHoldFull => ERRNO_RWLOCK_READ_HOLD_FULL,
+        }
+    }
+}
+
+impl TryFrom<u32> for RwlockError {
+    type Error = ();
+
+    fn try_from(errno: u32) -> Result<Self, Self::Error> {
+        match errno {
+            ERRNO_RWLOCK_INVALID => Ok(RwlockError::Invalid),
+            ERRNO_RWLOCK_PTR_NULL => Ok(RwlockError::PtrNull),
+            ERRNO_RWLOCK_ALL_BUSY => Ok(RwlockError::AllBusy),
+            ERRNO_RWLOCK_UNAVAILABLE => Ok(RwlockError::Unavailable),
+            ERRNO_RWLOCK_PEND_INTERR => Ok(RwlockError::PendInterrupt),
+            ERRNO_RWLOCK_PEND_IN_LOCK => Ok(RwlockError::PendInLock),
+            ERRNO_RWLOCK_TIMEOUT => Ok(RwlockError::Timeout),
+            ERRNO_RWLOCK_PENDED => Ok(RwlockError::Pended),
+            ERRNO_RWLOCK_NOT_LOCKED => Ok(RwlockError::NotLocked),
+            ERRNO_RWLOCK_DEADLOCK => Ok(RwlockError::Deadlock),
+            ERRNO_RWLOCK_POLICY_INVALID => Ok(RwlockError::PolicyInvalid),
+            ERRNO_RWLOCK_NOT_OWNER => Ok(RwlockError::NotOwner),
+            ERRNO_RWLOCK_READ_HOLD_FULL => Ok(RwlockError::ReadHoldFull),
+            _ => Err(()),
+        }
+    }
+}
+
+const ERRNO_RWLOCK_INVALID: u32 = 0x02001f01;
+const ERRNO_RWLOCK_PTR_NULL: u32 = 0x02001f02;
+const ERRNO_RWLOCK_ALL_BUSY: u32 = 0x02001f03;
+const ERRNO_RWLOCK_UNAVAILABLE: u32 = 0x02001f04;
+const ERRNO_RWLOCK_PEND_INTERR: u32 = 0x02001f05;
+const ERRNO_RWLOCK_PEND_IN_LOCK: u32 = 0x02001f06;
+const ERRNO_RWLOCK_TIMEOUT: u32 = 0x02001f07;
+const ERRNO_RWLOCK_PENDED: u32 = 0x02001f08;
+const ERRNO_RWLOCK_NOT_LOCKED: u32 = 0x02001f09;
+const ERRNO_RWLOCK_DEADLOCK: u32 = 0x02001f0a;
+const ERRNO_RWLOCK_POLICY_INVALID: u32 = 0x02001f0b;
+const ERRNO_RWLOCK_NOT_OWNER: u32 = 0x02001f0c;
+const ERRNO_RWLOCK_READ_HOLD_FULL: u32 = 0x02001f0d;
+
+impl core::fmt::Display for RwlockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let desc = match self {
+            Self::Invalid => "Invalid rwlock handle",
+            Self::PtrNull => "Rwlock pointer is null",
+            Self::AllBusy => "All rwlocks are busy",
+            Self::Unavailable => "Rwlock is unavailable",
+            Self::PendInterrupt => "Cannot operate rwlock in interrupt context",
+            Self::PendInLock => "Cannot wait for rwlock in scheduler locked state",
+            Self::Timeout => "Rwlock wait timeout",
+            Self::Pended => "Rwlock is held or pended and cannot be deleted",
+            Self::NotLocked => "Rwlock is not held",
+            Self::Deadlock => "Read lock requested by the write lock holder",
+            Self::PolicyInvalid => "Invalid rwlock policy",
+            Self::NotOwner => "Unlocking task holds no rwlock",
+            Self::ReadHoldFull => "Task holds too many read locks",
+        };
+        write!(f, "{}", desc)
+    }
+}
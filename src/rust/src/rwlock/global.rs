@@ -0,0 +1,73 @@
+//! 读写锁全局变量
+use crate::config::RWLOCK_LIMIT;
+use crate::result::SystemResult;
+use crate::utils::list::LinkedList;
+
+use super::error::RwlockError;
+use super::types::{RwlockControlBlock, RwlockId, RwlockPolicy};
+
+pub static mut RWLOCK_POOL: [RwlockControlBlock; RWLOCK_LIMIT as usize] =
+    [RwlockControlBlock::UNINIT; RWLOCK_LIMIT as usize];
+
+pub static mut UNUSED_RWLOCK_LIST: LinkedList = LinkedList::new();
+
+pub struct RwlockManager;
+
+impl RwlockManager {
+    /// 初始化读写锁池
+    #[inline]
+    pub fn initialize() {
+        LinkedList::init(&raw mut UNUSED_RWLOCK_LIST);
+        for id in 0..RWLOCK_LIMIT {
+            let rwlock = Self::get_rwlock_by_id(id);
+            rwlock.id = id.into();
+            LinkedList::tail_insert(&raw mut UNUSED_RWLOCK_LIST, &raw mut rwlock.read_list);
+        }
+    }
+
+    /// 检查是否有可用的读写锁
+    #[inline]
+    pub fn has_available_rwlock() -> bool {
+        !LinkedList::is_empty(&raw const UNUSED_RWLOCK_LIST)
+    }
+
+    // 通过索引获取读写锁
+    #[inline]
+    fn get_rwlock_by_id(id: u32) -> &'static mut RwlockControlBlock {
+        unsafe { &mut RWLOCK_POOL[id as usize] }
+    }
+
+    /// 分配一个新的读写锁
+    #[inline]
+    pub fn allocate(policy: RwlockPolicy) -> RwlockId {
+        let node = LinkedList::first(&raw const UNUSED_RWLOCK_LIST);
+        LinkedList::remove(node);
+        let rwlock = RwlockControlBlock::from_read_list(node);
+        rwlock.initialize(policy);
+        rwlock.get_id()
+    }
+
+    /// 释放读写锁
+    #[inline]
+    pub fn deallocate(rwlock: &mut RwlockControlBlock, id: RwlockId) -> SystemResult<()> {
+        if !rwlock.matches_id(id) || rwlock.is_unused() {
+            return Err(RwlockError::Invalid.into());
+        }
+        if rwlock.has_waiting_tasks() || rwlock.is_locked() {
+            return Err(RwlockError::Pended.into());
+        }
+        rwlock.reset();
+        LinkedList::tail_insert(&raw mut UNUSED_RWLOCK_LIST, &raw mut rwlock.read_list);
+        Ok(())
+    }
+
+    /// 获取读写锁
+    #[inline]
+    pub fn get_rwlock_mut(id: RwlockId) -> SystemResult<&'static mut RwlockControlBlock> {
+        let index = id.get_index() as u32;
+        if index >= RWLOCK_LIMIT {
+            return Err(RwlockError::Invalid.into());
+        }
+        Ok(Self::get_rwlock_by_id(index))
+    }
+}
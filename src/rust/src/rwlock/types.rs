@@ -0,0 +1,227 @@
+//! 读写锁相关类型定义
+
+use crate::{container_of, task::types::TaskCB, utils::list::LinkedList};
+
+use super::error::RwlockError;
+
+/// 读写锁状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RwlockState {
+    Unused = 0,
+    Used = 1,
+}
+
+/// 读者与写者同时等待时的授予策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum RwlockPolicy {
+    /// 读者优先，读锁被持有时新的读者可以直接加锁
+    #[default]
+    ReaderPreferred = 0,
+    /// 写者优先，有写者等待时新的读者也要等待
+    WriterPreferred = 1,
+}
+
+impl TryFrom<u32> for RwlockPolicy {
+    type Error = RwlockError;
+
+    fn try_from(policy: u32) -> Result<Self, Self::Error> {
+        match policy {
+            0 => Ok(Self::ReaderPreferred),
+            1 => Ok(Self::WriterPreferred),
+            _ => Err(RwlockError::PolicyInvalid),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct RwlockId(pub u32);
+
+impl RwlockId {
+    const RWLOCK_SPLIT_BIT: u32 = 16;
+
+    pub fn new(count: u16, index: u16) -> Self {
+        let id = ((count as u32) << Self::RWLOCK_SPLIT_BIT) | (index as u32);
+        Self(id)
+    }
+
+    /// 获取读写锁索引部分
+    pub fn get_index(self) -> u16 {
+        (self.0 & ((1 << Self::RWLOCK_SPLIT_BIT) - 1)) as u16
+    }
+
+    /// 获取读写锁计数部分
+    pub fn get_count(self) -> u16 {
+        (self.0 >> Self::RWLOCK_SPLIT_BIT) as u16
+    }
+
+    /// 增加计数值生成新ID，保持索引不变
+    pub fn increment_count(&self) -> Self {
+        Self::new(self.get_count().wrapping_add(1), self.get_index())
+    }
+}
+
+impl From<u32> for RwlockId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<RwlockId> for u32 {
+    fn from(id: RwlockId) -> Self {
+        id.0
+    }
+}
+
+/// 任务持有的一个读锁，加锁次数为0表示空闲
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RwlockReadHold {
+    /// 读写锁ID
+    pub id: RwlockId,
+    /// 读锁的递归加锁次数
+    pub count: u16,
+}
+
+impl RwlockReadHold {
+    pub const UNINIT: Self = Self {
+        id: RwlockId(0),
+        count: 0,
+    };
+}
+
+/// 读写锁控制块
+#[repr(C)]
+#[derive(Debug)]
+pub struct RwlockControlBlock {
+    /// 等待读锁的任务列表，未使用时挂入空闲链表
+    pub read_list: LinkedList,
+
+    /// 等待写锁的任务列表，按优先级排序
+    pub write_list: LinkedList,
+
+    /// 持有写锁的任务
+    pub writer: *mut TaskCB,
+
+    /// 写锁的递归加锁次数
+    pub write_count: u16,
+
+    /// 持有读锁的次数
+    pub read_count: u32,
+
+    /// 授予策略
+    pub policy: RwlockPolicy,
+
+    /// 读写锁状态
+    pub state: RwlockState,
+
+    /// 读写锁ID
+    pub id: RwlockId,
+
+    /// 持有写锁时挂入写者的held_rwlocks
+    pub held_node: LinkedList,
+}
+
+impl RwlockControlBlock {
+    pub const UNINIT: Self = Self {
+        read_list: LinkedList::new(),
+        write_list: LinkedList::new(),
+        writer: core::ptr::null_mut(),
+        write_count: 0,
+        read_count: 0,
+        policy: RwlockPolicy::ReaderPreferred,
+        state: RwlockState::Unused,
+        id: RwlockId(0),
+        held_node: LinkedList::new(),
+    };
+
+    pub fn is_unused(&self) -> bool {
+        self.state == RwlockState::Unused
+    }
+
+    /// 获取读写锁ID
+    pub fn get_id(&self) -> RwlockId {
+        self.id
+    }
+
+    /// 检查是否为指定的句柄
+    pub fn matches_id(&self, id: RwlockId) -> bool {
+        self.id == id
+    }
+
+    /// 检查指定任务是否持有写锁
+    pub fn is_writer(&self, task_cb: *mut TaskCB) -> bool {
+        !self.writer.is_null() && self.writer == task_cb
+    }
+
+    /// 设置写者，并把读写锁挂入写者的held_rwlocks
+    pub fn set_writer(&mut self, task: &mut TaskCB) {
+        self.clear_writer();
+        LinkedList::tail_insert(&raw mut task.held_rwlocks, &raw mut self.held_node);
+        self.writer = task;
+    }
+
+    /// 清除写者
+    pub fn clear_writer(&mut self) {
+        if !self.writer.is_null() {
+            LinkedList::remove(&raw mut self.held_node);
+        }
+        self.writer = core::ptr::null_mut();
+    }
+
+    /// 检查是否被持有
+    pub fn is_locked(&self) -> bool {
+        !self.writer.is_null() || self.read_count > 0
+    }
+
+    /// 检查是否有等待的任务
+    pub fn has_waiting_tasks(&self) -> bool {
+        !LinkedList::is_empty(&raw const self.read_list)
+            || !LinkedList::is_empty(&raw const self.write_list)
+    }
+
+    /// 新的读者能否直接加锁
+    pub fn can_read(&self) -> bool {
+        self.writer.is_null()
+            && (self.policy == RwlockPolicy::ReaderPreferred
+                || LinkedList::is_empty(&raw const self.write_list))
+    }
+
+    /// 初始化读写锁
+    pub fn initialize(&mut self, policy: RwlockPolicy) {
+        self.writer = core::ptr::null_mut();
+        self.write_count = 0;
+        self.read_count = 0;
+        self.policy = policy;
+        LinkedList::init(&raw mut self.read_list);
+        LinkedList::init(&raw mut self.write_list);
+        self.state = RwlockState::Used;
+    }
+
+    /// 重置读写锁
+    pub fn reset(&mut self) {
+        self.clear_writer();
+        self.write_count = 0;
+        self.read_count = 0;
+        self.state = RwlockState::Unused;
+        self.id = self.id.increment_count();
+    }
+
+    pub fn from_read_list(ptr: *mut LinkedList) -> &'static mut RwlockControlBlock {
+        let rwlock_ptr = container_of!(ptr, RwlockControlBlock, read_list);
+        unsafe { &mut *rwlock_ptr }
+    }
+
+    pub fn from_held_node(ptr: *mut LinkedList) -> &'static mut RwlockControlBlock {
+        let rwlock_ptr = container_of!(ptr, RwlockControlBlock, held_node);
+        unsafe { &mut *rwlock_ptr }
+    }
+}
+
+impl Default for RwlockControlBlock {
+    fn default() -> Self {
+        Self::UNINIT
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    config::SELECT_OBJECT_LIMIT,
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    percpu::can_preempt_in_scheduler,
+    queue::global::QUEUE_POOL,
+    result::{SystemError, SystemResult},
+    semaphore::global::SemaphoreManager,
+    task::{
+        error::TaskError,
+        sched::schedule_reschedule,
+        sync::wait::{task_wait, task_wait_interrupted, task_wake},
+        types::{TaskCB, TaskPendObject, TaskStatus},
+    },
+    timer::{timer_expired_take, timer_select_poll},
+    utils::list::LinkedList,
+};
+
+use super::{
+    error::SelectError,
+    types::{SelectNode, SelectObject},
+};
+
+/// 检查对象是否就绪，同时返回对象的多对象等待链表
+fn poll_object(object: &SelectObject) -> SystemResult<(bool, *mut LinkedList)> {
+    match *object {
+        SelectObject::Queue(id) => critical_section::with(|cs| {
+            let mut queue_pool = QUEUE_POOL.borrow_ref_mut(cs);
+            match queue_pool.get_mut(id.get_index() as usize) {
+                Some(queue) if queue.matches_id(id) && !queue.is_unused() => {
+                    Ok((!queue.is_empty(), &raw mut queue.select_list))
+                }
+                _ => Err(SelectError::ObjectInvalid.into()),
+            }
+        }),
+        SelectObject::Semaphore(id) => match SemaphoreManager::get_semaphore(id) {
+            Ok(semaphore) if semaphore.matches_id(id) && !semaphore.is_unused() => {
+                Ok((semaphore.get_count() > 0, &raw mut semaphore.select_list))
+            }
+            _ => Err(SelectError::ObjectInvalid.into()),
+        },
+        SelectObject::EventGroup(group, mask) => {
+            if group.is_null() || mask == 0 {
+                return Err(SelectError::ObjectInvalid.into());
+            }
+            let group = unsafe { &mut *group };
+            Ok((group.bits & mask != 0, &raw mut group.select_list))
+        }
+        SelectObject::Timer(id) => timer_select_poll(id)
+            .map(|(expired, list)| (expired > 0, list))
+            .map_err(|_| SelectError::ObjectInvalid.into()),
+    }
+}
+
+/// 报告对象就绪后清除定时器的超时计数
+fn consume(object: &SelectObject) {
+    if let SelectObject::Timer(id) = *object {
+        let _ = timer_expired_take(id);
+    }
+}
+
+/// 唤醒对象多对象等待链表上满足条件的节点所属的任务，返回是否唤醒了任务
+fn wake_nodes(list: *mut LinkedList, mut hit: impl FnMut(&mut SelectNode) -> bool) -> bool {
+    let mut woken = false;
+    let mut cur = unsafe { (*list).next };
+    while cur != list {
+        let node = SelectNode::from_list(cur);
+        cur = node.list.next;
+        if !hit(node) {
+            continue;
+        }
+        let task = unsafe { &mut *node.task };
+        if task.task_status.contains(TaskStatus::PEND) && task.pend_object == TaskPendObject::Select
+        {
+            task_wake(task);
+            woken = true;
+        }
+    }
+    woken
+}
+
+/// 从对象上摘除等待节点，对象可能已先行摘除
+fn unlink(node: &mut SelectNode) {
+    if !node.list.next.is_null() {
+        LinkedList::remove(&raw mut node.list);
+    }
+}
+
+/// 等待多个对象中的任意一个就绪，返回就绪对象在`objects`中的下标
+///
+/// 只报告就绪，不获取对象：返回后由调用者以不等待的方式读取队列、获取信号量或等待事件组，
+/// 对象可能已被其他任务取走。一次最多等待[`SELECT_OBJECT_LIMIT`]个对象。
+pub fn wait_any(objects: &[SelectObject], timeout: u32) -> SystemResult<usize> {
+    if objects.is_empty() || objects.len() > SELECT_OBJECT_LIMIT as usize {
+        return Err(SelectError::SetInvalid.into());
+    }
+
+    let mut int_save = disable_interrupts();
+
+    // 已有就绪的对象时直接返回
+    let mut lists = [core::ptr::null_mut(); SELECT_OBJECT_LIMIT as usize];
+    for (index, object) in objects.iter().enumerate() {
+        match poll_object(object) {
+            Ok((true, _)) => {
+                consume(object);
+                restore_interrupt_state(int_save);
+                return Ok(index);
+            }
+            Ok((false, list)) => lists[index] = list,
+            Err(e) => {
+                restore_interrupt_state(int_save);
+                return Err(e);
+            }
+        }
+    }
+
+    let check = if is_interrupt_active() {
+        Err(SelectError::PendInterrupt)
+    } else if timeout == 0 {
+        Err(SelectError::Unavailable)
+    } else if !can_preempt_in_scheduler() {
+        Err(SelectError::PendInLock)
+    } else {
+        Ok(())
+    };
+    if let Err(e) = check {
+        restore_interrupt_state(int_save);
+        return Err(e.into());
+    }
+
+    let run_task = get_current_task();
+
+    // 每个对象一个等待节点，挂在各对象自己的等待链表上；任务本身挂在栈上的空链表中以复用等待与超时机制
+    let mut nodes = [SelectNode::UNINIT; SELECT_OBJECT_LIMIT as usize];
+    let nodes = &mut nodes[..objects.len()];
+    for ((node, object), list) in nodes.iter_mut().zip(objects).zip(lists) {
+        *node = SelectNode::new(run_task, *object);
+        LinkedList::tail_insert(list, &raw mut node.list);
+    }
+    run_task.select_nodes = nodes.as_mut_ptr();
+    run_task.select_count = nodes.len() as u32;
+    let mut wait_head = LinkedList::new();
+    LinkedList::init(&raw mut wait_head);
+    task_wait(&mut wait_head, TaskPendObject::Select, timeout);
+
+    // 立即调度
+    schedule_reschedule();
+
+    // 解锁并重新加锁
+    restore_interrupt_state(int_save);
+
+    int_save = disable_interrupts();
+
+    for node in nodes.iter_mut() {
+        unlink(node);
+    }
+    run_task.select_nodes = core::ptr::null_mut();
+    run_task.select_count = 0;
+
+    let result = if task_wait_interrupted(run_task) {
+        Err(SystemError::Task(TaskError::Interrupted))
+    } else if run_task.task_status.contains(TaskStatus::TIMEOUT) {
+        run_task.task_status.remove(TaskStatus::TIMEOUT);
+        Err(SelectError::Timeout.into())
+    } else if let Some(index) = nodes.iter().position(|node| node.fired) {
+        consume(&objects[index]);
+        Ok(index)
+    } else if nodes.iter().any(|node| node.deleted) {
+        Err(SelectError::ObjectDeleted.into())
+    } else {
+        Err(SelectError::Unavailable.into())
+    };
+
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 通知等待该对象的任务对象已就绪，须在关中断后调用，返回是否唤醒了任务
+///
+/// `list`为对象的多对象等待链表，`ready`按节点记录的对象判断是否就绪，用于事件掩码等需要逐个判断的条件。
+pub(crate) fn select_notify(list: *mut LinkedList, ready: impl Fn(&SelectObject) -> bool) -> bool {
+    // 没有任务在等待时不遍历
+    if LinkedList::is_empty(list) {
+        return false;
+    }
+    wake_nodes(list, |node| {
+        if !ready(&node.object) {
+            return false;
+        }
+        node.fired = true;
+        true
+    })
+}
+
+/// 对象被删除时唤醒等待它的任务，须在关中断后调用，返回是否唤醒了任务
+///
+/// 节点随即从对象上摘除，对象被重新创建后不会再引用旧的节点。
+pub(crate) fn select_object_deleted(list: *mut LinkedList) -> bool {
+    if LinkedList::is_empty(list) {
+        return false;
+    }
+    let woken = wake_nodes(list, |node| {
+        node.deleted = true;
+        true
+    });
+    while !LinkedList::is_empty(list) {
+        LinkedList::remove(LinkedList::first(list));
+    }
+    woken
+}
+
+/// 从各对象上摘除被删除任务的等待节点，须在关中断后调用
+///
+/// 等待节点在被删除任务的栈上，任务被唤醒后尚未运行时也可能仍挂在对象上。
+pub(crate) fn select_cancel(task_cb: &mut TaskCB) {
+    if task_cb.select_nodes.is_null() {
+        return;
+    }
+    let nodes = unsafe {
+        core::slice::from_raw_parts_mut(task_cb.select_nodes, task_cb.select_count as usize)
+    };
+    for node in nodes {
+        unlink(node);
+    }
+    task_cb.select_nodes = core::ptr::null_mut();
+    task_cb.select_count = 0;
+}
@@ -0,0 +1,81 @@
+//! 多对象等待错误码定义
+
+/// 多对象等待操作错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum SelectError {
+    /// 等待的对象集合为空或超过上限
+    SetInvalid,
+    /// 指针为空
+    PtrNull,
+    /// 集合中有无效的对象
+    ObjectInvalid,
+    /// 没有就绪的对象且不等待
+    Unavailable,
+    /// 在中断中等待
+    PendInterrupt,
+    /// 在调度锁定状态下等待
+    PendInLock,
+    /// 等待超时
+    Timeout,
+    /// 等待的对象在等待期间被删除
+    ObjectDeleted,
+}
+
+impl From<SelectError> for u32 {
+    fn from(err: SelectError) -> u32 {
+        match err {
+            SelectError::SetInvalid => ERRNO_SELECT_SET_INVALID,
+            SelectError::PtrNull => ERRNO_SELECT_PTR_NULL,
+            SelectError::ObjectInvalid => ERRNO_SELECT_OBJECT_INVALID,
+            SelectError::Unavailable => ERRNO_SELECT_UNAVAILABLE,
+            SelectError::PendInterrupt => ERRNO_SELECT_PEND_INTERR,
+            SelectError::PendInLock => ERRNO_SELECT_PEND_IN_LOCK,
+            SelectError::Timeout => ERRNO_SELECT_TIMEOUT,
+            SelectError::ObjectDeleted => ERRNO_SELECT_OBJECT_DELETED,
+        }
+    }
+}
+
+impl TryFrom<u32> for SelectError {
+    type Error = ();
+
+    fn try_from(errno: u32) -> Result<Self, Self::Error> {
+        match errno {
+            ERRNO_SELECT_SET_INVALID => Ok(SelectError::SetInvalid),
+            ERRNO_SELECT_PTR_NULL => Ok(SelectError::PtrNull),
+            ERRNO_SELECT_OBJECT_INVALID => Ok(SelectError::ObjectInvalid),
+            ERRNO_SELECT_UNAVAILABLE => Ok(SelectError::Unavailable),
+            ERRNO_SELECT_PEND_INTERR => Ok(SelectError::PendInterrupt),
+            ERRNO_SELECT_PEND_IN_LOCK => Ok(SelectError::PendInLock),
+            ERRNO_SELECT_TIMEOUT => Ok(SelectError::Timeout),
+            ERRNO_SELECT_OBJECT_DELETED => Ok(SelectError::ObjectDeleted),
+            _ => Err(()),
+        }
+    }
+}
+
+const ERRNO_SELECT_SET_INVALID: u32 = 0x02002201;
+const ERRNO_SELECT_PTR_NULL: u32 = 0x02002202;
+const ERRNO_SELECT_OBJECT_INVALID: u32 = 0x02002203;
+const ERRNO_SELECT_UNAVAILABLE: u32 = 0x02002204;
+const ERRNO_SELECT_PEND_INTERR: u32 = 0x02002205;
+const ERRNO_SELECT_PEND_IN_LOCK: u32 = 0x02002206;
+const ERRNO_SELECT_TIMEOUT: u32 = 0x02002207;
+const ERRNO_SELECT_OBJECT_DELETED: u32 = 0x02002208;
+
+impl core::fmt::Display for SelectError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let desc = match self {
+            Self::SetInvalid => "Select set is empty or too large",
+            Self::PtrNull => "Select pointer is null",
+            Self::ObjectInvalid => "Select set contains an invalid object",
+            Self::Unavailable => "No object in the select set is ready",
+            Self::PendInterrupt => "Cannot wait for objects in interrupt context",
+            Self::PendInLock => "Cannot wait for objects in scheduler locked state",
+            Self::Timeout => "Select wait timeout",
+            Self::ObjectDeleted => "An object in the select set was deleted while waiting",
+        };
+        write!(f, "{}", desc)
+    }
+}
@@ -0,0 +1,62 @@
+//! 多对象等待相关类型定义
+
+use crate::{
+    container_of, event::types::EventGroupCB, queue::types::QueueId, semaphore::types::SemaphoreId,
+    task::types::TaskCB, timer::TimerId, utils::list::LinkedList,
+};
+
+/// 可同时等待的内核对象
+#[repr(C, u32)]
+#[derive(Debug, Clone, Copy)]
+pub enum SelectObject {
+    /// 队列中有消息可读
+    Queue(QueueId),
+    /// 信号量计数大于0
+    Semaphore(SemaphoreId),
+    /// 事件组中掩码内的任意位已置位
+    EventGroup(*mut EventGroupCB, u64),
+    /// 定时器超时过，每次报告后清零
+    Timer(TimerId),
+}
+
+/// 多对象等待节点，等待期间挂在所等待对象的多对象等待链表上，每个对象一个
+#[repr(C)]
+#[derive(Debug)]
+pub struct SelectNode {
+    /// 对象的多对象等待链表节点
+    pub list: LinkedList,
+    /// 等待的任务
+    pub task: *mut TaskCB,
+    /// 等待的对象
+    pub object: SelectObject,
+    /// 等待期间对象是否就绪过
+    pub fired: bool,
+    /// 等待期间对象是否被删除
+    pub deleted: bool,
+}
+
+impl SelectNode {
+    /// 未使用的等待节点
+    pub const UNINIT: Self = Self {
+        list: LinkedList::UNINIT,
+        task: core::ptr::null_mut(),
+        object: SelectObject::EventGroup(core::ptr::null_mut(), 0),
+        fired: false,
+        deleted: false,
+    };
+
+    pub fn new(task: *mut TaskCB, object: SelectObject) -> Self {
+        Self {
+            list: LinkedList::new(),
+            task,
+            object,
+            fired: false,
+            deleted: false,
+        }
+    }
+
+    pub fn from_list(ptr: *mut LinkedList) -> &'static mut SelectNode {
+        let node_ptr = container_of!(ptr, SelectNode, list);
+        unsafe { &mut *node_ptr }
+    }
+}
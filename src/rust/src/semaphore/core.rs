@@ -3,16 +3,22 @@ use crate::{
     interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
     percpu::can_preempt,
     println_debug,
-    result::SystemResult,
+    result::{SystemError, SystemResult},
+    select::core::select_notify,
     semaphore::{
         error::SemaphoreError,
-        global::SemaphoreManager,
+        global::{SEMAPHORE_POOL, SemaphoreManager},
         types::{SemaphoreId, SemaphoreType},
     },
     task::{
+        error::TaskError,
+        resource::{OWNER_NONE, OwnerDeadPolicy},
         sched::{schedule, schedule_reschedule},
-        sync::wait::{task_wait, task_wake},
-        types::{TaskCB, TaskStatus},
+        sync::wait::{
+            task_wait, task_wait_interrupted, task_wait_owner_dead, task_wake,
+            task_wake_all_owner_dead,
+        },
+        types::{TaskCB, TaskPendObject, TaskStatus},
     },
     utils::list::LinkedList,
 };
@@ -58,12 +64,18 @@ pub fn create_binary_semaphore(count: u16) -> SystemResult<SemaphoreId> {
     }
 }
 
-/// 删除信号量
+/// 删除信号量，多对象等待该信号量的任务返回[`SelectError::ObjectDeleted`]
+///
+/// [`SelectError::ObjectDeleted`]: crate::select::error::SelectError::ObjectDeleted
 pub fn delete_semaphore(id: SemaphoreId) -> SystemResult<()> {
     let int_save = disable_interrupts();
     match SemaphoreManager::deallocate(id) {
-        Ok(_) => {
+        Ok(woken) => {
             restore_interrupt_state(int_save);
+            // 唤醒多对象等待该信号量的任务
+            if woken {
+                schedule();
+            }
             Ok(())
         }
         Err(e) => {
@@ -73,6 +85,50 @@ pub fn delete_semaphore(id: SemaphoreId) -> SystemResult<()> {
     }
 }
 
+/// 设置创建者被删除时的处理策略，默认为[`OwnerDeadPolicy::Orphan`]
+///
+/// `Release`唤醒所有等待者并保留信号量，`Destroy`唤醒所有等待者并删除信号量，
+/// 被唤醒的等待者返回[`TaskError::OwnerDead`]。
+pub fn semaphore_owner_policy_set(
+    handle: SemaphoreId,
+    policy: OwnerDeadPolicy,
+) -> SystemResult<()> {
+    let semaphore = SemaphoreManager::get_semaphore(handle)?;
+
+    let int_save = disable_interrupts();
+
+    if semaphore.is_unused() || !semaphore.matches_id(handle) {
+        restore_interrupt_state(int_save);
+        return Err(SemaphoreError::Invalid.into());
+    }
+    semaphore.owner_policy = policy;
+
+    restore_interrupt_state(int_save);
+    Ok(())
+}
+
+/// 处理被删除任务创建的信号量，须在关中断后调用，返回是否唤醒了任务
+pub(crate) fn semaphore_owner_dead(task_id: u32, count: &mut u16) -> bool {
+    let mut woken = false;
+    let pool = &raw mut SEMAPHORE_POOL;
+    for semaphore in unsafe { (*pool).iter_mut() } {
+        if semaphore.is_unused() || semaphore.owner != task_id {
+            continue;
+        }
+
+        semaphore.owner = OWNER_NONE;
+        if semaphore.owner_policy == OwnerDeadPolicy::Orphan {
+            continue;
+        }
+        woken |= task_wake_all_owner_dead(&mut semaphore.sem_list);
+        if semaphore.owner_policy == OwnerDeadPolicy::Destroy {
+            woken |= SemaphoreManager::deallocate(semaphore.get_id()).unwrap_or(false);
+        }
+        *count += 1;
+    }
+    woken
+}
+
 /// 等待信号量
 pub fn semaphore_pend(handle: SemaphoreId, timeout: u32) -> SystemResult<()> {
     let semaphore = SemaphoreManager::get_semaphore(handle)?;
@@ -107,7 +163,11 @@ pub fn semaphore_pend(handle: SemaphoreId, timeout: u32) -> SystemResult<()> {
         restore_interrupt_state(int_save);
         return Err(SemaphoreError::Unavailable.into());
     }
-    task_wait(&mut semaphore.sem_list, timeout);
+    task_wait(
+        &mut semaphore.sem_list,
+        TaskPendObject::Semaphore(handle.into()),
+        timeout,
+    );
     schedule_reschedule();
 
     restore_interrupt_state(int_save);
@@ -118,6 +178,12 @@ pub fn semaphore_pend(handle: SemaphoreId, timeout: u32) -> SystemResult<()> {
         run_task.task_status.remove(TaskStatus::TIMEOUT);
         restore_interrupt_state(int_save);
         Err(SemaphoreError::Timeout.into())
+    } else if task_wait_interrupted(run_task) {
+        restore_interrupt_state(int_save);
+        Err(SystemError::Task(TaskError::Interrupted))
+    } else if task_wait_owner_dead(run_task) {
+        restore_interrupt_state(int_save);
+        Err(SystemError::Task(TaskError::OwnerDead))
     } else {
         restore_interrupt_state(int_save);
         Ok(())
@@ -150,6 +216,12 @@ pub fn semaphore_post(handle: SemaphoreId) -> SystemResult<()> {
         schedule();
     } else {
         semaphore.increment_count();
+        // 通知等待该信号量的多对象等待任务
+        let woken = select_notify(&raw mut semaphore.select_list, |_| true);
+        restore_interrupt_state(int_save);
+        if woken {
+            schedule();
+        }
     }
 
     Ok(())
@@ -1,6 +1,7 @@
 use crate::{
     config::SEM_LIMIT,
     result::SystemResult,
+    select::core::select_object_deleted,
     semaphore::{
         error::SemaphoreError,
         types::{SemaphoreControlBlock, SemaphoreId, SemaphoreType},
@@ -58,7 +59,8 @@ impl SemaphoreManager {
 
     /// 释放互斥锁
     #[inline]
-    pub fn deallocate(id: SemaphoreId) -> SystemResult<()> {
+    /// 回收信号量，返回是否唤醒了多对象等待的任务
+    pub fn deallocate(id: SemaphoreId) -> SystemResult<bool> {
         let semaphore = Self::get_semaphore(id)?;
         if !semaphore.matches_id(id) || semaphore.is_unused() {
             return Err(SemaphoreError::Invalid.into());
@@ -66,11 +68,12 @@ impl SemaphoreManager {
         if semaphore.has_waiting_tasks() {
             return Err(SemaphoreError::Pended.into());
         }
+        let woken = select_object_deleted(&raw mut semaphore.select_list);
         // 重置互斥锁状态
         semaphore.reset();
         LinkedList::tail_insert(&raw mut UNUSED_SEMAPHORE_LIST, &raw mut semaphore.sem_list);
         // 加回未使用列表
-        Ok(())
+        Ok(woken)
     }
 
     /// 获取互斥锁
@@ -1,6 +1,10 @@
 //! 信号量类型定义
 
-use crate::{container_of, utils::list::LinkedList};
+use crate::{
+    container_of,
+    task::resource::{OWNER_NONE, OwnerDeadPolicy, current_owner},
+    utils::list::LinkedList,
+};
 use core::sync::atomic::{AtomicU16, Ordering};
 
 /// 信号量类型
@@ -99,16 +103,35 @@ pub struct SemaphoreControlBlock {
 
     /// 等待信号量的任务列表，对应C代码中的semList
     pub sem_list: LinkedList,
+
+    /// 创建信号量的任务
+    pub owner: u32,
+
+    /// 创建者被删除时的处理策略
+    pub owner_policy: OwnerDeadPolicy,
+
+    /// 多对象等待此信号量的等待节点列表
+    pub select_list: LinkedList,
+}
+
+impl Default for SemaphoreControlBlock {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SemaphoreControlBlock {
     /// 创建一个新的信号量控制块
+    #[allow(clippy::declare_interior_mutable_const)]
     pub const UNINT: Self = Self {
         sem_stat: SemaphoreState::Unused,
         sem_type: SemaphoreType::Counting,
         sem_count: AtomicU16::new(0),
         sem_id: SemaphoreId(0),
         sem_list: LinkedList::new(),
+        owner: OWNER_NONE,
+        owner_policy: OwnerDeadPolicy::Orphan,
+        select_list: LinkedList::new(),
     };
 
     /// 创建一个新的信号量控制块
@@ -120,6 +143,9 @@ impl SemaphoreControlBlock {
             sem_count: AtomicU16::new(0),
             sem_id: SemaphoreId(0),
             sem_list: LinkedList::new(),
+            owner: OWNER_NONE,
+            owner_policy: OwnerDeadPolicy::Orphan,
+            select_list: LinkedList::new(),
         }
     }
 
@@ -223,6 +249,9 @@ impl SemaphoreControlBlock {
         self.set_type(sem_type);
         self.set_count(count);
         LinkedList::init(&raw mut self.sem_list);
+        self.owner = current_owner();
+        self.owner_policy = OwnerDeadPolicy::Orphan;
+        LinkedList::init(&raw mut self.select_list);
     }
 
     /// 重置信号量
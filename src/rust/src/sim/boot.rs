@@ -0,0 +1,74 @@
+//! 仿真启动流程
+
+use super::cpu::{set_cpuid, start_first_task};
+use crate::{
+    barrier::core::barrier_init,
+    condvar::core::condvar_init,
+    config::KERNEL_CORE_NUM,
+    interrupt::{disable_interrupts, initialize_interrupt},
+    memory::init_allocator,
+    mutex::core::mutex_init,
+    queue::management::init_queue_system,
+    rwlock::core::rwlock_init,
+    semaphore::core::init_semaphore_system,
+    task::{idle::idle_task_create, manager::init::init_task_system, sched::schedule_start},
+    tick::{initialize_tick, start_tick},
+    timer::timer_init,
+};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Once;
+
+/// 启动的仿真CPU数量
+static CPU_COUNT: AtomicUsize = AtomicUsize::new(1);
+static BOOTED: AtomicBool = AtomicBool::new(false);
+
+/// 设置启动的仿真CPU数量，须在内核启动前调用
+pub fn set_cpu_count(count: usize) {
+    assert!(
+        (1..=KERNEL_CORE_NUM).contains(&count),
+        "cpu count must be in 1..={KERNEL_CORE_NUM}"
+    );
+    if BOOTED.load(Ordering::SeqCst) {
+        assert_eq!(
+            CPU_COUNT.load(Ordering::SeqCst),
+            count,
+            "kernel already booted with a different cpu count"
+        );
+    }
+    CPU_COUNT.store(count, Ordering::SeqCst);
+}
+
+/// 初始化内核并启动调度，整个进程只执行一次
+pub fn boot() {
+    static BOOT: Once = Once::new();
+    BOOT.call_once(|| {
+        BOOTED.store(true, Ordering::SeqCst);
+        init_allocator();
+        initialize_interrupt();
+        init_task_system();
+        init_semaphore_system();
+        mutex_init();
+        rwlock_init();
+        condvar_init();
+        barrier_init();
+        init_queue_system();
+        timer_init().expect("failed to initialize software timers");
+        idle_task_create().expect("failed to create idle task");
+        initialize_tick();
+        start_kernel();
+    });
+}
+
+fn start_kernel() {
+    for cpuid in 0..CPU_COUNT.load(Ordering::SeqCst) {
+        set_cpuid(cpuid);
+        // 与真实移植一致，各核在关中断的状态下启动调度，由第一个任务打开中断
+        disable_interrupts();
+        let new_task = schedule_start();
+        if cpuid == 0 {
+            start_tick();
+        }
+        start_first_task(cpuid, new_task.task_id);
+    }
+    set_cpuid(0);
+}
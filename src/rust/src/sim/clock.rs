@@ -0,0 +1,103 @@
+//! 仿真时钟，由宿主定时线程按TICK_PER_SECOND产生Tick中断
+//!
+//! 每个核的下一次Tick时刻单独记录，开启`tickless`特性后可以由内核重新设置。
+
+use super::cpu::{IRQ_TICK, irq_dispatch, raise_irq};
+use crate::{
+    config::{KERNEL_CORE_NUM, SYS_CLOCK, TICK_PER_SECOND},
+    percpu::cpu_online_mask,
+};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    sync::{Condvar, Mutex, Once, OnceLock, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+const NS_PER_SECOND: u64 = 1_000_000_000;
+
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+/// 各核下一次Tick中断的时刻
+static DEADLINES: Mutex<[Option<Instant>; KERNEL_CORE_NUM]> = Mutex::new([None; KERNEL_CORE_NUM]);
+/// Tick定时器被重新设置
+static RELOAD: Condvar = Condvar::new();
+
+/// 各核已产生的Tick中断数
+static TICK_IRQ_COUNT: [AtomicU64; KERNEL_CORE_NUM] =
+    [const { AtomicU64::new(0) }; KERNEL_CORE_NUM];
+
+fn elapsed() -> Duration {
+    EPOCH.get_or_init(Instant::now).elapsed()
+}
+
+/// 指定核已产生的Tick中断数
+pub fn tick_interrupt_count(cpuid: usize) -> u64 {
+    TICK_IRQ_COUNT[cpuid].load(Ordering::SeqCst)
+}
+
+fn tick_thread() {
+    let period = Duration::from_nanos(NS_PER_SECOND / TICK_PER_SECOND as u64);
+    let mut deadlines = DEADLINES.lock().unwrap_or_else(PoisonError::into_inner);
+    loop {
+        // 每个在线核都有自己的Tick中断
+        let now = Instant::now();
+        let online = cpu_online_mask();
+        let mut next = now + period;
+        for cpuid in (0..KERNEL_CORE_NUM).filter(|cpuid| online & (1 << cpuid) != 0) {
+            let deadline = deadlines[cpuid].get_or_insert(now + period);
+            if *deadline <= now {
+                *deadline += period;
+                TICK_IRQ_COUNT[cpuid].fetch_add(1, Ordering::SeqCst);
+                raise_irq(cpuid, IRQ_TICK);
+            }
+            next = next.min(*deadline);
+        }
+        deadlines = RELOAD
+            .wait_timeout(deadlines, next.saturating_duration_since(now))
+            .unwrap_or_else(PoisonError::into_inner)
+            .0;
+    }
+}
+
+#[unsafe(export_name = "HalClockInit")]
+extern "C" fn hal_clock_init() {
+    EPOCH.get_or_init(Instant::now);
+}
+
+#[unsafe(export_name = "HalClockStart")]
+extern "C" fn hal_clock_start() {
+    static START: Once = Once::new();
+    START.call_once(|| {
+        thread::Builder::new()
+            .name("sim-tick".into())
+            .spawn(tick_thread)
+            .expect("failed to spawn tick thread");
+    });
+}
+
+#[unsafe(export_name = "HalClockGetCycles")]
+extern "C" fn hal_clock_get_cycles() -> u64 {
+    (elapsed().as_nanos() * SYS_CLOCK as u128 / NS_PER_SECOND as u128) as u64
+}
+
+/// 下一次Tick中断在指定周期数后产生，之后恢复原有的周期
+#[cfg(feature = "tickless")]
+#[unsafe(export_name = "HalClockTickTimerReload")]
+extern "C" fn hal_clock_tick_timer_reload(cycles: u32) {
+    let cpuid = crate::ffi::bindings::arch_curr_cpuid() as usize;
+    let mut deadlines = DEADLINES.lock().unwrap_or_else(PoisonError::into_inner);
+    let delay = Duration::from_nanos(cycles as u64 * NS_PER_SECOND / SYS_CLOCK as u64);
+    deadlines[cpuid] = Some(Instant::now() + delay);
+    RELOAD.notify_all();
+}
+
+#[unsafe(export_name = "HalDelayUs")]
+extern "C" fn hal_delay_us(usecs: u32) {
+    // 忙等期间照常响应中断
+    let deadline = elapsed() + Duration::from_micros(usecs as u64);
+    while elapsed() < deadline {
+        irq_dispatch();
+        core::hint::spin_loop();
+    }
+}
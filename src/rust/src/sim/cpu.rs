@@ -0,0 +1,329 @@
+//! 仿真CPU
+//!
+//! 每个任务由一个宿主线程承载，线程在任务第一次被调度时创建。
+//! 每个仿真CPU同一时刻只有其持有者线程在运行，上下文切换即移交CPU所有权，
+//! 被换出的任务可以在任意一个CPU上被再次换入。
+//! 中断屏蔽由屏蔽位模拟，挂起的中断在开中断或WFI时投递，相当于硬件在指令边界响应IRQ。
+
+use crate::{
+    config::{KERNEL_CORE_NUM, STACK_INIT_PATTERN, STACK_MAGIC_WORD, TASK_LIMIT},
+    interrupt::global::{irq_nesting_count_dec, irq_nesting_count_inc},
+    task::{
+        entry::task_entry, sched::schedule_preempt, signal::process_task_signals, types::TaskCB,
+    },
+    tick::handle_tick,
+};
+use alloc::{boxed::Box, collections::VecDeque};
+use core::{
+    cell::Cell,
+    ffi::c_void,
+    ptr::null_mut,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, Ordering},
+};
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    thread,
+};
+
+/// 中断源：系统Tick
+pub const IRQ_TICK: u32 = 1 << 0;
+/// 中断源：宿主请求
+pub const IRQ_HOST_REQUEST: u32 = 1 << 1;
+/// 中断源：核间中断
+#[cfg(feature = "smp")]
+pub const IRQ_IPI: u32 = 1 << 2;
+
+/// 承载任务的宿主线程栈大小
+const TASK_THREAD_STACK_SIZE: usize = 1 << 20;
+/// 伪栈指针相对栈底的偏移，对应真实移植中保存的上下文大小
+const TASK_CONTEXT_SIZE: usize = 0x40;
+
+/// 宿主请求，在中断上下文中执行
+pub type HostRequest = Box<dyn FnOnce() + Send>;
+
+/// 线程身份：(任务ID, 代数)
+type ThreadId = (u32, u64);
+
+struct CpuState {
+    /// 每个仿真CPU当前的持有者
+    owner: [Option<ThreadId>; KERNEL_CORE_NUM],
+    /// 每个任务控制块当前的代数，栈初始化时递增
+    generations: [u64; TASK_LIMIT as usize],
+    /// 已创建宿主线程的代数
+    spawned: [u64; TASK_LIMIT as usize],
+}
+
+static CPU: Mutex<CpuState> = Mutex::new(CpuState {
+    owner: [None; KERNEL_CORE_NUM],
+    generations: [0; TASK_LIMIT as usize],
+    spawned: [0; TASK_LIMIT as usize],
+});
+/// CPU所有权变化
+static SWITCH: Condvar = Condvar::new();
+/// 有中断挂起
+static WAKEUP: Condvar = Condvar::new();
+
+static INT_LOCKED: [AtomicBool; KERNEL_CORE_NUM] =
+    [const { AtomicBool::new(true) }; KERNEL_CORE_NUM];
+static IRQ_PENDING: [AtomicU32; KERNEL_CORE_NUM] = [const { AtomicU32::new(0) }; KERNEL_CORE_NUM];
+static CURRENT_TASK: [AtomicPtr<TaskCB>; KERNEL_CORE_NUM] =
+    [const { AtomicPtr::new(null_mut()) }; KERNEL_CORE_NUM];
+static HOST_REQUESTS: Mutex<VecDeque<HostRequest>> = Mutex::new(VecDeque::new());
+
+std::thread_local! {
+    static SELF: Cell<Option<ThreadId>> = const { Cell::new(None) };
+    /// 线程当前所在的仿真CPU
+    static CPUID: Cell<usize> = const { Cell::new(0) };
+}
+
+fn cpu() -> MutexGuard<'static, CpuState> {
+    CPU.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 当前线程所在的仿真CPU
+#[inline]
+fn cpuid() -> usize {
+    CPUID.get()
+}
+
+/// 设置当前线程所在的仿真CPU，仅用于启动阶段
+pub fn set_cpuid(cpuid: usize) {
+    CPUID.set(cpuid);
+}
+
+impl CpuState {
+    /// 将CPU交给指定任务，必要时为其创建宿主线程
+    fn switch_to(&mut self, cpuid: usize, task_id: u32) {
+        let index = task_id as usize;
+        let generation = self.generations[index];
+        if self.spawned[index] != generation {
+            self.spawned[index] = generation;
+            spawn_task_thread(task_id, generation);
+        }
+        self.owner[cpuid] = Some((task_id, generation));
+        SWITCH.notify_all();
+    }
+}
+
+/// 阻塞直到再次持有某个CPU；已删除任务的线程将永远停在这里
+fn wait_for_cpu(
+    guard: MutexGuard<'static, CpuState>,
+    me: ThreadId,
+) -> MutexGuard<'static, CpuState> {
+    let guard = SWITCH
+        .wait_while(guard, |cpu| !cpu.owner.contains(&Some(me)))
+        .unwrap_or_else(PoisonError::into_inner);
+    // 任务可能被其他核换入
+    let cpuid = guard.owner.iter().position(|owner| *owner == Some(me));
+    CPUID.set(cpuid.expect("thread does not own a cpu"));
+    guard
+}
+
+fn spawn_task_thread(task_id: u32, generation: u64) {
+    thread::Builder::new()
+        .name(std::format!("task-{task_id}"))
+        .stack_size(TASK_THREAD_STACK_SIZE)
+        .spawn(move || {
+            let me = (task_id, generation);
+            SELF.set(Some(me));
+            drop(wait_for_cpu(cpu(), me));
+            task_entry(task_id);
+        })
+        .expect("failed to spawn task thread");
+}
+
+/// 启动阶段由宿主线程把CPU交给第一个任务，之后该线程不再参与调度
+pub fn start_first_task(cpuid: usize, task_id: u32) {
+    cpu().switch_to(cpuid, task_id);
+}
+
+/// 在指定CPU上挂起中断并唤醒处于WFI的CPU
+pub fn raise_irq(cpuid: usize, irq: u32) {
+    IRQ_PENDING[cpuid].fetch_or(irq, Ordering::SeqCst);
+    let _cpu = cpu();
+    WAKEUP.notify_all();
+}
+
+/// 提交一个宿主请求，下一次0号CPU响应中断时在中断上下文中执行
+pub fn post_host_request(request: HostRequest) {
+    HOST_REQUESTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push_back(request);
+    raise_irq(0, IRQ_HOST_REQUEST);
+}
+
+fn run_host_requests() {
+    loop {
+        let request = HOST_REQUESTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front();
+        match request {
+            Some(request) => request(),
+            None => break,
+        }
+    }
+}
+
+/// 投递当前CPU挂起的中断，仅在持有CPU且中断打开时生效
+pub fn irq_dispatch() {
+    if SELF.get().is_none() {
+        return;
+    }
+    // 中断退出时的调度可能使任务迁移到其他核，每轮重新读取所在的CPU
+    while !INT_LOCKED[cpuid()].load(Ordering::SeqCst) {
+        let pending = IRQ_PENDING[cpuid()].swap(0, Ordering::SeqCst);
+        if pending == 0 {
+            break;
+        }
+
+        INT_LOCKED[cpuid()].store(true, Ordering::SeqCst);
+        irq_nesting_count_inc();
+
+        #[cfg(feature = "tickless")]
+        crate::tick::tickless::tickless_update();
+
+        if pending & IRQ_TICK != 0 {
+            handle_tick();
+        }
+        if pending & IRQ_HOST_REQUEST != 0 {
+            run_host_requests();
+        }
+        #[cfg(feature = "smp")]
+        if pending & IRQ_IPI != 0 {
+            crate::mp::mp_schedule_handler();
+        }
+
+        irq_nesting_count_dec();
+
+        // 中断退出：处理任务信号，需要时进行抢占调度
+        if process_task_signals() != 0 {
+            schedule_preempt();
+        }
+        INT_LOCKED[cpuid()].store(false, Ordering::SeqCst);
+    }
+
+    // 回到开中断的任务上下文，相当于体系结构的信号返回入口
+    #[cfg(feature = "task_signal")]
+    crate::task::user_signal::signal_return();
+}
+
+#[unsafe(export_name = "ArchCurrTaskGetWrapper")]
+extern "C" fn arch_curr_task_get() -> *mut TaskCB {
+    CURRENT_TASK[cpuid()].load(Ordering::SeqCst)
+}
+
+#[unsafe(export_name = "ArchCurrTaskSetWrapper")]
+extern "C" fn arch_curr_task_set(task: *const c_void) {
+    CURRENT_TASK[cpuid()].store(task as *mut TaskCB, Ordering::SeqCst);
+}
+
+#[unsafe(export_name = "ArchIntLockedWrapper")]
+extern "C" fn arch_int_locked() -> u32 {
+    INT_LOCKED[cpuid()].load(Ordering::SeqCst) as u32
+}
+
+#[unsafe(export_name = "ArchIntLockWrapper")]
+extern "C" fn arch_int_lock() -> u32 {
+    INT_LOCKED[cpuid()].swap(true, Ordering::SeqCst) as u32
+}
+
+#[unsafe(export_name = "ArchIntUnlockWrapper")]
+extern "C" fn arch_int_unlock() -> u32 {
+    let int_save = INT_LOCKED[cpuid()].swap(false, Ordering::SeqCst) as u32;
+    irq_dispatch();
+    int_save
+}
+
+#[unsafe(export_name = "ArchIntRestoreWrapper")]
+extern "C" fn arch_int_restore(int_save: u32) {
+    INT_LOCKED[cpuid()].store(int_save != 0, Ordering::SeqCst);
+    if int_save == 0 {
+        irq_dispatch();
+    }
+}
+
+#[unsafe(export_name = "ArchIrqInit")]
+extern "C" fn arch_irq_init() {}
+
+#[cfg(feature = "smp")]
+#[unsafe(export_name = "ArchCurrCpuid")]
+extern "C" fn arch_curr_cpuid() -> u32 {
+    cpuid() as u32
+}
+
+#[cfg(feature = "smp")]
+#[unsafe(export_name = "HalIrqSendIpi")]
+extern "C" fn hal_irq_send_ipi(target: u32, _ipi: u32) {
+    (0..KERNEL_CORE_NUM)
+        .filter(|cpuid| target & (1 << cpuid) != 0)
+        .for_each(|cpuid| raise_irq(cpuid, IRQ_IPI));
+}
+
+// 宿主机可能只有一个处理器，自旋等待时让出宿主线程，避免持锁的线程得不到运行
+#[cfg(feature = "smp")]
+#[unsafe(export_name = "ArchSpinLock")]
+extern "C" fn arch_spin_lock(lock: *mut u32) {
+    while arch_spin_trylock(lock) != 0 {
+        thread::yield_now();
+    }
+}
+
+#[cfg(feature = "smp")]
+#[unsafe(export_name = "ArchSpinUnlock")]
+extern "C" fn arch_spin_unlock(lock: *mut u32) {
+    unsafe { AtomicU32::from_ptr(lock) }.store(0, Ordering::Release);
+}
+
+#[cfg(feature = "smp")]
+#[unsafe(export_name = "ArchSpinTrylock")]
+extern "C" fn arch_spin_trylock(lock: *mut u32) -> i32 {
+    let lock = unsafe { AtomicU32::from_ptr(lock) };
+    match lock.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed) {
+        Ok(_) => 0,
+        Err(_) => -1,
+    }
+}
+
+#[unsafe(export_name = "WfiWrapper")]
+extern "C" fn wfi() {
+    let guard = cpu();
+    let cpuid = cpuid();
+    drop(
+        WAKEUP
+            .wait_while(guard, |_| IRQ_PENDING[cpuid].load(Ordering::SeqCst) == 0)
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+    irq_dispatch();
+}
+
+#[unsafe(export_name = "OsTaskScheduleWrapper")]
+extern "C" fn task_schedule(new_task: *mut TaskCB, _run_task: *mut TaskCB) {
+    let me = SELF.get().expect("task switch outside of a task thread");
+    let new_task_id = unsafe { (*new_task).task_id };
+    let mut guard = cpu();
+    guard.switch_to(cpuid(), new_task_id);
+    drop(wait_for_cpu(guard, me));
+}
+
+#[unsafe(export_name = "OsTaskStackInit")]
+extern "C" fn task_stack_init(
+    task_id: u32,
+    stack_size: u32,
+    top_stack: *mut c_void,
+) -> *mut c_void {
+    // 按真实移植的约定填充栈，供栈检查使用；上下文本身由宿主线程保存
+    let stack = top_stack as *mut usize;
+    let words = stack_size as usize / size_of::<usize>();
+    unsafe {
+        for index in 0..words {
+            stack.add(index).write(STACK_INIT_PATTERN);
+        }
+        stack.write(STACK_MAGIC_WORD);
+    }
+
+    cpu().generations[task_id as usize] += 1;
+
+    unsafe { (top_stack as *mut u8).add(stack_size as usize - TASK_CONTEXT_SIZE) as *mut c_void }
+}
@@ -0,0 +1,116 @@
+//! Linux主机仿真移植
+//!
+//! 以纯Rust实现`ffi::bindings`中的体系结构接口，使内核可以直接在Linux上运行：
+//! 任务由宿主线程承载，中断屏蔽由仿真CPU的屏蔽位模拟，系统Tick由宿主定时线程产生。
+//! 任务、IPC与定时器的集成测试通过以下命令运行：
+//!
+//! ```text
+//! cargo sim-test
+//! ```
+//!
+//! 开启`smp`特性后可以通过[`set_cpu_count`]启动多个仿真CPU，每个CPU同样由持有它的宿主线程运行：
+//!
+//! ```text
+//! cargo sim-test --features smp
+//! ```
+
+mod boot;
+mod clock;
+mod cpu;
+
+pub use boot::set_cpu_count;
+pub use clock::tick_interrupt_count;
+pub use cpu::{HostRequest, post_host_request};
+
+#[cfg(feature = "edf")]
+use crate::task::edf::TaskEdfParam;
+use crate::{
+    result::SystemResult,
+    task::builder::{Task, TaskBuilder},
+};
+use alloc::boxed::Box;
+use core::ffi::CStr;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{Mutex, PoisonError, mpsc},
+};
+
+/// 仿真环境的名义堆大小
+pub const SIM_HEAP_SIZE: usize = 64 * 1024 * 1024;
+
+/// 测试根任务的默认优先级
+pub const SIM_TEST_TASK_PRIORITY: u16 = 10;
+
+fn builder(name: &'static CStr, priority: u16) -> TaskBuilder<'static> {
+    Task::builder()
+        .name(name.to_str().expect("task name is not UTF-8"))
+        .priority(priority)
+}
+
+/// 以闭包为入口创建任务
+pub fn spawn<F>(name: &'static CStr, priority: u16, f: F) -> SystemResult<u32>
+where
+    F: FnOnce() + Send + 'static,
+{
+    builder(name, priority).spawn(f).map(|task| task.id())
+}
+
+/// 以闭包为入口创建可被等待的任务
+pub fn spawn_joinable<F>(name: &'static CStr, priority: u16, f: F) -> SystemResult<u32>
+where
+    F: FnOnce() + Send + 'static,
+{
+    builder(name, priority)
+        .joinable()
+        .spawn(f)
+        .map(|task| task.id())
+}
+
+/// 以闭包为入口创建EDF任务，`priority`为预算耗尽后使用的普通优先级
+#[cfg(feature = "edf")]
+pub fn spawn_edf<F>(
+    name: &'static CStr,
+    priority: u16,
+    edf: TaskEdfParam,
+    f: F,
+) -> SystemResult<u32>
+where
+    F: FnOnce() + Send + 'static,
+{
+    builder(name, priority)
+        .edf(edf)
+        .spawn(f)
+        .map(|task| task.id())
+}
+
+/// 在内核任务中运行测试闭包，闭包中的panic会传回调用线程
+pub fn run<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    run_with_priority(SIM_TEST_TASK_PRIORITY, f);
+}
+
+/// 以指定优先级在内核任务中运行测试闭包
+pub fn run_with_priority<F>(priority: u16, f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    // 所有测试共享同一个内核，逐个运行
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+    let _guard = TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    boot::boot();
+
+    let (sender, receiver) = mpsc::channel();
+    post_host_request(Box::new(move || {
+        let body = move || {
+            let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(f)));
+        };
+        spawn(c"SimTest", priority, body).expect("failed to create test task");
+    }));
+
+    if let Err(payload) = receiver.recv().expect("test task exited without reporting") {
+        panic::resume_unwind(payload);
+    }
+}
@@ -0,0 +1,97 @@
+//! 自旋锁
+//!
+//! 仅在多核下使用，单核下关中断即可保护内核数据。自旋锁由体系结构实现原子加解锁，
+//! `disable_interrupts`还会持有内核锁，内核锁按核记录嵌套深度，
+//! 只在最外层加解锁，并在任务切换时由换入的任务继续持有。
+
+use crate::{
+    ffi::bindings::{arch_spin_lock, arch_spin_unlock},
+    percpu::os_percpu_get,
+};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// 自旋锁
+#[repr(C)]
+#[derive(Debug)]
+pub struct Spinlock {
+    raw_lock: AtomicU32,
+}
+
+impl Default for Spinlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinlock {
+    pub const fn new() -> Self {
+        Self {
+            raw_lock: AtomicU32::new(0),
+        }
+    }
+
+    /// 加锁，锁被占用时自旋等待
+    #[inline]
+    pub fn lock(&self) {
+        arch_spin_lock(self.raw_lock.as_ptr());
+    }
+
+    /// 解锁
+    #[inline]
+    pub fn unlock(&self) {
+        arch_spin_unlock(self.raw_lock.as_ptr());
+    }
+
+    /// 锁是否被持有
+    #[inline]
+    pub fn is_held(&self) -> bool {
+        self.raw_lock.load(Ordering::Relaxed) != 0
+    }
+}
+
+/// 内核锁
+static KERNEL_SPIN: Spinlock = Spinlock::new();
+
+/// 获取内核锁，须在关中断后调用
+#[inline]
+pub fn kernel_lock() {
+    let percpu = os_percpu_get();
+    if percpu.kernel_lock_depth == 0 {
+        KERNEL_SPIN.lock();
+    }
+    percpu.kernel_lock_depth += 1;
+}
+
+/// 释放一层内核锁
+#[inline]
+pub fn kernel_unlock() {
+    let percpu = os_percpu_get();
+    debug_assert!(percpu.kernel_lock_depth > 0, "内核锁未持有");
+    percpu.kernel_lock_depth -= 1;
+    if percpu.kernel_lock_depth == 0 {
+        KERNEL_SPIN.unlock();
+    }
+}
+
+/// 完全释放当前核持有的内核锁
+#[inline]
+pub fn kernel_unlock_all() {
+    let percpu = os_percpu_get();
+    if percpu.kernel_lock_depth != 0 {
+        percpu.kernel_lock_depth = 0;
+        KERNEL_SPIN.unlock();
+    }
+}
+
+/// 获取当前核的内核锁嵌套深度
+#[inline]
+pub fn kernel_lock_depth() -> u32 {
+    os_percpu_get().kernel_lock_depth
+}
+
+/// 设置当前核的内核锁嵌套深度，任务切换回来后恢复该任务的嵌套层数
+#[inline]
+pub fn kernel_lock_depth_set(depth: u32) {
+    debug_assert!(KERNEL_SPIN.is_held());
+    os_percpu_get().kernel_lock_depth = depth;
+}
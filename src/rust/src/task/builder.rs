@@ -0,0 +1,188 @@
+//! 以闭包为入口创建任务的Rust接口
+//!
+//! ```ignore
+//! let task = Task::builder()
+//!     .name("net")
+//!     .priority(5)
+//!     .stack(8192)
+//!     .spawn(move || { /* ... */ })?;
+//! ```
+
+#[cfg(feature = "edf")]
+use crate::task::edf::TaskEdfParam;
+use crate::{
+    config::TASK_NAME_LEN,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        manager::{
+            create::task_create,
+            delete::task_delete,
+            exit::{task_detach, task_join},
+            priority::{get_task_priority, set_task_priority},
+            suspend::{task_resume, task_suspend},
+        },
+        types::{TASK_ATTR_JOINABLE, TaskInitParam},
+    },
+};
+use alloc::boxed::Box;
+use core::ffi::{c_char, c_void};
+
+/// 未指定时使用的任务优先级
+pub const TASK_BUILDER_DEFAULT_PRIORITY: u16 = 10;
+
+type TaskClosure = Box<dyn FnOnce() + Send>;
+
+extern "C" fn closure_entry(args: *mut c_void) {
+    let closure = unsafe { Box::from_raw(args as *mut TaskClosure) };
+    closure();
+}
+
+/// 任务构建器
+///
+/// 名称在创建时复制到任务控制块中，超过`TASK_NAME_LEN - 1`字节的部分按字符边界截断。
+#[derive(Debug)]
+pub struct TaskBuilder<'a> {
+    name: &'a str,
+    init_param: TaskInitParam,
+}
+
+impl<'a> TaskBuilder<'a> {
+    /// 任务名称，不能为空
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// 任务优先级
+    pub fn priority(mut self, priority: u16) -> Self {
+        self.init_param.priority = priority;
+        self
+    }
+
+    /// 任务栈大小，0表示使用默认大小
+    pub fn stack(mut self, stack_size: u32) -> Self {
+        self.init_param.stack_size = stack_size;
+        self
+    }
+
+    /// 任务退出后保留控制块，直到被[`Task::join`]或[`Task::detach`]回收
+    pub fn joinable(mut self) -> Self {
+        self.init_param.resved |= TASK_ATTR_JOINABLE;
+        self
+    }
+
+    /// 时间片长度，0表示同优先级内先进先出
+    #[cfg(feature = "time_slice")]
+    pub fn time_slice(mut self, ticks: u16) -> Self {
+        self.init_param.time_slice = ticks;
+        self
+    }
+
+    /// EDF调度参数，预算耗尽后以[`priority`](Self::priority)运行
+    #[cfg(feature = "edf")]
+    pub fn edf(mut self, edf: TaskEdfParam) -> Self {
+        self.init_param.edf = edf;
+        self
+    }
+
+    /// 创建并启动以闭包为入口的任务
+    ///
+    /// 闭包在任务入口中被取出运行；任务在运行闭包前被删除时闭包不会被释放。
+    pub fn spawn<F>(self, f: F) -> SystemResult<Task>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.name.is_empty() {
+            return Err(SystemError::Task(TaskError::NameEmpty));
+        }
+
+        // 在栈上构造以0结尾的名称，创建时由内核复制
+        let mut len = self.name.len().min(TASK_NAME_LEN - 1);
+        while !self.name.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut name = [0u8; TASK_NAME_LEN];
+        name[..len].copy_from_slice(&self.name.as_bytes()[..len]);
+
+        let closure: Box<TaskClosure> = Box::new(Box::new(f));
+        let args = Box::into_raw(closure) as *mut c_void;
+        let mut init_param = self.init_param;
+        init_param.name = name.as_ptr() as *const c_char;
+        init_param.task_entry = Some(closure_entry);
+        init_param.args = args;
+
+        let mut task_id = 0;
+        match task_create(&mut task_id, &mut init_param) {
+            Ok(()) => Ok(Task { id: task_id }),
+            Err(err) => {
+                drop(unsafe { Box::from_raw(args as *mut TaskClosure) });
+                Err(err)
+            }
+        }
+    }
+}
+
+/// 任务句柄
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Task {
+    id: u32,
+}
+
+impl Task {
+    /// 创建任务构建器
+    pub fn builder<'a>() -> TaskBuilder<'a> {
+        TaskBuilder {
+            name: "",
+            init_param: TaskInitParam {
+                priority: TASK_BUILDER_DEFAULT_PRIORITY,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 由任务ID构造句柄
+    pub fn from_id(id: u32) -> Self {
+        Self { id }
+    }
+
+    /// 任务ID
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// 等待可被等待的任务退出，返回其退出码
+    pub fn join(self, timeout: u32) -> SystemResult<u32> {
+        task_join(self.id, timeout)
+    }
+
+    /// 将任务设为分离状态
+    pub fn detach(self) -> SystemResult<()> {
+        task_detach(self.id)
+    }
+
+    /// 删除任务
+    pub fn delete(self) -> SystemResult<()> {
+        task_delete(self.id)
+    }
+
+    /// 挂起任务
+    pub fn suspend(&self) -> SystemResult<()> {
+        task_suspend(self.id)
+    }
+
+    /// 恢复被挂起的任务
+    pub fn resume(&self) -> SystemResult<()> {
+        task_resume(self.id)
+    }
+
+    /// 获取任务优先级
+    pub fn priority(&self) -> SystemResult<u16> {
+        get_task_priority(self.id)
+    }
+
+    /// 设置任务优先级
+    pub fn set_priority(&self, priority: u16) -> SystemResult<()> {
+        set_task_priority(self.id, priority)
+    }
+}
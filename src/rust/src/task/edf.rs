@@ -0,0 +1,332 @@
+//! 最早截止时间优先（EDF）调度类
+//!
+//! EDF任务在创建时声明周期、每周期的执行预算和相对截止时间，就绪时进入按绝对截止时间排序的EDF队列，
+//! 总是先于普通优先级的任务运行。作业用完预算后任务退回到自身的普通优先级，直到下一个作业释放。
+//! 作业在截止时间到达时仍未完成即记为一次截止时间错失。
+//!
+//! 所有EDF任务链接在EDF任务列表中，Tick处理只检查该列表中的任务。
+
+#[cfg(feature = "smp")]
+use crate::mp::mp_schedule;
+use crate::{
+    config::TASK_LIMIT,
+    container_of,
+    ffi::bindings::{arch_curr_cpuid, arch_int_locked, get_current_task},
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    percpu::can_preempt,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        global::get_tcb_from_id,
+        sched::{
+            priority_queue_insert_at_back, priority_queue_remove, schedule, schedule_reschedule,
+        },
+        timer::add_to_timer_list,
+        types::{TaskCB, TaskStatus},
+    },
+    tick::global::get_current_tick_count,
+    utils::list::LinkedList,
+};
+
+/// EDF任务列表，按创建顺序链接所有EDF任务
+static mut EDF_TASK_LIST: LinkedList = LinkedList::new();
+
+/// EDF调度参数，单位为Tick，周期为0表示普通优先级任务
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskEdfParam {
+    /// 作业释放周期
+    pub period: u32,
+    /// 每个作业的执行预算
+    pub budget: u32,
+    /// 相对截止时间，0表示与周期相同
+    pub deadline: u32,
+}
+
+/// EDF任务的运行统计
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskEdfStats {
+    /// 已释放的作业数
+    pub jobs: u32,
+    /// 截止时间错失次数
+    pub misses: u32,
+    /// 预算耗尽次数
+    pub overruns: u32,
+}
+
+/// 任务控制块中的EDF状态
+#[repr(C)]
+#[derive(Debug)]
+pub struct TaskEdf {
+    /// 调度参数
+    pub param: TaskEdfParam,
+    /// 当前作业的绝对截止时间
+    pub job_deadline: u64,
+    /// 下一个作业的释放时间
+    pub next_release: u64,
+    /// 当前作业剩余的执行预算
+    pub remaining: u32,
+    /// 当前作业已完成，等待下一个作业释放
+    pub job_done: bool,
+    /// 预算耗尽，以普通优先级运行直到下一个作业释放
+    pub throttled: bool,
+    /// 当前作业已计入截止时间错失
+    pub missed: bool,
+    /// 任务位于EDF就绪队列中
+    pub queued: bool,
+    /// 运行统计
+    pub stats: TaskEdfStats,
+    /// EDF任务列表节点
+    pub list: LinkedList,
+}
+
+impl TaskEdf {
+    pub const UNINIT: Self = Self {
+        param: TaskEdfParam {
+            period: 0,
+            budget: 0,
+            deadline: 0,
+        },
+        job_deadline: 0,
+        next_release: 0,
+        remaining: 0,
+        job_done: false,
+        throttled: false,
+        missed: false,
+        queued: false,
+        stats: TaskEdfStats {
+            jobs: 0,
+            misses: 0,
+            overruns: 0,
+        },
+        list: LinkedList::new(),
+    };
+}
+
+/// 初始化EDF任务列表
+pub(crate) fn edf_init() {
+    LinkedList::init(&raw mut EDF_TASK_LIST);
+}
+
+/// 检查EDF调度参数，须满足 0 < 预算 <= 截止时间 <= 周期
+pub(crate) fn check_edf_param(param: &TaskEdfParam) -> SystemResult<()> {
+    if param.period == 0 {
+        return Ok(());
+    }
+    let deadline = if param.deadline == 0 {
+        param.period
+    } else {
+        param.deadline
+    };
+    if param.budget == 0 || param.budget > deadline || deadline > param.period {
+        return Err(SystemError::Task(TaskError::EdfParamError));
+    }
+    Ok(())
+}
+
+/// 初始化任务的EDF状态，EDF任务的第一个作业在创建时释放
+pub(crate) fn edf_task_init(task_cb: &mut TaskCB, param: &TaskEdfParam) {
+    task_cb.edf = TaskEdf::UNINIT;
+    if param.period == 0 {
+        return;
+    }
+    task_cb.edf.param = *param;
+    if param.deadline == 0 {
+        task_cb.edf.param.deadline = param.period;
+    }
+    // 按上一个周期刚结束计算，使第一个作业在当前时刻释放
+    let now = get_current_tick_count();
+    task_cb.edf.next_release = now;
+    task_cb.edf.job_done = true;
+    edf_job_release(task_cb, now);
+
+    let int_save = disable_interrupts();
+    LinkedList::tail_insert(&raw mut EDF_TASK_LIST, &mut task_cb.edf.list);
+    restore_interrupt_state(int_save);
+}
+
+/// 任务删除时移出EDF任务列表，须在关中断后调用
+pub(crate) fn edf_task_deinit(task_cb: &mut TaskCB) {
+    debug_assert!(arch_int_locked());
+    if is_edf_task(task_cb) {
+        LinkedList::remove(&mut task_cb.edf.list);
+        task_cb.edf.param.period = 0;
+    }
+}
+
+/// 任务是否为EDF任务
+#[inline]
+pub fn is_edf_task(task_cb: &TaskCB) -> bool {
+    task_cb.edf.param.period != 0
+}
+
+/// 任务是否按截止时间调度，预算耗尽的EDF任务按普通优先级调度
+#[inline]
+pub(crate) fn is_edf_active(task_cb: &TaskCB) -> bool {
+    is_edf_task(task_cb) && !task_cb.edf.throttled
+}
+
+/// 释放新的作业，落后多个周期时跳过已经过去的作业
+fn edf_job_release(task_cb: &mut TaskCB, now: u64) {
+    let edf = &mut task_cb.edf;
+    let period = edf.param.period as u64;
+
+    // 上一个作业在释放新作业时仍未完成
+    if !edf.job_done && !edf.missed {
+        edf.stats.misses += 1;
+    }
+
+    let release = edf.next_release + (now - edf.next_release) / period * period;
+    edf.job_deadline = release + edf.param.deadline as u64;
+    edf.next_release = release + period;
+    edf.remaining = edf.param.budget;
+    edf.job_done = false;
+    edf.throttled = false;
+    edf.missed = false;
+    edf.stats.jobs += 1;
+}
+
+/// EDF的Tick处理：扣除运行任务的预算，并由0号核检查截止时间和释放作业
+pub fn edf_tick() {
+    let int_save = disable_interrupts();
+
+    let mut need_schedule = false;
+    let run_task = get_current_task();
+    if is_edf_active(run_task) && !run_task.edf.job_done {
+        run_task.edf.remaining = run_task.edf.remaining.saturating_sub(1);
+        if run_task.edf.remaining == 0 {
+            // 预算耗尽，退回普通优先级
+            run_task.edf.throttled = true;
+            run_task.edf.stats.overruns += 1;
+            need_schedule = true;
+        }
+    }
+
+    if arch_curr_cpuid() == 0 {
+        let now = get_current_tick_count();
+        let head = &raw mut EDF_TASK_LIST;
+        let mut cur = unsafe { (*head).next };
+        while cur != head {
+            let task_cb = unsafe { &mut *container_of!(cur, TaskCB, edf.list) };
+            cur = unsafe { (*cur).next };
+            // 挂起的任务不释放作业
+            if task_cb.task_status.contains(TaskStatus::SUSPEND) {
+                continue;
+            }
+            need_schedule |= edf_task_check(task_cb, now);
+        }
+    }
+
+    restore_interrupt_state(int_save);
+
+    if need_schedule {
+        schedule();
+    }
+}
+
+/// 检查单个EDF任务的截止时间和作业释放，返回本核是否需要调度
+fn edf_task_check(task_cb: &mut TaskCB, now: u64) -> bool {
+    let edf = &mut task_cb.edf;
+    if !edf.job_done && !edf.missed && now >= edf.job_deadline {
+        edf.missed = true;
+        edf.stats.misses += 1;
+    }
+    if now < edf.next_release {
+        return false;
+    }
+
+    // 就绪的任务按新的截止时间重新入队
+    let status = task_cb.task_status;
+    if status.contains(TaskStatus::READY) {
+        priority_queue_remove(&mut task_cb.pend_list);
+    }
+    edf_job_release(task_cb, now);
+    if status.contains(TaskStatus::READY) {
+        priority_queue_insert_at_back(&mut task_cb.pend_list, task_cb.priority as u32);
+        return true;
+    }
+
+    // 运行中的任务可能从普通优先级恢复为EDF调度
+    if status.contains(TaskStatus::RUNNING) {
+        #[cfg(feature = "smp")]
+        if task_cb.curr_cpu as u32 != arch_curr_cpuid() {
+            mp_schedule(1 << task_cb.curr_cpu);
+            return false;
+        }
+        return true;
+    }
+    false
+}
+
+/// 完成当前作业并等待下一个作业释放
+pub fn task_edf_wait_next_period() -> SystemResult<()> {
+    // 检查是否在中断上下文
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::EdfWaitInInterrupt));
+    }
+
+    // 检查是否可以抢占
+    if !can_preempt() {
+        return Err(SystemError::Task(TaskError::EdfWaitInLock));
+    }
+
+    let run_task = get_current_task();
+    if !is_edf_task(run_task) {
+        return Err(SystemError::Task(TaskError::NotEdfTask));
+    }
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    let now = get_current_tick_count();
+    let edf = &mut run_task.edf;
+    if !edf.missed && now >= edf.job_deadline {
+        edf.missed = true;
+        edf.stats.misses += 1;
+    }
+    edf.job_done = true;
+
+    let next_release = edf.next_release;
+    if now >= next_release {
+        // 已经进入下一个周期，立即开始新的作业
+        edf_job_release(run_task, now);
+    } else {
+        // 延时到下一个作业释放，由Tick处理释放作业
+        add_to_timer_list(run_task, (next_release - now) as u32);
+        run_task.task_status.insert(TaskStatus::DELAY);
+        schedule_reschedule();
+    }
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    Ok(())
+}
+
+/// 获取EDF任务的运行统计
+pub fn task_edf_stats_get(task_id: u32) -> SystemResult<TaskEdfStats> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    let result = if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        Err(SystemError::Task(TaskError::NotCreated))
+    } else if !is_edf_task(task_cb) {
+        Err(SystemError::Task(TaskError::NotEdfTask))
+    } else {
+        Ok(task_cb.edf.stats)
+    };
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    result
+}
@@ -43,6 +43,50 @@ pub enum TaskError {
     YieldInLock,
     /// 没有足够的同优先级任务进行让出操作
     YieldNotEnoughTask,
+    /// CPU亲和性掩码错误
+    CpuAffinityMaskError,
+    /// 任务不可被等待
+    NotJoinable,
+    /// 在中断中等待任务退出
+    JoinInInterrupt,
+    /// 在锁定状态下等待任务退出
+    JoinInLock,
+    /// 等待任务退出超时
+    JoinTimeout,
+    /// 时间片长度错误
+    TimeSliceError,
+    /// EDF调度参数错误
+    EdfParamError,
+    /// 不是EDF任务
+    NotEdfTask,
+    /// 等待被信号打断
+    Interrupted,
+    /// 信号编号无效
+    SignalInvalid,
+    /// 在中断中操作当前任务的信号
+    SignalInInterrupt,
+    /// 任务切换处理函数已满
+    SwitchHandlerFull,
+    /// 任务切换处理函数未注册
+    SwitchHandlerNotFound,
+    /// 等待对象的拥有者已被删除
+    OwnerDead,
+    /// 对象的拥有者处理策略无效
+    OwnerPolicyInvalid,
+    /// 任务通知动作无效
+    NotifyActionInvalid,
+    /// 已有未取走的任务通知
+    NotifyPending,
+    /// 在中断中等待任务通知
+    NotifyInInterrupt,
+    /// 在锁定状态下等待任务通知
+    NotifyInLock,
+    /// 等待任务通知超时
+    NotifyTimeout,
+    /// 在中断中等待EDF下一个周期
+    EdfWaitInInterrupt,
+    /// 在锁定状态下等待EDF下一个周期
+    EdfWaitInLock,
 }
 
 /// 将TaskError转换为错误码
@@ -70,6 +114,28 @@ impl From<TaskError> for u32 {
             TaskError::YieldInInterrupt => ERRNO_TSK_YIELD_IN_INT,
             TaskError::YieldInLock => ERRNO_TSK_YIELD_IN_LOCK,
             TaskError::YieldNotEnoughTask => ERRNO_TSK_YIELD_NOT_ENOUGH_TASK,
+            TaskError::CpuAffinityMaskError => ERRNO_TSK_CPU_AFFINITY_MASK_ERR,
+            TaskError::NotJoinable => ERRNO_TSK_NOT_JOINABLE,
+            TaskError::JoinInInterrupt => ERRNO_TSK_JOIN_IN_INT,
+            TaskError::JoinInLock => ERRNO_TSK_JOIN_IN_LOCK,
+            TaskError::JoinTimeout => ERRNO_TSK_JOIN_TIMEOUT,
+            TaskError::TimeSliceError => ERRNO_TSK_TIME_SLICE_ERR,
+            TaskError::EdfParamError => ERRNO_TSK_EDF_PARAM_ERR,
+            TaskError::NotEdfTask => ERRNO_TSK_NOT_EDF,
+            TaskError::Interrupted => ERRNO_TSK_INTERRUPTED,
+            TaskError::SignalInvalid => ERRNO_TSK_SIGNAL_INVALID,
+            TaskError::SignalInInterrupt => ERRNO_TSK_SIGNAL_IN_INT,
+            TaskError::SwitchHandlerFull => ERRNO_TSK_SWITCH_HANDLER_FULL,
+            TaskError::SwitchHandlerNotFound => ERRNO_TSK_SWITCH_HANDLER_NOT_FOUND,
+            TaskError::OwnerDead => ERRNO_TSK_OWNER_DEAD,
+            TaskError::OwnerPolicyInvalid => ERRNO_TSK_OWNER_POLICY_INVALID,
+            TaskError::NotifyActionInvalid => ERRNO_TSK_NOTIFY_ACTION_INVALID,
+            TaskError::NotifyPending => ERRNO_TSK_NOTIFY_PENDING,
+            TaskError::NotifyInInterrupt => ERRNO_TSK_NOTIFY_IN_INT,
+            TaskError::NotifyInLock => ERRNO_TSK_NOTIFY_IN_LOCK,
+            TaskError::NotifyTimeout => ERRNO_TSK_NOTIFY_TIMEOUT,
+            TaskError::EdfWaitInInterrupt => ERRNO_TSK_EDF_WAIT_IN_INT,
+            TaskError::EdfWaitInLock => ERRNO_TSK_EDF_WAIT_IN_LOCK,
         }
     }
 }
@@ -94,7 +160,29 @@ const ERRNO_TSK_TCB_UNAVAILABLE: u32 = 0x02000211;
 const ERRNO_TSK_OPERATE_SYSTEM_TASK: u32 = 0x02000214;
 const ERRNO_TSK_SUSPEND_LOCKED: u32 = 0x03000215;
 const ERRNO_TSK_STKSZ_TOO_LARGE: u32 = 0x02000220;
+const ERRNO_TSK_CPU_AFFINITY_MASK_ERR: u32 = 0x02000223;
 const ERRNO_TSK_YIELD_IN_INT: u32 = 0x02000224;
+const ERRNO_TSK_NOT_JOINABLE: u32 = 0x02000225;
+const ERRNO_TSK_JOIN_IN_INT: u32 = 0x02000226;
+const ERRNO_TSK_JOIN_IN_LOCK: u32 = 0x02000227;
+const ERRNO_TSK_JOIN_TIMEOUT: u32 = 0x02000228;
+const ERRNO_TSK_TIME_SLICE_ERR: u32 = 0x02000229;
+const ERRNO_TSK_EDF_PARAM_ERR: u32 = 0x0200022a;
+const ERRNO_TSK_NOT_EDF: u32 = 0x0200022b;
+const ERRNO_TSK_INTERRUPTED: u32 = 0x0200022c;
+const ERRNO_TSK_SIGNAL_INVALID: u32 = 0x0200022d;
+const ERRNO_TSK_SIGNAL_IN_INT: u32 = 0x0200022e;
+const ERRNO_TSK_SWITCH_HANDLER_FULL: u32 = 0x0200022f;
+const ERRNO_TSK_SWITCH_HANDLER_NOT_FOUND: u32 = 0x02000230;
+const ERRNO_TSK_OWNER_DEAD: u32 = 0x02000231;
+const ERRNO_TSK_OWNER_POLICY_INVALID: u32 = 0x02000232;
+const ERRNO_TSK_NOTIFY_ACTION_INVALID: u32 = 0x02000233;
+const ERRNO_TSK_NOTIFY_PENDING: u32 = 0x02000234;
+const ERRNO_TSK_NOTIFY_IN_INT: u32 = 0x02000235;
+const ERRNO_TSK_NOTIFY_IN_LOCK: u32 = 0x02000236;
+const ERRNO_TSK_NOTIFY_TIMEOUT: u32 = 0x02000237;
+const ERRNO_TSK_EDF_WAIT_IN_INT: u32 = 0x02000238;
+const ERRNO_TSK_EDF_WAIT_IN_LOCK: u32 = 0x02000239;
 
 /// 从u32错误码转换为TaskError
 impl TryFrom<u32> for TaskError {
@@ -123,6 +211,28 @@ impl TryFrom<u32> for TaskError {
             ERRNO_TSK_YIELD_IN_INT => Ok(TaskError::YieldInInterrupt),
             ERRNO_TSK_YIELD_IN_LOCK => Ok(TaskError::YieldInLock),
             ERRNO_TSK_YIELD_NOT_ENOUGH_TASK => Ok(TaskError::YieldNotEnoughTask),
+            ERRNO_TSK_CPU_AFFINITY_MASK_ERR => Ok(TaskError::CpuAffinityMaskError),
+            ERRNO_TSK_NOT_JOINABLE => Ok(TaskError::NotJoinable),
+            ERRNO_TSK_JOIN_IN_INT => Ok(TaskError::JoinInInterrupt),
+            ERRNO_TSK_JOIN_IN_LOCK => Ok(TaskError::JoinInLock),
+            ERRNO_TSK_JOIN_TIMEOUT => Ok(TaskError::JoinTimeout),
+            ERRNO_TSK_TIME_SLICE_ERR => Ok(TaskError::TimeSliceError),
+            ERRNO_TSK_EDF_PARAM_ERR => Ok(TaskError::EdfParamError),
+            ERRNO_TSK_NOT_EDF => Ok(TaskError::NotEdfTask),
+            ERRNO_TSK_INTERRUPTED => Ok(TaskError::Interrupted),
+            ERRNO_TSK_SIGNAL_INVALID => Ok(TaskError::SignalInvalid),
+            ERRNO_TSK_SIGNAL_IN_INT => Ok(TaskError::SignalInInterrupt),
+            ERRNO_TSK_SWITCH_HANDLER_FULL => Ok(TaskError::SwitchHandlerFull),
+            ERRNO_TSK_SWITCH_HANDLER_NOT_FOUND => Ok(TaskError::SwitchHandlerNotFound),
+            ERRNO_TSK_OWNER_DEAD => Ok(TaskError::OwnerDead),
+            ERRNO_TSK_OWNER_POLICY_INVALID => Ok(TaskError::OwnerPolicyInvalid),
+            ERRNO_TSK_NOTIFY_ACTION_INVALID => Ok(TaskError::NotifyActionInvalid),
+            ERRNO_TSK_NOTIFY_PENDING => Ok(TaskError::NotifyPending),
+            ERRNO_TSK_NOTIFY_IN_INT => Ok(TaskError::NotifyInInterrupt),
+            ERRNO_TSK_NOTIFY_IN_LOCK => Ok(TaskError::NotifyInLock),
+            ERRNO_TSK_NOTIFY_TIMEOUT => Ok(TaskError::NotifyTimeout),
+            ERRNO_TSK_EDF_WAIT_IN_INT => Ok(TaskError::EdfWaitInInterrupt),
+            ERRNO_TSK_EDF_WAIT_IN_LOCK => Ok(TaskError::EdfWaitInLock),
             _ => Err(()),
         }
     }
@@ -152,6 +262,30 @@ impl core::fmt::Display for TaskError {
             TaskError::YieldInInterrupt => write!(f, "Yield in interrupt context"),
             TaskError::YieldInLock => write!(f, "Yield in lock context"),
             TaskError::YieldNotEnoughTask => write!(f, "Not enough tasks to yield"),
+            TaskError::CpuAffinityMaskError => write!(f, "CPU affinity mask error"),
+            TaskError::NotJoinable => write!(f, "Task is not joinable"),
+            TaskError::JoinInInterrupt => write!(f, "Join in interrupt context"),
+            TaskError::JoinInLock => write!(f, "Join in lock context"),
+            TaskError::JoinTimeout => write!(f, "Join timed out"),
+            TaskError::TimeSliceError => write!(f, "Time slice error"),
+            TaskError::EdfParamError => write!(f, "EDF parameter error"),
+            TaskError::NotEdfTask => write!(f, "Not an EDF task"),
+            TaskError::Interrupted => write!(f, "Wait interrupted by signal"),
+            TaskError::SignalInvalid => write!(f, "Invalid signal number"),
+            TaskError::SignalInInterrupt => write!(f, "Signal operation in interrupt context"),
+            TaskError::SwitchHandlerFull => write!(f, "Task switch handler table is full"),
+            TaskError::SwitchHandlerNotFound => write!(f, "Task switch handler not registered"),
+            TaskError::OwnerDead => write!(f, "Owner of the waited object was deleted"),
+            TaskError::OwnerPolicyInvalid => write!(f, "Invalid owner-dead policy"),
+            TaskError::NotifyActionInvalid => write!(f, "Invalid task notify action"),
+            TaskError::NotifyPending => write!(f, "Task notification already pending"),
+            TaskError::NotifyInInterrupt => write!(f, "Wait for notification in interrupt context"),
+            TaskError::NotifyInLock => write!(f, "Wait for notification in lock context"),
+            TaskError::NotifyTimeout => write!(f, "Wait for notification timed out"),
+            TaskError::EdfWaitInInterrupt => {
+                write!(f, "Wait for next EDF period in interrupt context")
+            }
+            TaskError::EdfWaitInLock => write!(f, "Wait for next EDF period in lock context"),
         }
     }
 }
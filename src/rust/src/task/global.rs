@@ -1,6 +1,11 @@
 use semihosting::println;
 
-use crate::{config::TASK_LIMIT, task::types::TaskCB, utils::list::LinkedList};
+#[cfg(feature = "cpup")]
+use crate::cpup::{CpupMode, task_cpu_usage};
+use crate::{
+    config::TASK_LIMIT, ffi::bindings::arch_curr_cpuid, task::types::TaskCB,
+    utils::list::LinkedList,
+};
 use core::ptr::addr_of;
 use core::sync::atomic::{AtomicU32, Ordering};
 
@@ -21,10 +26,11 @@ pub fn get_tcb_from_id(task_id: u32) -> &'static mut TaskCB {
 #[unsafe(export_name = "g_taskScheduled")]
 pub static TASK_SCHEDULED: AtomicU32 = AtomicU32::new(0);
 
+/// 当前核是否已启动调度
 #[inline]
 pub fn is_scheduler_active() -> bool {
     let current_state = TASK_SCHEDULED.load(Ordering::Acquire);
-    current_state & 1 != 0
+    current_state & (1 << arch_curr_cpuid()) != 0
 }
 
 #[unsafe(export_name = "OsShellCmdTskInfoGet")]
@@ -37,7 +43,28 @@ pub extern "C" fn print_task_info() {
             if task_cb.is_unused() {
                 continue;
             }
+            #[cfg(not(feature = "cpup"))]
             println!("{}", task_cb);
+            #[cfg(feature = "cpup")]
+            {
+                // 占用率为千分比，按百分比保留一位小数输出
+                let usage = |mode| task_cpu_usage(task_cb.task_id, mode).unwrap_or(0);
+                let (all, ten, one) = (
+                    usage(CpupMode::AllTime),
+                    usage(CpupMode::LastTenSeconds),
+                    usage(CpupMode::LastOneSecond),
+                );
+                println!(
+                    "{} CPUUSE: {}.{}% CPUUSE10s: {}.{}% CPUUSE1s: {}.{}%",
+                    task_cb,
+                    all / 10,
+                    all % 10,
+                    ten / 10,
+                    ten % 10,
+                    one / 10,
+                    one % 10
+                );
+            }
         }
     }
 }
@@ -1,18 +1,19 @@
 use crate::{
-    config::{TASK_IDLE_STACK_SIZE, TASK_PRIORITY_LOWEST},
+    config::{KERNEL_CORE_NUM, TASK_IDLE_STACK_SIZE, TASK_PRIORITY_LOWEST},
     ffi::bindings::wfi,
     interrupt::{disable_interrupts, restore_interrupt_state},
     memory::free,
-    percpu::os_percpu_get,
+    percpu::os_percpu_get_by_id,
     result::SystemResult,
     task::{
         global::{FREE_TASK_LIST, TASK_RECYCLE_LIST, get_tcb_from_id},
-        manager::create::task_create,
-        types::{TaskCB, TaskInitParam},
+        manager::create::{task_create_only, task_resume},
+        resource::task_reclaim_log_flush,
+        types::{TaskCB, TaskInitParam, TaskStatus},
     },
     utils::list::LinkedList,
 };
-use core::ffi::c_void;
+use core::ffi::{CStr, c_void};
 
 fn los_task_recycle() {
     let int_save = disable_interrupts();
@@ -27,43 +28,74 @@ fn los_task_recycle() {
             // 获取包含此链表节点的任务控制块
             let task_cb = TaskCB::from_pend_list(first_node);
 
-            // 将任务控制块添加到空闲列表
-            LinkedList::insert(&raw mut FREE_TASK_LIST, &mut task_cb.pend_list);
-            free(task_cb.top_of_stack as *mut c_void);
+            // 释放任务栈，用户提供的栈由用户管理
+            if task_cb.usr_stack == 0 {
+                free(task_cb.top_of_stack);
+            }
             // 重置栈顶指针
             task_cb.top_of_stack = core::ptr::null_mut();
+
+            // 可被等待的任务保留控制块，由等待者回收
+            if !task_cb.is_joinable() {
+                task_cb.task_status = TaskStatus::UNUSED;
+                LinkedList::insert(&raw mut FREE_TASK_LIST, &mut task_cb.pend_list);
+            }
         }
     }
     restore_interrupt_state(int_save);
+
+    // 输出任务删除时回收的对象
+    task_reclaim_log_flush();
 }
 
 extern "C" fn idle_task(_arg: *mut c_void) {
     loop {
         los_task_recycle();
+        #[cfg(feature = "tickless")]
+        crate::tick::tickless::tickless_start();
         wfi();
     }
 }
 
+/// 各核空闲任务的名称
+const IDLE_TASK_NAMES: [&CStr; 4] = [
+    c"IdleCore000",
+    c"IdleCore001",
+    c"IdleCore002",
+    c"IdleCore003",
+];
+const _: () = assert!(KERNEL_CORE_NUM <= IDLE_TASK_NAMES.len());
+
+/// 为每个CPU核创建空闲任务
 pub fn idle_task_create() -> SystemResult<()> {
-    // 初始化任务参数
-    let mut task_init_param = TaskInitParam {
-        task_entry: Some(idle_task),
-        priority: TASK_PRIORITY_LOWEST,
-        stack_size: TASK_IDLE_STACK_SIZE,
-        name: b"IdleCore000\0".as_ptr(),
-        ..Default::default()
-    };
+    for (cpuid, name) in IDLE_TASK_NAMES.iter().enumerate().take(KERNEL_CORE_NUM) {
+        // 初始化任务参数
+        let mut task_init_param = TaskInitParam {
+            task_entry: Some(idle_task),
+            priority: TASK_PRIORITY_LOWEST,
+            stack_size: TASK_IDLE_STACK_SIZE,
+            name: name.as_ptr(),
+            ..Default::default()
+        };
 
-    // 获取当前CPU的percpu结构
-    let percpu = os_percpu_get();
-    let idle_task_id = &mut percpu.idle_task_id;
+        // 获取对应CPU的percpu结构
+        let percpu = os_percpu_get_by_id(cpuid as u32);
+        let idle_task_id = &mut percpu.idle_task_id;
 
-    // 创建任务
-    task_create(idle_task_id, &mut task_init_param)?;
+        // 创建任务
+        task_create_only(idle_task_id, &mut task_init_param)?;
 
-    // 如果创建成功，设置系统任务标志
-    let task_cb = get_tcb_from_id(*idle_task_id);
-    task_cb.set_system_task();
+        // 设置系统任务标志，空闲任务固定在所属的核上
+        let task_cb = get_tcb_from_id(*idle_task_id);
+        task_cb.set_system_task();
+        #[cfg(feature = "smp")]
+        {
+            task_cb.curr_cpu = cpuid as u16;
+            task_cb.cpu_affi_mask = 1 << cpuid;
+        }
+
+        task_resume(*idle_task_id);
+    }
 
     Ok(())
 }
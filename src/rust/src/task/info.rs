@@ -1,4 +1,21 @@
-use crate::ffi::bindings::get_current_task;
+//! 任务信息查询
+
+#[cfg(feature = "time_slice")]
+use crate::task::manager::timeslice::time_slice_len;
+use crate::{
+    config::{TASK_LIMIT, TASK_NAME_LEN},
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    percpu::os_percpu_get_by_id,
+    result::{SystemError, SystemResult},
+    stack::get_stack_waterline,
+    task::{
+        error::TaskError,
+        global::get_tcb_from_id,
+        types::{TaskCB, TaskPendObject, TaskStatus},
+    },
+    utils::sortlink::get_target_expire_time,
+};
 
 /// 获取当前运行任务的ID
 pub fn get_current_task_id() -> u32 {
@@ -6,3 +23,143 @@ pub fn get_current_task_id() -> u32 {
     let run_task = get_current_task();
     run_task.task_id
 }
+
+/// 任务信息快照
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskInfo {
+    /// 任务ID
+    pub task_id: u32,
+    /// 以0结尾的任务名称
+    pub name: [u8; TASK_NAME_LEN],
+    /// 任务状态
+    pub status: TaskStatus,
+    /// 当前优先级，包含继承得到的优先级
+    pub priority: u16,
+    /// 优先级继承前的基础优先级
+    pub base_priority: u16,
+    /// 栈大小
+    pub stack_size: u32,
+    /// 栈水位线，栈顶魔数被破坏时为`u32::MAX`
+    pub stack_waterline: u32,
+    /// 等待的对象
+    pub pend_object: TaskPendObject,
+    /// 延时或等待超时的剩余Tick数，不在定时器链表中时为0
+    pub delay_ticks: u32,
+    /// 剩余时间片，未启用时间片时为0
+    pub time_slice: u16,
+    /// 生效的时间片长度，0表示同优先级内先进先出
+    pub time_slice_len: u16,
+}
+
+impl TaskInfo {
+    /// 任务名称
+    pub fn name(&self) -> &str {
+        let len = self
+            .name
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(TASK_NAME_LEN);
+        core::str::from_utf8(&self.name[..len]).unwrap_or("unknown")
+    }
+
+    /// 须在关中断后调用
+    fn snapshot(task_cb: &TaskCB) -> Self {
+        let status = task_cb.task_status;
+
+        let stack_waterline = if task_cb.top_of_stack.is_null() {
+            0
+        } else {
+            let top = task_cb.top_of_stack as usize;
+            let bottom = top + task_cb.stack_size as usize;
+            unsafe { get_stack_waterline(&*(top as *const usize), &*(bottom as *const usize)) }
+                .unwrap_or(u32::MAX)
+        };
+
+        let pend_object = if status.contains(TaskStatus::PEND) {
+            task_cb.pend_object
+        } else {
+            TaskPendObject::None
+        };
+
+        let delay_ticks = if status.intersects(TaskStatus::DELAY | TaskStatus::PEND_TIME) {
+            let sort_link_header =
+                &os_percpu_get_by_id(task_cb.sort_list.get_cpuid()).task_sort_link;
+            get_target_expire_time(sort_link_header, &task_cb.sort_list)
+        } else {
+            0
+        };
+
+        #[cfg(feature = "time_slice")]
+        let (time_slice, time_slice_len) = (task_cb.time_slice, time_slice_len(task_cb));
+        #[cfg(not(feature = "time_slice"))]
+        let (time_slice, time_slice_len) = (0, 0);
+
+        Self {
+            task_id: task_cb.task_id,
+            name: task_cb.name_buf,
+            status,
+            priority: task_cb.priority,
+            base_priority: task_cb.priority_bitmap.last().unwrap_or(task_cb.priority),
+            stack_size: task_cb.stack_size,
+            stack_waterline,
+            pend_object,
+            delay_ticks,
+            time_slice,
+            time_slice_len,
+        }
+    }
+}
+
+/// 获取任务信息快照
+pub fn task_info_get(task_id: u32) -> SystemResult<TaskInfo> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    let result = if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        Err(SystemError::Task(TaskError::NotCreated))
+    } else {
+        Ok(TaskInfo::snapshot(task_cb))
+    };
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    result
+}
+
+/// 遍历所有已创建任务的迭代器，见[`task_info_iter`]
+#[derive(Debug, Clone)]
+pub struct TaskInfoIter {
+    next_id: u32,
+}
+
+impl Iterator for TaskInfoIter {
+    type Item = TaskInfo;
+
+    fn next(&mut self) -> Option<TaskInfo> {
+        while self.next_id < TASK_LIMIT {
+            let task_id = self.next_id;
+            self.next_id += 1;
+            if let Ok(info) = task_info_get(task_id) {
+                return Some(info);
+            }
+        }
+        None
+    }
+}
+
+/// 按任务ID顺序遍历所有已创建的任务
+///
+/// 每个任务的快照在各自的临界区内获取，遍历期间创建或删除的任务可能被包含也可能被跳过。
+pub fn task_info_iter() -> TaskInfoIter {
+    TaskInfoIter { next_id: 0 }
+}
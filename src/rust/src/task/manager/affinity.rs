@@ -0,0 +1,111 @@
+use crate::{
+    config::{KERNEL_CPU_MASK, TASK_LIMIT},
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    result::{SystemError, SystemResult},
+    task::{error::TaskError, global::get_tcb_from_id, types::TaskStatus},
+};
+#[cfg(feature = "smp")]
+use crate::{
+    ffi::bindings::arch_curr_cpuid,
+    mp::mp_schedule,
+    task::sched::{priority_queue_insert_at_back, priority_queue_remove, schedule},
+};
+
+/// 设置任务的CPU亲和性掩码
+///
+/// 就绪任务会被移到掩码允许的核上；正在不允许的核上运行的任务会在该核下一次调度时迁移。
+pub fn task_cpu_affi_set(task_id: u32, cpu_affi_mask: u16) -> SystemResult<()> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 掩码中必须至少包含一个存在的核
+    let cpu_affi_mask = cpu_affi_mask & KERNEL_CPU_MASK;
+    if cpu_affi_mask == 0 {
+        return Err(SystemError::Task(TaskError::CpuAffinityMaskError));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 检查是否为系统任务
+    if task_cb.is_system_task() {
+        return Err(SystemError::Task(TaskError::OperateSystemTask));
+    }
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    let temp_status = task_cb.task_status;
+    if temp_status.contains(TaskStatus::UNUSED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    #[cfg(feature = "smp")]
+    let needs_reschedule = {
+        task_cb.cpu_affi_mask = cpu_affi_mask;
+        let cpuid = task_cb.curr_cpu as usize;
+        if task_cb.can_run_on(cpuid) {
+            false
+        } else if temp_status.contains(TaskStatus::READY) {
+            // 重新入队，由调度器按新的掩码选择核
+            priority_queue_remove(&mut task_cb.pend_list);
+            priority_queue_insert_at_back(&mut task_cb.pend_list, task_cb.priority as u32);
+            false
+        } else if temp_status.contains(TaskStatus::RUNNING) {
+            // 通知运行该任务的核重新调度，任务在被换出时迁移
+            if cpuid as u32 == arch_curr_cpuid() {
+                true
+            } else {
+                mp_schedule(1 << cpuid);
+                false
+            }
+        } else {
+            false
+        }
+    };
+    #[cfg(not(feature = "smp"))]
+    let _ = temp_status;
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    #[cfg(feature = "smp")]
+    if needs_reschedule {
+        schedule();
+    }
+
+    Ok(())
+}
+
+/// 获取任务的CPU亲和性掩码
+pub fn task_cpu_affi_get(task_id: u32) -> SystemResult<u16> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    let result = if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        Err(SystemError::Task(TaskError::NotCreated))
+    } else {
+        #[cfg(feature = "smp")]
+        let cpu_affi_mask = task_cb.cpu_affi_mask;
+        #[cfg(not(feature = "smp"))]
+        let cpu_affi_mask = KERNEL_CPU_MASK;
+        Ok(cpu_affi_mask)
+    };
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    result
+}
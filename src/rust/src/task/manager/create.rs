@@ -1,23 +1,26 @@
 use crate::{
     config::{
-        STACK_POINT_ALIGN_SIZE, TASK_DEFAULT_STACK_SIZE, TASK_MIN_STACK_SIZE, TASK_PRIORITY_LOWEST,
+        RWLOCK_READ_HOLD_LIMIT, STACK_POINT_ALIGN_SIZE, TASK_DEFAULT_STACK_SIZE,
+        TASK_MIN_STACK_SIZE, TASK_NAME_LEN, TASK_PRIORITY_LOWEST,
     },
     ffi::bindings::task_stack_init,
     interrupt::{disable_interrupts, restore_interrupt_state},
     memory::{memalign, os_sys_mem_size},
     result::{SystemError, SystemResult},
+    rwlock::types::RwlockReadHold,
     task::{
         error::TaskError,
         global::{FREE_TASK_LIST, get_tcb_from_id, is_scheduler_active},
         sched::{priority_queue_insert_at_back, schedule},
-        types::{TaskCB, TaskInitParam, TaskStatus},
+        types::{TASK_ATTR_JOINABLE, TaskCB, TaskFlags, TaskInitParam, TaskStatus},
     },
     utils::{
         align::{align_up, is_aligned},
+        bitmap::PriorityBitmap,
         list::LinkedList,
     },
 };
-use core::ffi::c_void;
+use core::ffi::{CStr, c_char, c_void};
 
 fn check_task_init_param(init_param: &TaskInitParam) -> SystemResult<()> {
     // 检查任务名称是否为空
@@ -32,6 +35,9 @@ fn check_task_init_param(init_param: &TaskInitParam) -> SystemResult<()> {
     if init_param.priority > TASK_PRIORITY_LOWEST {
         return Err(SystemError::Task(TaskError::PriorityError));
     }
+    // 检查EDF调度参数
+    #[cfg(feature = "edf")]
+    crate::task::edf::check_edf_param(&init_param.edf)?;
     Ok(())
 }
 
@@ -114,6 +120,15 @@ fn get_free_task_cb() -> SystemResult<&'static mut TaskCB> {
     Ok(task_cb)
 }
 
+/// 把任务名称复制到控制块中，调用者无需在任务存续期间保留名称
+fn copy_task_name(task_cb: &mut TaskCB, name: *const c_char) {
+    let name = unsafe { CStr::from_ptr(name) }.to_bytes();
+    let len = name.len().min(TASK_NAME_LEN - 1);
+    task_cb.name_buf[..len].copy_from_slice(&name[..len]);
+    task_cb.name_buf[len] = 0;
+    task_cb.task_name = task_cb.name_buf.as_ptr() as *const c_char;
+}
+
 /// 初始化任务控制块
 fn init_task_cb(
     task_cb: &mut TaskCB,
@@ -131,21 +146,42 @@ fn init_task_cb(
     // 任务状态和优先级
     task_cb.task_status = TaskStatus::SUSPEND;
     task_cb.priority = init_param.priority;
-    task_cb.priority_bitmap = 0;
+    task_cb.priority_bitmap = PriorityBitmap::new();
+    LinkedList::init(&raw mut task_cb.held_mutexes);
+    LinkedList::init(&raw mut task_cb.held_rwlocks);
     task_cb.task_entry = init_param.task_entry;
 
+    // 读锁记录
+    task_cb.rwlock_reads = [RwlockReadHold::UNINIT; RWLOCK_READ_HOLD_LIMIT];
+    task_cb.select_nodes = core::ptr::null_mut();
+    task_cb.select_count = 0;
+
     {
         LinkedList::init(&raw mut task_cb.event.wait_list);
         task_cb.event.event_id = 0;
         task_cb.event_mask = 0;
+        task_cb.event_group_bits = 0;
     }
 
+    // 退出状态
+    task_cb.exit_code = 0;
+    LinkedList::init(&raw mut task_cb.join_list);
+
+    // 任务通知
+    task_cb.notify_value = 0;
+    task_cb.notify_pending = false;
+    LinkedList::init(&raw mut task_cb.notify_list);
+    LinkedList::init(&raw mut task_cb.mem_list);
+
     // 任务名称和消息
-    task_cb.task_name = init_param.name;
+    copy_task_name(task_cb, init_param.name);
     // task_cb.msg = core::ptr::null_mut();
 
     // 设置任务标志
     task_cb.clear_all_flags();
+    if init_param.resved & TASK_ATTR_JOINABLE != 0 {
+        task_cb.task_flags.insert(TaskFlags::JOINABLE);
+    }
 
     // 栈类型标志：0-动态分配栈空间；1-用户提供栈空间
     task_cb.usr_stack = if use_usr_stack { 1 } else { 0 };
@@ -157,6 +193,28 @@ fn init_task_cb(
     #[cfg(feature = "time_slice")]
     {
         task_cb.time_slice = 0;
+        task_cb.time_slice_len = init_param.time_slice;
+    }
+
+    // CPU占用率统计
+    #[cfg(feature = "cpup")]
+    {
+        task_cb.cpup = crate::cpup::TaskCpup::UNINIT;
+    }
+
+    // EDF调度状态
+    #[cfg(feature = "edf")]
+    crate::task::edf::edf_task_init(task_cb, &init_param.edf);
+
+    // 用户信号
+    #[cfg(feature = "task_signal")]
+    crate::task::user_signal::signal_task_init(task_cb);
+
+    // 新任务从创建它的核开始调度，默认可以在所有核上运行
+    #[cfg(feature = "smp")]
+    {
+        task_cb.curr_cpu = crate::ffi::bindings::arch_curr_cpuid() as u16;
+        task_cb.cpu_affi_mask = crate::config::KERNEL_CPU_MASK;
     }
 }
 
@@ -218,7 +276,7 @@ fn task_create_internal(
     Ok(())
 }
 
-fn task_resume(task_id: u32) {
+pub(crate) fn task_resume(task_id: u32) {
     // 根据任务ID获取任务控制块
     let task_cb = get_tcb_from_id(task_id);
     // 加锁进行原子操作
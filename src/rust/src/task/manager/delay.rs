@@ -5,7 +5,10 @@ use crate::{
     result::{SystemError, SystemResult},
     task::{
         error::TaskError,
-        sched::{priority_queue_get_size, priority_queue_insert_at_back, schedule_reschedule},
+        sched::{
+            priority_queue_get_size, priority_queue_insert_at_back, schedule_reschedule,
+            schedule_yield,
+        },
         timer::add_to_timer_list,
         types::TaskStatus,
     },
@@ -88,7 +91,7 @@ pub fn task_yield() -> SystemResult<()> {
         priority_queue_insert_at_back(&mut run_task.pend_list, run_task.priority as u32);
 
         // 触发重新调度
-        schedule_reschedule();
+        schedule_yield();
 
         // 解锁调度器
         restore_interrupt_state(int_save);
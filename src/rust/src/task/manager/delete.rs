@@ -4,24 +4,30 @@ use crate::{
     memory::free,
     percpu::can_preempt_in_scheduler,
     result::{SystemError, SystemResult},
+    select::core::select_cancel,
     task::{
         error::TaskError,
         global::{FREE_TASK_LIST, TASK_RECYCLE_LIST, get_tcb_from_id},
-        sched::{priority_queue_remove, schedule_reschedule},
+        manager::exit::task_join_post,
+        resource::task_resource_reclaim,
+        sched::{priority_queue_remove, schedule, schedule_reschedule},
         timer::delete_from_timer_list,
         types::{TaskCB, TaskSignal, TaskStatus},
     },
     utils::list::LinkedList,
 };
-use core::{ffi::c_void, ptr::null_mut};
+#[cfg(feature = "smp")]
+use crate::{ffi::bindings::arch_curr_cpuid, mp::mp_schedule};
+use core::ptr::null_mut;
 
 /// 执行任务删除操作
 fn perform_task_deletion(task_cb: &mut TaskCB, use_usr_stack: bool) -> bool {
     // 检查任务是否在运行中
     if task_cb.task_status.contains(TaskStatus::RUNNING) {
+        // 可被等待的任务在被回收前不能复用控制块
         #[cfg(feature = "task_static_allocation")]
         {
-            if use_usr_stack {
+            if use_usr_stack && !task_cb.is_joinable() {
                 LinkedList::insert(&raw mut FREE_TASK_LIST, &mut task_cb.pend_list);
             } else {
                 LinkedList::tail_insert(&raw mut TASK_RECYCLE_LIST, &mut task_cb.pend_list);
@@ -31,25 +37,36 @@ fn perform_task_deletion(task_cb: &mut TaskCB, use_usr_stack: bool) -> bool {
         {
             LinkedList::tail_insert(&raw mut TASK_RECYCLE_LIST, &mut task_cb.pend_list);
         }
-        return true;
+        true
     } else {
         // 处理非运行状态的任务删除
         task_cb.task_status = TaskStatus::UNUSED;
-        LinkedList::insert(&raw mut FREE_TASK_LIST, &mut task_cb.pend_list);
+        if task_cb.is_joinable() {
+            task_cb.task_status.insert(TaskStatus::EXIT);
+        } else {
+            LinkedList::insert(&raw mut FREE_TASK_LIST, &mut task_cb.pend_list);
+        }
 
         // 释放任务栈内存
         if !use_usr_stack {
-            let task_stack = (*task_cb).top_of_stack;
-            free(task_stack as *mut c_void);
+            let task_stack = task_cb.top_of_stack;
+            free(task_stack);
         }
 
         task_cb.top_of_stack = null_mut();
-        return false;
+        false
     }
 }
 
 /// 检查是否可以删除运行中的任务
 fn can_delete_running_task(task_cb: &mut TaskCB) -> SystemResult<bool> {
+    // 任务运行在其他核上，由该核在中断退出时删除
+    #[cfg(feature = "smp")]
+    if task_cb.curr_cpu as u32 != arch_curr_cpuid() {
+        task_cb.signal = TaskSignal::KILL;
+        mp_schedule(1 << task_cb.curr_cpu);
+        return Ok(false);
+    }
     // 检查调度器是否可抢占
     if !can_preempt_in_scheduler() {
         // 如果任务正在运行且调度器被锁定，则不能删除
@@ -71,9 +88,6 @@ pub fn task_delete(task_id: u32) -> SystemResult<()> {
         return Err(SystemError::Task(TaskError::InvalidId));
     }
 
-    // 锁定调度器
-    let int_save = disable_interrupts();
-
     // 获取任务控制块
     let task_cb = get_tcb_from_id(task_id);
 
@@ -81,6 +95,9 @@ pub fn task_delete(task_id: u32) -> SystemResult<()> {
         return Err(SystemError::Task(TaskError::OperateSystemTask));
     }
 
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
     // 获取任务状态
     let temp_status = task_cb.task_status;
 
@@ -114,6 +131,9 @@ pub fn task_delete(task_id: u32) -> SystemResult<()> {
         LinkedList::remove(&mut task_cb.pend_list);
     }
 
+    // 多对象等待的节点在被删除任务的栈上
+    select_cancel(task_cb);
+
     // 如果任务在延时列表中，将其移除
     if temp_status.intersects(TaskStatus::DELAY | TaskStatus::PEND_TIME) {
         delete_from_timer_list(task_cb);
@@ -126,6 +146,17 @@ pub fn task_delete(task_id: u32) -> SystemResult<()> {
     // 清除事件相关信息
     task_cb.event.event_id = u32::MAX;
     task_cb.event_mask = 0;
+    task_cb.event_group_bits = 0;
+
+    // 移出EDF任务列表
+    #[cfg(feature = "edf")]
+    crate::task::edf::edf_task_deinit(task_cb);
+
+    // 按策略回收任务持有或创建的内核对象
+    let owner_woken = task_resource_reclaim(task_cb);
+
+    // 记录退出状态并唤醒等待该任务退出的任务
+    let joiner_woken = task_join_post(task_cb);
 
     // 执行任务删除操作，如果需要重新调度则执行
     if perform_task_deletion(task_cb, task_cb.usr_stack != 0) {
@@ -134,5 +165,9 @@ pub fn task_delete(task_id: u32) -> SystemResult<()> {
 
     // 解锁调度器
     restore_interrupt_state(int_save);
+
+    if joiner_woken || owner_woken {
+        schedule();
+    }
     Ok(())
 }
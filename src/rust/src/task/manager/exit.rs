@@ -0,0 +1,179 @@
+use crate::{
+    config::TASK_LIMIT,
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    percpu::{can_preempt, os_percpu_get},
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        global::{FREE_TASK_LIST, get_tcb_from_id},
+        manager::delete::task_delete,
+        sched::schedule_reschedule,
+        sync::wait::{task_wait, task_wait_interrupted, task_wake},
+        types::{TaskCB, TaskFlags, TaskPendObject, TaskStatus},
+    },
+    utils::list::LinkedList,
+};
+
+/// 记录任务退出并唤醒等待者，返回是否唤醒了任务
+///
+/// 须在关中断后由删除流程调用
+pub(crate) fn task_join_post(task_cb: &mut TaskCB) -> bool {
+    if !task_cb.is_joinable() {
+        return false;
+    }
+    task_cb.task_status.insert(TaskStatus::EXIT);
+
+    if LinkedList::is_empty(&raw const task_cb.join_list) {
+        return false;
+    }
+    let joiner = TaskCB::from_pend_list(LinkedList::first(&raw const task_cb.join_list));
+    task_wake(joiner);
+    true
+}
+
+/// 回收已退出任务的控制块，栈尚未释放时交由空闲任务回收
+fn task_reclaim(task_cb: &mut TaskCB) {
+    task_cb
+        .task_flags
+        .remove(TaskFlags::JOINABLE | TaskFlags::JOINED);
+    if task_cb.top_of_stack.is_null() {
+        task_cb.task_status = TaskStatus::UNUSED;
+        LinkedList::insert(&raw mut FREE_TASK_LIST, &mut task_cb.pend_list);
+    }
+}
+
+/// 以指定的退出码结束当前任务
+///
+/// 任务入口函数返回时以退出码0调用，只能在任务上下文中使用
+pub fn task_exit(exit_code: u32) -> ! {
+    let run_task = get_current_task();
+
+    let int_save = disable_interrupts();
+
+    // 清除任务锁定计数
+    os_percpu_get().task_lock_cnt = 0;
+    run_task.exit_code = exit_code;
+
+    restore_interrupt_state(int_save);
+
+    // 删除任务，成功时不会返回
+    let task_id = run_task.task_id;
+    let result = task_delete(task_id);
+    unreachable!("task {} failed to exit: {:?}", task_id, result);
+}
+
+/// 等待可被等待的任务退出，返回其退出码并回收控制块
+///
+/// 被[`task_delete`]删除的任务保留删除前的退出码，默认为0
+pub fn task_join(task_id: u32, timeout: u32) -> SystemResult<u32> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 检查是否在中断上下文
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::JoinInInterrupt));
+    }
+
+    // 检查调度器是否被锁定
+    if !can_preempt() {
+        return Err(SystemError::Task(TaskError::JoinInLock));
+    }
+
+    let run_task = get_current_task();
+    if run_task.task_id == task_id {
+        return Err(SystemError::Task(TaskError::NotJoinable));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    if task_cb.task_status.contains(TaskStatus::UNUSED) && !task_cb.is_exited() {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    // 每个任务只能被一个任务等待
+    if !task_cb.is_joinable() || task_cb.task_flags.contains(TaskFlags::JOINED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotJoinable));
+    }
+
+    if !task_cb.is_exited() {
+        if timeout == 0 {
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::JoinTimeout));
+        }
+
+        task_cb.task_flags.insert(TaskFlags::JOINED);
+        task_wait(
+            &mut task_cb.join_list,
+            TaskPendObject::Join(task_id),
+            timeout,
+        );
+        schedule_reschedule();
+
+        if run_task.task_status.contains(TaskStatus::TIMEOUT) {
+            run_task.task_status.remove(TaskStatus::TIMEOUT);
+            task_cb.task_flags.remove(TaskFlags::JOINED);
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::JoinTimeout));
+        }
+        if task_wait_interrupted(run_task) {
+            task_cb.task_flags.remove(TaskFlags::JOINED);
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::Interrupted));
+        }
+    }
+
+    let exit_code = task_cb.exit_code;
+    task_reclaim(task_cb);
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    Ok(exit_code)
+}
+
+/// 将任务设为分离状态，任务退出后由系统直接回收
+pub fn task_detach(task_id: u32) -> SystemResult<()> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    if task_cb.task_status.contains(TaskStatus::UNUSED) && !task_cb.is_exited() {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    // 已分离或正在被等待的任务不能分离
+    if !task_cb.is_joinable() || task_cb.task_flags.contains(TaskFlags::JOINED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotJoinable));
+    }
+
+    if task_cb.is_exited() {
+        task_reclaim(task_cb);
+    } else {
+        task_cb.task_flags.remove(TaskFlags::JOINABLE);
+    }
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    Ok(())
+}
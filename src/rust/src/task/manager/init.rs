@@ -1,6 +1,6 @@
 use crate::{
-    config::TASK_LIMIT,
-    percpu::os_percpu_get,
+    config::{KERNEL_CORE_NUM, TASK_LIMIT},
+    percpu::os_percpu_get_by_id,
     task::{
         global::{FREE_TASK_LIST, TASK_RECYCLE_LIST, get_tcb_from_id},
         sched::init_priority_queue,
@@ -24,7 +24,12 @@ pub fn init_task_system() {
     // 初始化优先级队列
     init_priority_queue();
 
+    // 初始化EDF任务列表
+    #[cfg(feature = "edf")]
+    crate::task::edf::edf_init();
+
     // 为每个CPU核心初始化排序链接
-    let percpu_array = os_percpu_get();
-    os_sort_link_init(&mut percpu_array.task_sort_link);
-}
\ No newline at end of file
+    for cpuid in 0..KERNEL_CORE_NUM {
+        os_sort_link_init(&mut os_percpu_get_by_id(cpuid as u32).task_sort_link);
+    }
+}
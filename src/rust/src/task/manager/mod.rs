@@ -1,6 +1,10 @@
+pub mod affinity;
 pub mod create;
 pub mod delay;
 pub mod delete;
+pub mod exit;
 pub mod init;
 pub mod priority;
 pub mod suspend;
+#[cfg(feature = "time_slice")]
+pub mod timeslice;
@@ -2,6 +2,7 @@ use crate::{
     config::TASK_PRIORITY_LOWEST,
     ffi::bindings::get_current_task,
     interrupt::{disable_interrupts, restore_interrupt_state},
+    mutex::priority::PriorityInheritance,
     result::{SystemError, SystemResult},
     task::{
         error::TaskError,
@@ -74,22 +75,25 @@ pub fn set_task_priority(task_id: u32, priority: u16) -> SystemResult<()> {
     // 标记是否需要重新调度
     let needs_reschedule = {
         if temp_status.contains(TaskStatus::READY) {
-            // 从就绪队列中移除任务
-            priority_queue_remove(&mut task_cb.pend_list);
-
-            // 更新优先级
-            task_cb.priority = priority;
-
-            // 将任务重新加入就绪队列
-            priority_queue_insert_at_back(&mut task_cb.pend_list, task_cb.priority as u32);
+            // 更新基础优先级，所持互斥锁带来的提升保持不变
+            PriorityInheritance::set_base_priority(task_cb, priority);
 
             true
         } else if temp_status.contains(TaskStatus::RUNNING) {
-            task_cb.priority = priority;
+            PriorityInheritance::set_base_priority(task_cb, priority);
+
+            // 任务运行在其他核上，由该核重新调度
+            #[cfg(feature = "smp")]
+            if task_cb.curr_cpu as u32 != crate::ffi::bindings::arch_curr_cpuid() {
+                crate::mp::mp_schedule(1 << task_cb.curr_cpu);
+            }
 
             true
+        } else if temp_status.contains(TaskStatus::PEND) {
+            // 重新计算阻塞任务继承的优先级，并沿阻塞链更新互斥锁持有者
+            PriorityInheritance::set_blocked_priority(task_cb, priority)
         } else {
-            task_cb.priority = priority;
+            PriorityInheritance::set_base_priority(task_cb, priority);
 
             false
         }
@@ -13,6 +13,8 @@ use crate::{
         types::{TaskCB, TaskSignal, TaskStatus},
     },
 };
+#[cfg(feature = "smp")]
+use crate::{ffi::bindings::arch_curr_cpuid, mp::mp_schedule};
 
 /// 恢复一个被挂起的任务
 pub fn task_resume(task_id: u32) -> SystemResult<()> {
@@ -74,6 +76,14 @@ pub fn task_resume(task_id: u32) -> SystemResult<()> {
 
 /// 检查是否可以挂起正在运行的任务
 fn can_suspend_running_task(task_cb: &mut TaskCB) -> SystemResult<bool> {
+    // 任务运行在其他核上，由该核在中断退出时挂起
+    #[cfg(feature = "smp")]
+    if task_cb.curr_cpu as u32 != arch_curr_cpuid() {
+        task_cb.signal = TaskSignal::SUSPEND;
+        mp_schedule(1 << task_cb.curr_cpu);
+        return Ok(false);
+    }
+
     // 检查调度器是否可抢占
     if !can_preempt_in_scheduler() {
         // 当前核心的运行任务无法挂起
@@ -0,0 +1,109 @@
+use crate::{
+    config::{KERNEL_TIMESLICE_TIMEOUT, TASK_LIMIT, TASK_PRIORITY_LOWEST, TASK_PRIORITY_NUM},
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        global::get_tcb_from_id,
+        types::{TASK_TIME_SLICE_DEFAULT, TaskCB, TaskStatus},
+    },
+};
+
+/// 各优先级的默认时间片长度
+static mut PRIORITY_TIME_SLICE: [u16; TASK_PRIORITY_NUM] =
+    [KERNEL_TIMESLICE_TIMEOUT; TASK_PRIORITY_NUM];
+
+/// 获取任务当前生效的时间片长度，0表示同优先级内先进先出
+#[inline]
+pub(crate) fn time_slice_len(task_cb: &TaskCB) -> u16 {
+    if task_cb.time_slice_len == TASK_TIME_SLICE_DEFAULT {
+        unsafe { PRIORITY_TIME_SLICE[task_cb.priority as usize] }
+    } else {
+        task_cb.time_slice_len
+    }
+}
+
+/// 设置任务的时间片长度
+///
+/// `ticks`为0时任务在同优先级内先进先出，为[`TASK_TIME_SLICE_DEFAULT`]时使用所在优先级的默认值。
+/// 设置后任务重新开始计算时间片。
+pub fn task_time_slice_set(task_id: u32, ticks: u16) -> SystemResult<()> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    task_cb.time_slice_len = ticks;
+    task_cb.time_slice = time_slice_len(task_cb);
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    Ok(())
+}
+
+/// 获取任务当前生效的时间片长度
+pub fn task_time_slice_get(task_id: u32) -> SystemResult<u16> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    let result = if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        Err(SystemError::Task(TaskError::NotCreated))
+    } else {
+        Ok(time_slice_len(task_cb))
+    };
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    result
+}
+
+/// 设置优先级的默认时间片长度，对使用默认值的任务在其下一次换入时生效
+pub fn priority_time_slice_set(priority: u16, ticks: u16) -> SystemResult<()> {
+    // 检查优先级是否有效
+    if priority > TASK_PRIORITY_LOWEST {
+        return Err(SystemError::Task(TaskError::PriorityError));
+    }
+
+    // 默认值本身不能再引用默认值
+    if ticks == TASK_TIME_SLICE_DEFAULT {
+        return Err(SystemError::Task(TaskError::TimeSliceError));
+    }
+
+    let int_save = disable_interrupts();
+    unsafe { PRIORITY_TIME_SLICE[priority as usize] = ticks };
+    restore_interrupt_state(int_save);
+
+    Ok(())
+}
+
+/// 获取优先级的默认时间片长度
+pub fn priority_time_slice_get(priority: u16) -> SystemResult<u16> {
+    // 检查优先级是否有效
+    if priority > TASK_PRIORITY_LOWEST {
+        return Err(SystemError::Task(TaskError::PriorityError));
+    }
+
+    Ok(unsafe { PRIORITY_TIME_SLICE[priority as usize] })
+}
@@ -1,3 +1,6 @@
+pub mod builder;
+#[cfg(feature = "edf")]
+pub mod edf;
 pub mod entry;
 pub mod error;
 pub mod global;
@@ -6,8 +9,12 @@ pub mod info;
 pub mod manager;
 #[cfg(feature = "task_monitor")]
 pub mod monitor;
+pub mod notify;
+pub mod resource;
 pub mod sched;
 pub mod signal;
 pub mod sync;
 pub mod timer;
 pub mod types;
+#[cfg(feature = "task_signal")]
+pub mod user_signal;
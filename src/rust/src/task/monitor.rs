@@ -1,4 +1,11 @@
-use crate::task::types::TaskCB;
+use crate::{
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        types::{TaskCB, TaskStatus},
+    },
+};
 
 /// 任务切换钩子函数类型
 pub type TaskSwitchHook = Option<extern "C" fn()>;
@@ -6,6 +13,33 @@ pub type TaskSwitchHook = Option<extern "C" fn()>;
 /// 用户定义的任务切换钩子
 static mut USER_TASK_SWITCH_HOOK: TaskSwitchHook = None;
 
+/// 可同时注册的任务切换处理函数数
+pub const TASK_SWITCH_HANDLER_LIMIT: usize = 8;
+
+/// 任务切换的原因
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSwitchReason {
+    /// 被更高优先级的任务抢占或时间片用完
+    Preempt = 0,
+    /// 阻塞、延时或被挂起
+    Block = 1,
+    /// 主动让出CPU
+    Yield = 2,
+    /// 被删除或退出
+    Delete = 3,
+}
+
+/// 任务切换处理函数，参数为换出和换入的任务ID及切换原因
+///
+/// 在关中断并持有调度器锁时调用，不能阻塞。
+pub type TaskSwitchHandler =
+    extern "C" fn(old_task_id: u32, new_task_id: u32, reason: TaskSwitchReason);
+
+/// 已注册的任务切换处理函数
+static mut TASK_SWITCH_HANDLERS: [Option<TaskSwitchHandler>; TASK_SWITCH_HANDLER_LIMIT] =
+    [None; TASK_SWITCH_HANDLER_LIMIT];
+
 #[inline]
 fn stack_magic_check(top_stack: *const usize) -> bool {
     const STACK_MAGIC_WORD: usize = 0xCCCCCCCC;
@@ -44,15 +78,70 @@ pub fn register_task_switch_hook(hook: TaskSwitchHook) {
     unsafe { USER_TASK_SWITCH_HOOK = hook };
 }
 
-/// 执行任务切换检查
-pub fn check_task_switch(old_task: &TaskCB, new_task: &TaskCB) {
+/// 注册任务切换处理函数，同一函数注册多次时每次切换被调用多次
+pub fn task_switch_handler_register(handler: TaskSwitchHandler) -> SystemResult<()> {
+    let int_save = disable_interrupts();
+    let handlers_ptr = &raw mut TASK_SWITCH_HANDLERS;
+    let handlers = unsafe { &mut *handlers_ptr };
+    let result = match handlers.iter_mut().find(|slot| slot.is_none()) {
+        Some(slot) => {
+            *slot = Some(handler);
+            Ok(())
+        }
+        None => Err(SystemError::Task(TaskError::SwitchHandlerFull)),
+    };
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 注销一次任务切换处理函数的注册
+pub fn task_switch_handler_unregister(handler: TaskSwitchHandler) -> SystemResult<()> {
+    let int_save = disable_interrupts();
+    let handlers_ptr = &raw mut TASK_SWITCH_HANDLERS;
+    let handlers = unsafe { &mut *handlers_ptr };
+    let result = match handlers
+        .iter_mut()
+        .find(|slot| slot.is_some_and(|registered| core::ptr::fn_addr_eq(registered, handler)))
+    {
+        Some(slot) => {
+            *slot = None;
+            Ok(())
+        }
+        None => Err(SystemError::Task(TaskError::SwitchHandlerNotFound)),
+    };
+    restore_interrupt_state(int_save);
+    result
+}
+
+/// 根据换出任务的状态判断切换原因
+fn switch_reason(old_task: &TaskCB, yielding: bool) -> TaskSwitchReason {
+    let status = old_task.task_status;
+    if status.contains(TaskStatus::UNUSED) {
+        TaskSwitchReason::Delete
+    } else if !status.contains(TaskStatus::READY) {
+        TaskSwitchReason::Block
+    } else if yielding {
+        TaskSwitchReason::Yield
+    } else {
+        TaskSwitchReason::Preempt
+    }
+}
+
+/// 执行任务切换检查，`yielding`表示换出的任务主动让出CPU
+pub fn check_task_switch(old_task: &TaskCB, new_task: &TaskCB, yielding: bool) {
     // 检查任务栈
     check_task_stack(old_task, new_task);
 
     unsafe {
-        match USER_TASK_SWITCH_HOOK {
-            Some(hook) => hook(),
-            None => {}
+        if let Some(hook) = USER_TASK_SWITCH_HOOK {
+            hook()
         }
     }
+
+    let reason = switch_reason(old_task, yielding);
+    let handlers_ptr = &raw const TASK_SWITCH_HANDLERS;
+    let handlers = unsafe { &*handlers_ptr };
+    for handler in handlers.iter().flatten() {
+        handler(old_task.task_id, new_task.task_id, reason);
+    }
 }
@@ -0,0 +1,177 @@
+//! 任务通知，用于一对一的轻量级同步
+//!
+//! 每个任务有一个32位的通知值，其他任务或中断向其发送通知，任务自身等待并取走通知。
+
+use crate::{
+    config::TASK_LIMIT,
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    percpu::can_preempt_in_scheduler,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        global::get_tcb_from_id,
+        sched::{schedule, schedule_reschedule},
+        sync::wait::{task_wait, task_wait_interrupted, task_wake},
+        types::{TaskPendObject, TaskStatus},
+    },
+};
+
+/// 发送任务通知时对通知值的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum NotifyAction {
+    /// 将参数按位或到通知值上
+    SetBits = 0,
+    /// 通知值加一，忽略参数，可作为轻量级计数信号量
+    Increment = 1,
+    /// 用参数覆盖通知值
+    Overwrite = 2,
+    /// 没有未取走的通知时才用参数覆盖通知值
+    SetIfEmpty = 3,
+}
+
+impl TryFrom<u32> for NotifyAction {
+    type Error = SystemError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::SetBits),
+            1 => Ok(Self::Increment),
+            2 => Ok(Self::Overwrite),
+            3 => Ok(Self::SetIfEmpty),
+            _ => Err(SystemError::Task(TaskError::NotifyActionInvalid)),
+        }
+    }
+}
+
+/// 向任务发送通知，可在中断中调用
+///
+/// `SetIfEmpty`在已有未取走的通知时返回`NotifyPending`，通知值保持不变。
+pub fn task_notify(task_id: u32, value: u32, action: NotifyAction) -> SystemResult<()> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    match action {
+        NotifyAction::SetBits => task_cb.notify_value |= value,
+        NotifyAction::Increment => task_cb.notify_value = task_cb.notify_value.wrapping_add(1),
+        NotifyAction::Overwrite => task_cb.notify_value = value,
+        NotifyAction::SetIfEmpty => {
+            if task_cb.notify_pending {
+                restore_interrupt_state(int_save);
+                return Err(SystemError::Task(TaskError::NotifyPending));
+            }
+            task_cb.notify_value = value;
+        }
+    }
+    task_cb.notify_pending = true;
+
+    // 唤醒正在等待通知的任务
+    let woken = task_cb.task_status.contains(TaskStatus::PEND)
+        && task_cb.pend_object == TaskPendObject::Notify;
+    if woken {
+        task_wake(task_cb);
+    }
+
+    restore_interrupt_state(int_save);
+
+    if woken {
+        schedule();
+    }
+    Ok(())
+}
+
+/// 等待并取走当前任务的通知，返回取走前的通知值
+///
+/// `clear`为真时通知值清零；否则通知值减一，减到0前通知一直保持未取走，
+/// 配合`Increment`使用时相当于计数信号量。
+pub fn task_notify_take(clear: bool, timeout: u32) -> SystemResult<u32> {
+    // 检查是否在中断上下文
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::NotifyInInterrupt));
+    }
+
+    let run_task = get_current_task();
+
+    let mut int_save = disable_interrupts();
+
+    if !run_task.notify_pending {
+        if timeout == 0 {
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::NotifyTimeout));
+        }
+        if !can_preempt_in_scheduler() {
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::NotifyInLock));
+        }
+
+        task_wait(&mut run_task.notify_list, TaskPendObject::Notify, timeout);
+
+        // 立即调度
+        schedule_reschedule();
+
+        // 解锁并重新加锁
+        restore_interrupt_state(int_save);
+        int_save = disable_interrupts();
+
+        if run_task.task_status.contains(TaskStatus::TIMEOUT) {
+            run_task.task_status.remove(TaskStatus::TIMEOUT);
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::NotifyTimeout));
+        }
+        if task_wait_interrupted(run_task) {
+            restore_interrupt_state(int_save);
+            return Err(SystemError::Task(TaskError::Interrupted));
+        }
+    }
+
+    let value = run_task.notify_value;
+    if clear {
+        run_task.notify_value = 0;
+    } else {
+        run_task.notify_value = value.saturating_sub(1);
+    }
+    run_task.notify_pending = run_task.notify_value != 0;
+
+    restore_interrupt_state(int_save);
+    Ok(value)
+}
+
+/// 丢弃任务未取走的通知，返回丢弃前的通知值
+pub fn task_notify_clear(task_id: u32) -> SystemResult<u32> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    let value = task_cb.notify_value;
+    task_cb.notify_value = 0;
+    task_cb.notify_pending = false;
+
+    restore_interrupt_state(int_save);
+    Ok(value)
+}
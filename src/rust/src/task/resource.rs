@@ -0,0 +1,139 @@
+//! 任务删除时的资源回收
+//!
+//! 互斥锁记录当前持有者，信号量、队列、软件定时器和堆内存块记录创建它们的任务。任务被删除时按
+//! 每个对象的[`OwnerDeadPolicy`]处理这些对象，任务持有的读写锁则总是被释放。只有互斥锁默认为[`OwnerDeadPolicy::Release`]；其余对象
+//! 常在创建后交给其他任务使用，默认为[`OwnerDeadPolicy::Orphan`]，需要随创建者回收时通过各自的
+//! `*_owner_policy_set`显式设置。回收在删除任务的临界区内完成，回收记录则留给空闲任务在回收任务栈时输出，避免在临界区内打印。
+
+use crate::{
+    ffi::bindings::get_current_task,
+    interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
+    memory::mem_owner_dead,
+    mutex::core::mutex_owner_dead,
+    println_info,
+    queue::management::queue_owner_dead,
+    result::SystemError,
+    rwlock::core::rwlock_owner_dead,
+    semaphore::core::semaphore_owner_dead,
+    task::{error::TaskError, global::is_scheduler_active, types::TaskCB},
+    timer::timer_owner_dead,
+};
+use heapless::Deque;
+
+/// 对象不属于任何任务
+pub const OWNER_NONE: u32 = u32::MAX;
+
+/// 任务被删除时对其拥有的对象的处理策略
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerDeadPolicy {
+    /// 保留对象，只清除其拥有者
+    Orphan = 0,
+    /// 解除任务对对象的持有并唤醒等待者，等待者返回[`TaskError::OwnerDead`]
+    Release = 1,
+    /// 删除对象，等待者返回[`TaskError::OwnerDead`]
+    Destroy = 2,
+}
+
+impl TryFrom<u32> for OwnerDeadPolicy {
+    type Error = SystemError;
+
+    fn try_from(policy: u32) -> Result<Self, Self::Error> {
+        match policy {
+            0 => Ok(Self::Orphan),
+            1 => Ok(Self::Release),
+            2 => Ok(Self::Destroy),
+            _ => Err(SystemError::Task(TaskError::OwnerPolicyInvalid)),
+        }
+    }
+}
+
+/// 一次任务删除回收的对象数量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskReclaimReport {
+    /// 被删除的任务ID
+    pub task_id: u32,
+    /// 被释放或删除的互斥锁数量
+    pub mutexes: u16,
+    /// 被释放的读写锁数量
+    pub rwlocks: u16,
+    /// 被释放或删除的信号量数量
+    pub semaphores: u16,
+    /// 被释放或删除的队列数量
+    pub queues: u16,
+    /// 被停止或删除的软件定时器数量
+    pub timers: u16,
+    /// 被释放的堆内存块数量
+    pub mem_blocks: u16,
+}
+
+impl TaskReclaimReport {
+    fn is_empty(&self) -> bool {
+        self.mutexes == 0
+            && self.rwlocks == 0
+            && self.semaphores == 0
+            && self.queues == 0
+            && self.timers == 0
+            && self.mem_blocks == 0
+    }
+}
+
+/// 待输出的回收记录数上限，超出的记录被丢弃
+const RECLAIM_LOG_LIMIT: usize = 8;
+
+static mut RECLAIM_LOG: Deque<TaskReclaimReport, RECLAIM_LOG_LIMIT> = Deque::new();
+
+/// 获取创建对象时记录的拥有者，中断中或调度启动前创建的对象不属于任何任务
+pub(crate) fn current_owner() -> u32 {
+    if is_interrupt_active() || !is_scheduler_active() {
+        return OWNER_NONE;
+    }
+    get_current_task().task_id
+}
+
+/// 按各对象的策略回收任务拥有的对象，须在关中断后由删除流程调用，返回是否唤醒了任务
+pub(crate) fn task_resource_reclaim(task_cb: &mut TaskCB) -> bool {
+    let mut report = TaskReclaimReport {
+        task_id: task_cb.task_id,
+        ..Default::default()
+    };
+    let mut woken = false;
+
+    woken |= mutex_owner_dead(task_cb, &mut report.mutexes);
+    woken |= rwlock_owner_dead(task_cb, &mut report.rwlocks);
+    woken |= semaphore_owner_dead(task_cb.task_id, &mut report.semaphores);
+    woken |= queue_owner_dead(task_cb.task_id, &mut report.queues);
+    woken |= timer_owner_dead(task_cb.task_id, &mut report.timers);
+    mem_owner_dead(task_cb, &mut report.mem_blocks);
+
+    if !report.is_empty() {
+        let log = &raw mut RECLAIM_LOG;
+        let _ = unsafe { (*log).push_back(report) };
+    }
+    woken
+}
+
+/// 取出最早的一条回收记录
+pub fn task_reclaim_report_pop() -> Option<TaskReclaimReport> {
+    let int_save = disable_interrupts();
+    let log = &raw mut RECLAIM_LOG;
+    let report = unsafe { (*log).pop_front() };
+    restore_interrupt_state(int_save);
+    report
+}
+
+/// 输出并清空回收记录，由空闲任务调用
+pub(crate) fn task_reclaim_log_flush() {
+    while let Some(report) = task_reclaim_report_pop() {
+        println_info!(
+            "task {} reclaimed: {} mutexes, {} rwlocks, {} semaphores, {} queues, {} timers, {} mem blocks",
+            report.task_id,
+            report.mutexes,
+            report.rwlocks,
+            report.semaphores,
+            report.queues,
+            report.timers,
+            report.mem_blocks
+        );
+    }
+}
@@ -1,59 +1,196 @@
+#[cfg(feature = "cpup")]
+use crate::cpup::{cpup_task_start, cpup_task_switch};
+#[cfg(feature = "edf")]
+use crate::task::edf::is_edf_active;
+#[cfg(feature = "time_slice")]
+use crate::task::manager::timeslice::time_slice_len;
 #[cfg(feature = "task_monitor")]
 use crate::task::monitor::check_task_switch;
 use crate::{
-    ffi::bindings::{arch_int_locked, curr_task_set, get_current_task, os_task_schedule},
+    config::{KERNEL_CORE_NUM, TASK_PRIORITY_NUM},
+    ffi::bindings::{
+        arch_curr_cpuid, arch_int_locked, curr_task_set, get_current_task, os_task_schedule,
+    },
     interrupt::{disable_interrupts, is_interrupt_active, restore_interrupt_state},
-    percpu::{can_preempt, can_preempt_in_scheduler, os_percpu_get},
-    task::types::{TaskCB, TaskStatus},
-    utils::list::LinkedList,
+    percpu::{can_preempt, can_preempt_in_scheduler, os_percpu_get, set_cpu_online},
+    task::{
+        global::TASK_SCHEDULED,
+        types::{TaskCB, TaskStatus},
+    },
+    utils::{bitmap::PriorityBitmap, list::LinkedList},
+};
+#[cfg(feature = "smp")]
+use crate::{
+    mp::mp_schedule,
+    percpu::{cpu_online_mask, os_percpu_get_by_id},
+    spinlock::{kernel_lock_depth, kernel_lock_depth_set},
 };
-use core::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "smp")]
+use core::sync::atomic::AtomicU32;
+use core::sync::atomic::Ordering;
+
+/// 每个CPU核的优先级就绪队列
+static mut PRI_QUEUE_LIST: [[LinkedList; TASK_PRIORITY_NUM]; KERNEL_CORE_NUM] =
+    [[LinkedList::UNINIT; TASK_PRIORITY_NUM]; KERNEL_CORE_NUM];
 
-const OS_PRIORITY_QUEUE_NUM: usize = 32;
-const PRIQUEUE_PRIOR0_BIT: u32 = 0x8000_0000;
+/// 每个CPU核就绪队列的非空优先级位图
+static mut PRI_QUEUE_BITMAP: [PriorityBitmap; KERNEL_CORE_NUM] =
+    [PriorityBitmap::new(); KERNEL_CORE_NUM];
 
-static mut PRI_QUEUE_LIST: [LinkedList; OS_PRIORITY_QUEUE_NUM] =
-    [LinkedList::UNINIT; OS_PRIORITY_QUEUE_NUM];
+/// 每个CPU核的EDF就绪队列，按绝对截止时间排序
+#[cfg(feature = "edf")]
+static mut EDF_QUEUE_LIST: [LinkedList; KERNEL_CORE_NUM] = [LinkedList::UNINIT; KERNEL_CORE_NUM];
 
-static PRI_QUEUE_BITMAP: AtomicU32 = AtomicU32::new(0);
+/// 每个CPU核就绪队列中的任务数
+#[cfg(feature = "smp")]
+static PRI_QUEUE_SIZE: [AtomicU32; KERNEL_CORE_NUM] =
+    [const { AtomicU32::new(0) }; KERNEL_CORE_NUM];
 
 /// 初始化优先级队列
 pub fn init_priority_queue() {
-    for priority in 0..OS_PRIORITY_QUEUE_NUM {
+    let lists = &raw mut PRI_QUEUE_LIST as *mut LinkedList;
+    for index in 0..KERNEL_CORE_NUM * TASK_PRIORITY_NUM {
         unsafe {
-            LinkedList::init(&mut PRI_QUEUE_LIST[priority]);
+            LinkedList::init(lists.add(index));
+        }
+    }
+    #[cfg(feature = "edf")]
+    {
+        let lists = &raw mut EDF_QUEUE_LIST as *mut LinkedList;
+        for index in 0..KERNEL_CORE_NUM {
+            unsafe {
+                LinkedList::init(lists.add(index));
+            }
         }
     }
 }
 
-/// 将任务节点插入优先级队列头部
-pub fn priority_queue_insert_at_front(priqueue_item: &mut LinkedList, priority: u32) {
-    assert!(priqueue_item.next.is_null(), "节点next指针必须为null");
+/// 获取指定CPU核的就绪队列位图
+#[inline]
+fn priority_queue_bitmap(cpuid: usize) -> &'static mut PriorityBitmap {
     unsafe {
-        // 如果该优先级队列为空，则在位图中设置对应位
-        if LinkedList::is_empty(&mut PRI_QUEUE_LIST[priority as usize]) {
-            PRI_QUEUE_BITMAP.fetch_or(PRIQUEUE_PRIOR0_BIT >> priority, Ordering::Release);
+        &mut *(&raw mut PRI_QUEUE_BITMAP)
+            .cast::<PriorityBitmap>()
+            .add(cpuid)
+    }
+}
+
+/// 获取指定CPU核就绪队列中的最高优先级，队列为空时返回`TASK_PRIORITY_NUM`
+#[inline]
+fn priority_queue_top_priority(cpuid: usize) -> u32 {
+    priority_queue_bitmap(cpuid)
+        .first()
+        .map_or(TASK_PRIORITY_NUM as u32, u32::from)
+}
+
+/// 将EDF任务插入其所在核的EDF队列，截止时间相同时`at_front`决定排在前面还是后面
+#[cfg(feature = "edf")]
+fn edf_queue_insert(task_cb: &mut TaskCB, at_front: bool) {
+    // EDF任务不在核间均衡，留在原核或亲和性掩码中的第一个在线核
+    #[cfg(feature = "smp")]
+    let cpuid = {
+        let online = cpu_online_mask() & task_cb.cpu_affi_mask as u32;
+        let mut cpuid = task_cb.curr_cpu as usize;
+        if online != 0 && online & (1 << cpuid) == 0 {
+            cpuid = online.trailing_zeros() as usize;
+        }
+        task_cb.curr_cpu = cpuid as u16;
+        cpuid
+    };
+    #[cfg(not(feature = "smp"))]
+    let cpuid = 0;
+
+    let deadline = task_cb.edf.job_deadline;
+    unsafe {
+        let list_head = &raw mut EDF_QUEUE_LIST[cpuid];
+        let mut current = (*list_head).next;
+        while current != list_head {
+            let other = TaskCB::from_pend_list(current).edf.job_deadline;
+            if other > deadline || (at_front && other == deadline) {
+                break;
+            }
+            current = (*current).next;
         }
+        // 插入到第一个截止时间更晚的任务之前
+        LinkedList::tail_insert(current, &mut task_cb.pend_list);
+    }
+    task_cb.edf.queued = true;
 
-        // 将节点插入到优先级队列的头部
-        LinkedList::head_insert(&mut PRI_QUEUE_LIST[priority as usize], priqueue_item);
+    #[cfg(feature = "smp")]
+    {
+        PRI_QUEUE_SIZE[cpuid].fetch_add(1, Ordering::Relaxed);
+        // 截止时间早于其他在线核上运行的任务时，通知其调度
+        if cpuid as u32 != arch_curr_cpuid() && cpu_online_mask() & (1 << cpuid) != 0 {
+            let run_task = unsafe { &*os_percpu_get_by_id(cpuid as u32).run_task };
+            if !is_edf_active(run_task) || deadline < run_task.edf.job_deadline {
+                mp_schedule(1 << cpuid);
+            }
+        }
     }
 }
 
-/// 将任务节点插入优先级队列尾部
-pub fn priority_queue_insert_at_back(priqueue_item: &mut LinkedList, priority: u32) {
+/// 将任务节点插入就绪队列
+fn priority_queue_insert(priqueue_item: &mut LinkedList, priority: u32, at_front: bool) {
     assert!(priqueue_item.next.is_null(), "节点next指针必须为null");
+    let task_cb = TaskCB::from_pend_list(priqueue_item);
+
+    // 按截止时间调度的任务进入EDF队列
+    #[cfg(feature = "edf")]
+    if is_edf_active(task_cb) {
+        edf_queue_insert(task_cb, at_front);
+        return;
+    }
+
+    // 选择任务所在的CPU核
+    #[cfg(feature = "smp")]
+    let cpuid = {
+        let cpuid = select_cpu(task_cb);
+        task_cb.curr_cpu = cpuid as u16;
+        cpuid
+    };
+    #[cfg(not(feature = "smp"))]
+    let cpuid = {
+        let _ = task_cb;
+        0
+    };
+
     unsafe {
+        let list_head = &raw mut PRI_QUEUE_LIST[cpuid][priority as usize];
         // 如果该优先级队列为空，则在位图中设置对应位
-        if LinkedList::is_empty(&mut PRI_QUEUE_LIST[priority as usize]) {
-            PRI_QUEUE_BITMAP.fetch_or(PRIQUEUE_PRIOR0_BIT >> priority, Ordering::Release);
+        if LinkedList::is_empty(list_head) {
+            priority_queue_bitmap(cpuid).set(priority as u16);
+        }
+
+        if at_front {
+            LinkedList::head_insert(list_head, priqueue_item);
+        } else {
+            LinkedList::tail_insert(list_head, priqueue_item);
         }
+    }
 
-        // 将节点插入到优先级队列的尾部
-        LinkedList::tail_insert(&mut PRI_QUEUE_LIST[priority as usize], priqueue_item);
+    #[cfg(feature = "smp")]
+    {
+        PRI_QUEUE_SIZE[cpuid].fetch_add(1, Ordering::Relaxed);
+        // 任务进入其他在线核的队列且优先级高于该核的运行任务时，通知其调度
+        if cpuid as u32 != arch_curr_cpuid() && cpu_online_mask() & (1 << cpuid) != 0 {
+            let run_task = unsafe { &*os_percpu_get_by_id(cpuid as u32).run_task };
+            if priority < run_task.priority as u32 {
+                mp_schedule(1 << cpuid);
+            }
+        }
     }
 }
 
+/// 将任务节点插入优先级队列头部
+pub fn priority_queue_insert_at_front(priqueue_item: &mut LinkedList, priority: u32) {
+    priority_queue_insert(priqueue_item, priority, true);
+}
+
+/// 将任务节点插入优先级队列尾部
+pub fn priority_queue_insert_at_back(priqueue_item: &mut LinkedList, priority: u32) {
+    priority_queue_insert(priqueue_item, priority, false);
+}
+
 /// 从优先级队列中移除任务节点
 pub fn priority_queue_remove(priqueue_item: &mut LinkedList) {
     // 从链表中删除节点
@@ -62,23 +199,36 @@ pub fn priority_queue_remove(priqueue_item: &mut LinkedList) {
     // 获取包含此节点的任务控制块
     let run_task = TaskCB::from_pend_list(priqueue_item);
 
+    #[cfg(feature = "smp")]
+    let cpuid = run_task.curr_cpu as usize;
+    #[cfg(not(feature = "smp"))]
+    let cpuid = 0;
+
+    #[cfg(feature = "smp")]
+    PRI_QUEUE_SIZE[cpuid].fetch_sub(1, Ordering::Relaxed);
+
+    // EDF队列没有位图需要维护
+    #[cfg(feature = "edf")]
+    if run_task.edf.queued {
+        run_task.edf.queued = false;
+        return;
+    }
+
     unsafe {
         // 如果该优先级队列为空，原子更新位图
-        if LinkedList::is_empty(&mut PRI_QUEUE_LIST[run_task.priority as usize]) {
-            PRI_QUEUE_BITMAP.fetch_and(
-                !(PRIQUEUE_PRIOR0_BIT >> run_task.priority),
-                Ordering::Release,
-            );
+        if LinkedList::is_empty(&PRI_QUEUE_LIST[cpuid][run_task.priority as usize]) {
+            priority_queue_bitmap(cpuid).clear(run_task.priority);
         }
     }
 }
 
+/// 获取当前核就绪队列中指定优先级的任务数
 pub fn priority_queue_get_size(priority: u16) -> u32 {
     let mut item_count = 0;
     assert!(arch_int_locked());
     unsafe {
         // 获取优先级队列的头节点
-        let list_head = &mut PRI_QUEUE_LIST[priority as usize];
+        let list_head = &mut PRI_QUEUE_LIST[arch_curr_cpuid() as usize][priority as usize];
         // 手动遍历链表
         let mut current = list_head.next;
         while !current.is_null() && current != list_head {
@@ -89,22 +239,134 @@ pub fn priority_queue_get_size(priority: u16) -> u32 {
     item_count
 }
 
+/// 获取CPU核即将运行的任务优先级，即运行任务与就绪队列中的较高者
+#[cfg(feature = "smp")]
+#[inline]
+fn cpu_effective_priority(cpuid: usize) -> u32 {
+    let run_task = unsafe { &*os_percpu_get_by_id(cpuid as u32).run_task };
+    (run_task.priority as u32).min(priority_queue_top_priority(cpuid))
+}
+
+/// 为进入就绪队列的任务选择CPU核
+#[cfg(feature = "smp")]
+fn select_cpu(task_cb: &TaskCB) -> usize {
+    let mut last_cpu = task_cb.curr_cpu as usize;
+    // 亲和性掩码不含原核时，以掩码中的第一个核作为原核
+    if !task_cb.can_run_on(last_cpu) {
+        last_cpu = task_cb.cpu_affi_mask.trailing_zeros() as usize;
+    }
+    let online = cpu_online_mask() & task_cb.cpu_affi_mask as u32;
+    if online == 0 {
+        return last_cpu;
+    }
+
+    let priority = task_cb.priority as u32;
+    // 被抢占的任务仍允许在本核运行时才按抢占处理
+    let preempted =
+        task_cb.task_status.contains(TaskStatus::RUNNING) && last_cpu == task_cb.curr_cpu as usize;
+    // 被抢占的任务若仍是本核最高优先级，留在本核
+    if preempted && priority_queue_top_priority(last_cpu) > priority {
+        return last_cpu;
+    }
+
+    // 从上次所在的核开始遍历亲和性掩码中的在线核，相同条件下优先留在原核
+    let cpus = (0..KERNEL_CORE_NUM)
+        .map(|offset| (last_cpu + offset) % KERNEL_CORE_NUM)
+        .filter(|&cpuid| online & (1 << cpuid) != 0);
+
+    // 优先选择即将运行的任务优先级最低且低于该任务的核
+    let mut target = None;
+    let mut lowest = priority;
+    for cpuid in cpus.clone() {
+        if preempted && cpuid == last_cpu {
+            continue;
+        }
+        let effective = cpu_effective_priority(cpuid);
+        if effective > lowest {
+            lowest = effective;
+            target = Some(cpuid);
+        }
+    }
+    if let Some(cpuid) = target {
+        return cpuid;
+    }
+    if preempted {
+        return last_cpu;
+    }
+
+    // 否则选择就绪任务最少的核
+    cpus.min_by_key(|&cpuid| PRI_QUEUE_SIZE[cpuid].load(Ordering::Relaxed))
+        .unwrap_or(last_cpu)
+}
+
+/// 从其他核的就绪队列中查找可迁移到本核运行的任务
+///
+/// 只考虑优先级高于`limit`且不高于所在核运行任务的任务，
+/// 高于所在核运行任务的任务会由所在核自行调度。
+#[cfg(feature = "smp")]
+fn priority_queue_find_steal(cpuid: usize, limit: u32) -> Option<&'static mut TaskCB> {
+    let online = cpu_online_mask();
+    let mut best: Option<&'static mut TaskCB> = None;
+    let mut best_priority = limit;
+    for other in (0..KERNEL_CORE_NUM).filter(|&other| other != cpuid && online & (1 << other) != 0)
+    {
+        let run_task = unsafe { &*os_percpu_get_by_id(other as u32).run_task };
+        let bitmap = priority_queue_bitmap(other);
+        let upper = best_priority;
+        'search: for priority in (run_task.priority as u32)..upper {
+            if !bitmap.contains(priority as u16) {
+                continue;
+            }
+            let list_head = unsafe { &raw mut PRI_QUEUE_LIST[other][priority as usize] };
+            let mut current = unsafe { (*list_head).next };
+            while current != list_head {
+                let task_cb = TaskCB::from_pend_list(current);
+                if task_cb.can_run_on(cpuid) {
+                    best_priority = priority;
+                    best = Some(task_cb);
+                    break 'search;
+                }
+                current = unsafe { (*current).next };
+            }
+        }
+    }
+    best
+}
+
 /// 获取优先级队列中优先级最高的任务
 #[unsafe(export_name = "OsGetTopTask")]
 pub extern "C" fn priority_queue_get_top_task() -> *mut TaskCB {
-    // 原子读取优先级位图
-    let bitmap = PRI_QUEUE_BITMAP.load(Ordering::Acquire);
-    let mut top_task: *mut TaskCB = core::ptr::null_mut();
+    let cpuid = arch_curr_cpuid() as usize;
 
-    if bitmap != 0 {
-        // 计算最高优先级（前导零的数量）
-        let priority = bitmap.leading_zeros();
+    // EDF任务先于所有普通优先级的任务运行
+    #[cfg(feature = "edf")]
+    unsafe {
+        let list_head = &raw mut EDF_QUEUE_LIST[cpuid];
+        if !LinkedList::is_empty(list_head) {
+            let first_node = (*list_head).next;
+            priority_queue_remove(&mut *first_node);
+            return TaskCB::from_pend_list(first_node);
+        }
+    }
+
+    // 由两级位图查找最高优先级
+    let priority = priority_queue_top_priority(cpuid);
+
+    // 其他核上等待的任务优先级更高时，迁移到本核运行
+    #[cfg(feature = "smp")]
+    if let Some(task_cb) = priority_queue_find_steal(cpuid, priority) {
+        priority_queue_remove(&mut task_cb.pend_list);
+        return task_cb;
+    }
+
+    let mut top_task: *mut TaskCB = core::ptr::null_mut();
+    if (priority as usize) < TASK_PRIORITY_NUM {
         unsafe {
             // 获取该优先级队列的第一个任务节点
-            let list_head = &mut PRI_QUEUE_LIST[priority as usize];
+            let list_head = &mut PRI_QUEUE_LIST[cpuid][priority as usize];
             let first_node = list_head.next;
             // 通过pendList获取任务控制块
-            top_task = TaskCB::from_pend_list(&mut *first_node);
+            top_task = TaskCB::from_pend_list(&*first_node);
             // 从队列中移除该任务
             priority_queue_remove(&mut *first_node);
         }
@@ -113,8 +375,44 @@ pub extern "C" fn priority_queue_get_top_task() -> *mut TaskCB {
     top_task
 }
 
+/// 设置当前核上运行的任务
+#[inline]
+fn set_running_task(new_task: &mut TaskCB) {
+    #[cfg(feature = "smp")]
+    {
+        new_task.curr_cpu = arch_curr_cpuid() as u16;
+        os_percpu_get().run_task = new_task;
+    }
+    curr_task_set(new_task);
+}
+
+/// 启动当前核的调度，返回第一个运行的任务
+///
+/// 须在关中断后调用，之后由体系结构代码切换到返回的任务
+pub fn schedule_start() -> &'static mut TaskCB {
+    assert!(arch_int_locked());
+    let new_task = unsafe { &mut *priority_queue_get_top_task() };
+    new_task.task_status.remove(TaskStatus::READY);
+    new_task.task_status.insert(TaskStatus::RUNNING);
+    #[cfg(feature = "cpup")]
+    cpup_task_start(new_task);
+    set_running_task(new_task);
+    set_cpu_online();
+    TASK_SCHEDULED.fetch_or(1 << arch_curr_cpuid(), Ordering::AcqRel);
+    new_task
+}
+
 /// 任务重新调度函数
 pub fn schedule_reschedule() {
+    reschedule(false);
+}
+
+/// 当前任务主动让出CPU时的重新调度函数
+pub(crate) fn schedule_yield() {
+    reschedule(true);
+}
+
+fn reschedule(yielding: bool) {
     assert!(arch_int_locked());
 
     // 检查是否可以进行调度
@@ -131,7 +429,7 @@ pub fn schedule_reschedule() {
         assert!(!new_task.is_null(), "无法获取就绪任务");
 
         (*new_task).task_status.remove(TaskStatus::READY);
-        if run_task as *mut TaskCB == new_task {
+        if core::ptr::eq(run_task, new_task) {
             return;
         }
 
@@ -140,18 +438,31 @@ pub fn schedule_reschedule() {
         (*new_task).task_status.insert(TaskStatus::RUNNING);
 
         #[cfg(feature = "task_monitor")]
-        check_task_switch(run_task, &mut *new_task);
+        check_task_switch(run_task, &*new_task, yielding);
+        #[cfg(not(feature = "task_monitor"))]
+        let _ = yielding;
+
+        #[cfg(feature = "cpup")]
+        cpup_task_switch(run_task, &mut *new_task);
 
         #[cfg(feature = "time_slice")]
         if (*new_task).time_slice == 0 {
-            (*new_task).time_slice = crate::config::KERNEL_TIMESLICE_TIMEOUT;
+            (*new_task).time_slice = time_slice_len(&*new_task);
         }
 
         // 设置当前任务
-        curr_task_set(new_task);
+        set_running_task(&mut *new_task);
 
-        // 执行任务上下文切换
+        // 执行任务上下文切换，内核锁由换入的任务继续持有
+        #[cfg(feature = "smp")]
+        let lock_depth = kernel_lock_depth();
         os_task_schedule(new_task, run_task);
+        #[cfg(feature = "smp")]
+        kernel_lock_depth_set(lock_depth);
+
+        // 当前任务被重新换入
+        #[cfg(feature = "task_signal")]
+        crate::task::user_signal::signal_return_mark(run_task);
     }
 }
 
@@ -168,11 +479,14 @@ pub extern "C" fn schedule_preempt() {
     let run_task = get_current_task();
     run_task.task_status.insert(TaskStatus::READY);
 
-    // 根据时间片情况，选择插入队列的方式
+    // 根据时间片情况，选择插入队列的方式，时间片用完的任务排到同优先级队尾并重新获得时间片，
+    // 同优先级没有其他任务时继续运行也能再次轮转
     #[cfg(feature = "time_slice")]
     {
-        if run_task.time_slice == 0 {
+        let len = time_slice_len(run_task);
+        if run_task.time_slice == 0 && len != 0 {
             priority_queue_insert_at_back(&mut run_task.pend_list, run_task.priority as u32);
+            run_task.time_slice = len;
         } else {
             priority_queue_insert_at_front(&mut run_task.pend_list, run_task.priority as u32);
         }
@@ -192,7 +506,7 @@ pub extern "C" fn schedule_preempt() {
 pub fn timeslice_check() {
     // 获取当前运行的任务
     let run_task = get_current_task();
-    // 检查时间片是否需要递减
+    // 检查时间片是否需要递减，先进先出的任务剩余时间片为0
     if run_task.time_slice != 0 {
         run_task.time_slice -= 1;
         if run_task.time_slice == 0 {
@@ -9,7 +9,7 @@ use crate::{
     },
 };
 
-/// 处理当前任务的挂起和删除信号
+/// 中断退出时处理当前任务的挂起、删除和用户信号
 pub fn process_task_signals() -> u32 {
     // 获取当前运行的任务
     let run_task = get_current_task();
@@ -36,6 +36,10 @@ pub fn process_task_signals() -> u32 {
         let _ = task_suspend(run_task.task_id);
     }
 
+    // 用户信号由体系结构返回被中断的任务后递送，这里只做记录
+    #[cfg(feature = "task_signal")]
+    crate::task::user_signal::signal_return_mark(run_task);
+
     // 检查是否需要调度
     check_reschedule_needed()
 }
@@ -29,10 +29,7 @@ pub fn task_unlock() {
         percpu.task_lock_cnt -= 1;
 
         // 如果任务锁计数为0，且有挂起的调度请求，且调度器处于活动状态
-        if percpu.task_lock_cnt == 0
-            && percpu.needs_reschedule == 1
-            && is_scheduler_active()
-        {
+        if percpu.task_lock_cnt == 0 && percpu.needs_reschedule == 1 && is_scheduler_active() {
             // 清除挂起标志
             percpu.needs_reschedule = 0;
 
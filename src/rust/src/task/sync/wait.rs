@@ -3,15 +3,16 @@ use crate::{
     task::{
         sched::priority_queue_insert_at_back,
         timer::{add_to_timer_list, delete_from_timer_list},
-        types::{TaskCB, TaskStatus},
+        types::{TaskCB, TaskPendObject, TaskStatus},
     },
     utils::list::LinkedList,
 };
 
-/// 将当前任务放入等待列表
-pub fn task_wait(list: &mut LinkedList, timeout: u32) {
+/// 将当前任务放入等待列表，`pend_object`记录所等待的对象
+pub fn task_wait(list: &mut LinkedList, pend_object: TaskPendObject, timeout: u32) {
     // 获取当前运行的任务
     let run_task = get_current_task();
+    run_task.pend_object = pend_object;
 
     // 清除就绪状态
     run_task.task_status.remove(TaskStatus::READY);
@@ -29,6 +30,37 @@ pub fn task_wait(list: &mut LinkedList, timeout: u32) {
     }
 }
 
+/// 检查并清除任务的等待被信号打断的标志
+#[inline]
+pub fn task_wait_interrupted(task: &mut TaskCB) -> bool {
+    let interrupted = task.task_status.contains(TaskStatus::INTERRUPTED);
+    task.task_status.remove(TaskStatus::INTERRUPTED);
+    interrupted
+}
+
+/// 检查并清除任务的等待因对象拥有者被删除而结束的标志
+#[inline]
+pub fn task_wait_owner_dead(task: &mut TaskCB) -> bool {
+    let owner_dead = task.task_status.contains(TaskStatus::OWNER_DEAD);
+    task.task_status.remove(TaskStatus::OWNER_DEAD);
+    owner_dead
+}
+
+/// 唤醒等待列表中的所有任务，使其等待以[`TaskError::OwnerDead`]结束，返回是否唤醒了任务
+///
+/// [`TaskError::OwnerDead`]: crate::task::error::TaskError::OwnerDead
+pub fn task_wake_all_owner_dead(list: &mut LinkedList) -> bool {
+    let list = list as *mut LinkedList;
+    let mut woken = false;
+    while !LinkedList::is_empty(list) {
+        let resumed_task = TaskCB::from_pend_list(LinkedList::first(list));
+        task_wake(resumed_task);
+        resumed_task.task_status.insert(TaskStatus::OWNER_DEAD);
+        woken = true;
+    }
+    woken
+}
+
 /// 唤醒等待中的任务
 pub fn task_wake(resumed_task: &mut TaskCB) {
     // 从等待列表中移除
@@ -1,6 +1,8 @@
 use crate::{
     container_of,
-    percpu::os_percpu_get,
+    ffi::bindings::arch_curr_cpuid,
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    percpu::{os_percpu_get, os_percpu_get_by_id},
     task::{
         sched::{priority_queue_insert_at_back, schedule},
         types::{TaskCB, TaskStatus},
@@ -14,22 +16,26 @@ use crate::{
 pub fn add_to_timer_list(task_cb: &mut TaskCB, timeout: u32) {
     // 设置排序链表值
     task_cb.sort_list.set_timeout(timeout);
+    task_cb.sort_list.set_cpuid(arch_curr_cpuid());
     let sort_link_header = &mut os_percpu_get().task_sort_link;
     add_to_sort_link(sort_link_header, &mut task_cb.sort_list);
 }
 
 pub fn delete_from_timer_list(task_cb: &mut TaskCB) {
-    let sort_link_header = &mut os_percpu_get().task_sort_link;
-    delete_from_sort_link(sort_link_header, &mut (*task_cb).sort_list);
+    // 从任务加入时所在核的排序链表中删除
+    let sort_link_header = &mut os_percpu_get_by_id(task_cb.sort_list.get_cpuid()).task_sort_link;
+    delete_from_sort_link(sort_link_header, &mut task_cb.sort_list);
 }
 
 pub fn task_scan() {
     let mut need_schedule = false;
+    let int_save = disable_interrupts();
     // 获取当前CPU的任务排序链表
     let sort_link_header = &mut os_percpu_get().task_sort_link;
     sort_link_header.advance_cursor();
     let list_object = sort_link_header.list_at_cursor();
     if LinkedList::is_empty(list_object) {
+        restore_interrupt_state(int_save);
         return;
     }
     unsafe {
@@ -73,6 +79,8 @@ pub fn task_scan() {
             // 获取下一个元素
             sort_list = container_of!((*list_object).next, SortLinkList, sort_link_node);
         }
+        restore_interrupt_state(int_save);
+
         // 如果有任务超时并就绪，触发调度
         if need_schedule {
             schedule();
@@ -1,14 +1,35 @@
+#[cfg(feature = "cpup")]
+use crate::cpup::TaskCpup;
 use crate::event::types::EventCB;
+#[cfg(feature = "edf")]
+use crate::task::edf::{TaskEdf, TaskEdfParam};
+#[cfg(feature = "task_signal")]
+use crate::task::user_signal::{SIGNAL_NUM, SignalHandler};
 use crate::{
+    config::{RWLOCK_READ_HOLD_LIMIT, TASK_NAME_LEN},
     container_of,
-    utils::{list::LinkedList, sortlink::SortLinkList},
+    rwlock::types::RwlockReadHold,
+    select::types::SelectNode,
+    utils::{bitmap::PriorityBitmap, list::LinkedList, sortlink::SortLinkList},
 };
 use bitflags::bitflags;
+#[cfg(feature = "task_signal")]
+use core::sync::atomic::AtomicU32;
 use core::{
     ffi::{c_char, c_void},
     fmt,
 };
 
+/// 任务属性：可被等待，任务退出后保留控制块直到被[`task_join`]或[`task_detach`]回收
+///
+/// [`task_join`]: crate::task::manager::exit::task_join
+/// [`task_detach`]: crate::task::manager::exit::task_detach
+pub const TASK_ATTR_JOINABLE: u32 = 0x8000_0000;
+
+/// 时间片长度：使用任务所在优先级的默认值
+#[cfg(feature = "time_slice")]
+pub const TASK_TIME_SLICE_DEFAULT: u16 = u16::MAX;
+
 /// 任务入口函数类型
 pub type TaskEntryFunc = Option<extern "C" fn(*mut c_void)>;
 
@@ -30,6 +51,17 @@ pub struct TaskInitParam {
 
     /// 任务名称
     pub name: *const c_char,
+
+    /// 任务属性
+    pub resved: u32,
+
+    /// 时间片长度，0表示同优先级内先进先出
+    #[cfg(feature = "time_slice")]
+    pub time_slice: u16,
+
+    /// EDF调度参数
+    #[cfg(feature = "edf")]
+    pub edf: TaskEdfParam,
 }
 
 impl Default for TaskInitParam {
@@ -40,6 +72,11 @@ impl Default for TaskInitParam {
             args: core::ptr::null_mut(),
             stack_size: 0,
             name: core::ptr::null(),
+            resved: 0,
+            #[cfg(feature = "time_slice")]
+            time_slice: TASK_TIME_SLICE_DEFAULT,
+            #[cfg(feature = "edf")]
+            edf: TaskEdfParam::default(),
         }
     }
 }
@@ -78,12 +115,15 @@ pub struct TaskCB {
     /// 任务参数
     pub args: *mut c_void,
 
-    /// 任务名称
+    /// 任务名称，指向`name_buf`
     pub task_name: *const c_char,
 
     /// 任务挂起节点
     pub pend_list: LinkedList,
 
+    /// 任务等待的对象，仅在`PEND`状态下有效
+    pub pend_object: TaskPendObject,
+
     /// 任务排序链表节点
     pub sort_list: SortLinkList,
 
@@ -96,19 +136,95 @@ pub struct TaskCB {
     /// 事件模式
     pub event_mode: u32,
 
-    /// 优先级位图
-    pub priority_bitmap: u32,
+    /// 事件组等待掩码，被唤醒时改写为满足条件时的事件组值
+    pub event_group_bits: u64,
+
+    /// 优先级继承前的原始优先级位图
+    pub priority_bitmap: PriorityBitmap,
+
+    /// 持有的互斥锁链表
+    pub held_mutexes: LinkedList,
+
+    /// 持有写锁的读写锁链表
+    pub held_rwlocks: LinkedList,
+
+    /// 持有的读锁
+    pub rwlock_reads: [RwlockReadHold; RWLOCK_READ_HOLD_LIMIT],
+
+    /// 多对象等待期间挂在各对象上的等待节点
+    pub select_nodes: *mut SelectNode,
+
+    /// 多对象等待的节点数
+    pub select_count: u32,
 
     /// 任务信号
     pub signal: TaskSignal,
 
+    /// 任务退出码
+    pub exit_code: u32,
+
+    /// 等待该任务退出的任务链表
+    pub join_list: LinkedList,
+
+    /// 任务通知值
+    pub notify_value: u32,
+
+    /// 是否有未取走的任务通知
+    pub notify_pending: bool,
+
+    /// 等待任务通知时任务自身挂入的链表
+    pub notify_list: LinkedList,
+
+    /// 任务退出时要释放的堆内存块链表
+    pub mem_list: LinkedList,
+
     /// 剩余时间片
     #[cfg(feature = "time_slice")]
     pub time_slice: u16,
+
+    /// 时间片长度，0表示同优先级内先进先出
+    #[cfg(feature = "time_slice")]
+    pub time_slice_len: u16,
+
+    /// CPU占用率统计
+    #[cfg(feature = "cpup")]
+    pub cpup: TaskCpup,
+
+    /// EDF调度状态
+    #[cfg(feature = "edf")]
+    pub edf: TaskEdf,
+
+    /// 已挂起的用户信号
+    #[cfg(feature = "task_signal")]
+    pub sig_pending: AtomicU32,
+
+    /// 被屏蔽的用户信号
+    #[cfg(feature = "task_signal")]
+    pub sig_blocked: AtomicU32,
+
+    /// 用户信号处理函数
+    #[cfg(feature = "task_signal")]
+    pub sig_handlers: [SignalHandler; SIGNAL_NUM as usize],
+
+    /// 返回任务上下文时有待递送的用户信号，由体系结构的返回入口递送
+    #[cfg(feature = "task_signal")]
+    pub sig_return_pending: bool,
+
+    /// 任务所在的CPU核，就绪时为所在就绪队列的核，运行时为运行的核
+    #[cfg(feature = "smp")]
+    pub curr_cpu: u16,
+
+    /// CPU亲和性掩码，任务只在掩码中的核上运行
+    #[cfg(feature = "smp")]
+    pub cpu_affi_mask: u16,
+
+    /// 创建时复制的任务名称，超长部分被截断
+    pub name_buf: [u8; TASK_NAME_LEN],
 }
 
 // event::types::EventCB,
 impl TaskCB {
+    #[cfg_attr(feature = "task_signal", allow(clippy::declare_interior_mutable_const))]
     pub const UNINIT: Self = Self {
         stack_pointer: core::ptr::null_mut(),
         task_status: TaskStatus::UNUSED,
@@ -122,14 +238,46 @@ impl TaskCB {
         args: core::ptr::null_mut(),
         task_name: core::ptr::null(),
         pend_list: LinkedList::UNINIT,
+        pend_object: TaskPendObject::None,
         sort_list: SortLinkList::UNINIT,
         event: EventCB::new(),
         event_mask: 0,
         event_mode: 0,
-        priority_bitmap: 0,
+        event_group_bits: 0,
+        priority_bitmap: PriorityBitmap::new(),
+        held_mutexes: LinkedList::UNINIT,
+        held_rwlocks: LinkedList::UNINIT,
+        rwlock_reads: [RwlockReadHold::UNINIT; RWLOCK_READ_HOLD_LIMIT],
+        select_nodes: core::ptr::null_mut(),
+        select_count: 0,
         signal: TaskSignal::empty(),
+        exit_code: 0,
+        join_list: LinkedList::UNINIT,
+        notify_value: 0,
+        notify_pending: false,
+        notify_list: LinkedList::UNINIT,
+        mem_list: LinkedList::UNINIT,
         #[cfg(feature = "time_slice")]
         time_slice: 0,
+        #[cfg(feature = "time_slice")]
+        time_slice_len: TASK_TIME_SLICE_DEFAULT,
+        #[cfg(feature = "cpup")]
+        cpup: TaskCpup::UNINIT,
+        #[cfg(feature = "edf")]
+        edf: TaskEdf::UNINIT,
+        #[cfg(feature = "task_signal")]
+        sig_pending: AtomicU32::new(0),
+        #[cfg(feature = "task_signal")]
+        sig_blocked: AtomicU32::new(0),
+        #[cfg(feature = "task_signal")]
+        sig_handlers: [None; SIGNAL_NUM as usize],
+        #[cfg(feature = "task_signal")]
+        sig_return_pending: false,
+        #[cfg(feature = "smp")]
+        curr_cpu: 0,
+        #[cfg(feature = "smp")]
+        cpu_affi_mask: 0,
+        name_buf: [0; TASK_NAME_LEN],
     };
 
     pub fn name(&self) -> &str {
@@ -161,6 +309,24 @@ impl TaskCB {
         self.task_flags.insert(TaskFlags::SYSTEM);
     }
 
+    #[inline]
+    pub fn is_joinable(&self) -> bool {
+        self.task_flags.contains(TaskFlags::JOINABLE)
+    }
+
+    /// 任务是否已退出且控制块尚未回收
+    #[inline]
+    pub fn is_exited(&self) -> bool {
+        self.task_status.contains(TaskStatus::EXIT)
+    }
+
+    /// 任务是否可以在指定的CPU核上运行
+    #[cfg(feature = "smp")]
+    #[inline]
+    pub fn can_run_on(&self, cpuid: usize) -> bool {
+        self.cpu_affi_mask & (1 << cpuid) != 0
+    }
+
     #[inline]
     pub fn is_unused(&self) -> bool {
         self.task_status == TaskStatus::UNUSED
@@ -218,6 +384,38 @@ impl fmt::Display for TaskCB {
     }
 }
 
+/// 任务等待的对象
+#[repr(C, u32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TaskPendObject {
+    /// 未在等待
+    None,
+    /// 信号量ID
+    Semaphore(u32),
+    /// 互斥锁ID
+    Mutex(u32),
+    /// 队列ID
+    Queue(u32),
+    /// 事件控制块地址
+    Event(usize),
+    /// 事件组控制块地址
+    EventGroup(usize),
+    /// 等待退出的任务ID
+    Join(u32),
+    /// 等待任务通知
+    Notify,
+    /// 读写锁ID
+    Rwlock(u32),
+    /// 条件变量ID
+    Condvar(u32),
+    /// 屏障ID
+    Barrier(u32),
+    /// 倒计数锁存器ID
+    Latch(u32),
+    /// 同时等待多个对象
+    Select,
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
     #[repr(transparent)]
@@ -230,6 +428,9 @@ bitflags! {
         const DELAY = 0x0020;     // 任务延时
         const TIMEOUT = 0x0040;   // 等待事件超时
         const PEND_TIME = 0x0080; // 任务等待特定时间
+        const EXIT = 0x0100;      // 任务已退出，等待回收
+        const INTERRUPTED = 0x0200; // 等待被信号打断
+        const OWNER_DEAD = 0x0400;  // 等待对象的拥有者被删除
 
         /// 任务阻塞状态掩码
         const BLOCKED = Self::DELAY.bits() | Self::PEND.bits() | Self::SUSPEND.bits();
@@ -242,6 +443,10 @@ bitflags! {
     pub struct TaskFlags: u16 {
         /// 系统级任务标志
         const SYSTEM = 0x0002;
+        /// 可被等待的任务标志
+        const JOINABLE = 0x0004;
+        /// 已有任务等待该任务退出
+        const JOINED = 0x0008;
     }
 }
 
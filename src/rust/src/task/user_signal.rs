@@ -0,0 +1,224 @@
+//! 用户自定义任务信号
+//!
+//! 每个任务有32个信号，各自的处理函数与屏蔽掩码由任务自己设置。目标任务从中断返回或被换入时只记录
+//! 有待递送的信号，由体系结构在回到开中断的任务上下文后调用[`signal_return`]递送，且要求不持有调度锁；
+//! 任务发给自己的信号在发送接口返回前递送。处理期间同一信号被屏蔽。
+//! 未设置处理函数的信号在发送时被丢弃。阻塞在等待队列中的任务收到未屏蔽的信号时以
+//! [`TaskError::Interrupted`]结束等待，延时中的任务不被打断。
+
+use crate::{
+    config::TASK_LIMIT,
+    ffi::bindings::{arch_int_lock, arch_int_locked, arch_int_restore, get_current_task},
+    interrupt::{
+        disable_interrupts, global::irq_nesting_count_get, is_interrupt_active,
+        restore_interrupt_state,
+    },
+    percpu::os_percpu_get,
+    result::{SystemError, SystemResult},
+    task::{
+        error::TaskError,
+        global::{get_tcb_from_id, is_scheduler_active},
+        sched::schedule,
+        sync::wait::task_wake,
+        types::{TaskCB, TaskStatus},
+    },
+};
+use core::sync::atomic::Ordering;
+
+/// 每个任务的信号数
+pub const SIGNAL_NUM: u32 = 32;
+
+/// 信号处理函数，参数为信号编号
+pub type SignalHandler = Option<extern "C" fn(u32)>;
+
+#[inline]
+fn signal_bit(signo: u32) -> SystemResult<u32> {
+    if signo >= SIGNAL_NUM {
+        return Err(SystemError::Task(TaskError::SignalInvalid));
+    }
+    Ok(1 << signo)
+}
+
+/// 重置任务的信号状态，在创建任务时调用
+pub(crate) fn signal_task_init(task_cb: &mut TaskCB) {
+    task_cb.sig_pending.store(0, Ordering::SeqCst);
+    task_cb.sig_blocked.store(0, Ordering::SeqCst);
+    task_cb.sig_handlers = [None; SIGNAL_NUM as usize];
+    task_cb.sig_return_pending = false;
+}
+
+/// 设置当前任务的信号处理函数，返回原来的处理函数
+///
+/// 处理函数设为`None`时丢弃该信号已挂起的实例。
+pub fn signal_handler_set(signo: u32, handler: SignalHandler) -> SystemResult<SignalHandler> {
+    let bit = signal_bit(signo)?;
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::SignalInInterrupt));
+    }
+
+    let int_save = disable_interrupts();
+    let run_task = get_current_task();
+    let old = core::mem::replace(&mut run_task.sig_handlers[signo as usize], handler);
+    if handler.is_none() {
+        run_task.sig_pending.fetch_and(!bit, Ordering::SeqCst);
+    }
+    restore_interrupt_state(int_save);
+
+    Ok(old)
+}
+
+/// 屏蔽当前任务的一组信号，返回原来的屏蔽掩码
+pub fn signal_block(set: u32) -> SystemResult<u32> {
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::SignalInInterrupt));
+    }
+    Ok(get_current_task()
+        .sig_blocked
+        .fetch_or(set, Ordering::SeqCst))
+}
+
+/// 解除当前任务对一组信号的屏蔽，返回原来的屏蔽掩码
+///
+/// 已挂起的信号在返回前递送。
+pub fn signal_unblock(set: u32) -> SystemResult<u32> {
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::SignalInInterrupt));
+    }
+    let old = get_current_task()
+        .sig_blocked
+        .fetch_and(!set, Ordering::SeqCst);
+    signal_deliver();
+    Ok(old)
+}
+
+/// 获取当前任务已挂起的信号
+pub fn signal_pending() -> SystemResult<u32> {
+    if is_interrupt_active() {
+        return Err(SystemError::Task(TaskError::SignalInInterrupt));
+    }
+    Ok(get_current_task().sig_pending.load(Ordering::SeqCst))
+}
+
+/// 向任务发送信号，可以在中断中调用
+pub fn signal_send(task_id: u32, signo: u32) -> SystemResult<()> {
+    // 检查任务ID是否有效
+    if task_id >= TASK_LIMIT {
+        return Err(SystemError::Task(TaskError::InvalidId));
+    }
+    let bit = signal_bit(signo)?;
+
+    // 获取任务控制块
+    let task_cb = get_tcb_from_id(task_id);
+
+    // 检查是否为系统任务
+    if task_cb.is_system_task() {
+        return Err(SystemError::Task(TaskError::OperateSystemTask));
+    }
+
+    // 锁定调度器
+    let int_save = disable_interrupts();
+
+    // 检查任务是否已创建
+    if task_cb.task_status.contains(TaskStatus::UNUSED) {
+        restore_interrupt_state(int_save);
+        return Err(SystemError::Task(TaskError::NotCreated));
+    }
+
+    // 没有处理函数的信号直接丢弃
+    if task_cb.sig_handlers[signo as usize].is_none() {
+        restore_interrupt_state(int_save);
+        return Ok(());
+    }
+    task_cb.sig_pending.fetch_or(bit, Ordering::SeqCst);
+
+    // 打断任务的等待，使其尽快处理信号
+    let interrupted = task_cb.task_status.contains(TaskStatus::PEND)
+        && task_cb.sig_blocked.load(Ordering::SeqCst) & bit == 0;
+    if interrupted {
+        task_wake(task_cb);
+        task_cb.task_status.insert(TaskStatus::INTERRUPTED);
+    }
+
+    // 解锁调度器
+    restore_interrupt_state(int_save);
+
+    if interrupted {
+        schedule();
+    }
+
+    // 发给自己的信号立即递送
+    if core::ptr::eq(task_cb, get_current_task()) && !is_interrupt_active() {
+        signal_deliver();
+    }
+    Ok(())
+}
+
+/// 任务被换入或从中断返回时调用，记录有待递送的信号，须在关中断后调用
+pub(crate) fn signal_return_mark(task_cb: &mut TaskCB) {
+    let deliverable =
+        task_cb.sig_pending.load(Ordering::SeqCst) & !task_cb.sig_blocked.load(Ordering::SeqCst);
+    if deliverable != 0 {
+        task_cb.sig_return_pending = true;
+    }
+}
+
+/// 信号递送的返回入口
+///
+/// 体系结构从中断或任务切换返回任务上下文、且已开中断时调用，为当前任务递送返回前记录的信号。
+pub fn signal_return() {
+    if !is_scheduler_active() {
+        return;
+    }
+    if get_current_task().sig_return_pending {
+        signal_deliver();
+    }
+}
+
+/// 递送当前任务已挂起且未屏蔽的信号
+///
+/// 只在开中断的任务上下文中递送，中断上下文、调度未启动、关中断或调度被锁定时不递送。
+pub(crate) fn signal_deliver() {
+    if !is_scheduler_active() {
+        return;
+    }
+
+    let run_task = get_current_task();
+    loop {
+        let deliverable = run_task.sig_pending.load(Ordering::SeqCst)
+            & !run_task.sig_blocked.load(Ordering::SeqCst);
+        if deliverable == 0 {
+            run_task.sig_return_pending = false;
+            return;
+        }
+
+        // 此处不能使用会再次恢复中断的内核接口，开中断时返回入口可能被再次调用
+        if arch_int_locked() {
+            run_task.sig_return_pending = true;
+            return;
+        }
+        run_task.sig_return_pending = false;
+        let int_save = arch_int_lock();
+        let deliverable_now = irq_nesting_count_get() == 0 && os_percpu_get().task_lock_cnt == 0;
+        arch_int_restore(int_save);
+        if !deliverable_now {
+            // 留到下一次返回任务上下文时递送
+            run_task.sig_return_pending = true;
+            return;
+        }
+
+        // 按编号从小到大递送
+        let signo = deliverable.trailing_zeros();
+        let bit = 1 << signo;
+        if run_task.sig_pending.fetch_and(!bit, Ordering::SeqCst) & bit == 0 {
+            continue;
+        }
+        let Some(handler) = run_task.sig_handlers[signo as usize] else {
+            continue;
+        };
+
+        // 处理期间屏蔽该信号，结束后恢复原来的屏蔽掩码
+        let old_blocked = run_task.sig_blocked.fetch_or(bit, Ordering::SeqCst);
+        handler(signo);
+        run_task.sig_blocked.store(old_blocked, Ordering::SeqCst);
+    }
+}
@@ -1,17 +1,19 @@
+use crate::{config::KERNEL_CORE_NUM, ffi::bindings::arch_curr_cpuid};
 use core::sync::atomic::{AtomicU64, Ordering};
 
 /// 每个CPU核的系统tick计数器
 #[unsafe(export_name = "g_tickCount")]
-pub static TICK_COUNT: [AtomicU64; 1] = [AtomicU64::new(0)];
+pub static TICK_COUNT: [AtomicU64; KERNEL_CORE_NUM] =
+    [const { AtomicU64::new(0) }; KERNEL_CORE_NUM];
 
-/// 获取tick计数
+/// 获取tick计数，以主核的计数为系统时间
 #[allow(dead_code)]
 pub fn get_current_tick_count() -> u64 {
     TICK_COUNT[0].load(Ordering::Acquire)
 }
 
-/// 增加tick计数
+/// 增加当前核的tick计数
 #[allow(dead_code)]
 pub fn increment_tick_count() {
-    TICK_COUNT[0].fetch_add(1, Ordering::Release);
+    TICK_COUNT[arch_curr_cpuid() as usize].fetch_add(1, Ordering::Release);
 }
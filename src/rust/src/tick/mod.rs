@@ -6,6 +6,8 @@ use crate::{
 };
 use global::increment_tick_count;
 pub mod global;
+#[cfg(feature = "tickless")]
+pub mod tickless;
 
 pub use clock::*;
 pub use convert::*;
@@ -31,6 +33,10 @@ pub fn handle_tick() {
     // 增加当前CPU的tick计数
     increment_tick_count();
 
+    // 记录Tick边界，Tickless休眠从这里起算
+    #[cfg(feature = "tickless")]
+    tickless::tickless_tick_mark();
+
     // 恢复中断状态
     restore_interrupt_state(int_save);
 
@@ -38,6 +44,14 @@ pub fn handle_tick() {
     #[cfg(feature = "time_slice")]
     timeslice_check();
 
+    // CPU占用率采样
+    #[cfg(feature = "cpup")]
+    crate::cpup::cpup_tick();
+
+    // EDF预算与作业释放，须在唤醒延时任务之前释放新的作业
+    #[cfg(feature = "edf")]
+    crate::task::edf::edf_tick();
+
     // 处理任务超时
     task_scan();
 
@@ -100,7 +114,7 @@ pub mod convert {
         let millisec = millisec as u64;
 
         // 向上取整：(delay_ms * ticks_per_sec + ms_per_sec - 1) / ms_per_sec
-        let ticks = (millisec * TICK_PER_SECOND as u64 + MS_PER_SECOND - 1) / MS_PER_SECOND;
+        let ticks = (millisec * TICK_PER_SECOND as u64).div_ceil(MS_PER_SECOND);
 
         // 确保结果在u32范围内
         ticks.min(u32::MAX as u64) as u32
@@ -0,0 +1,124 @@
+//! Tickless低功耗机制
+//!
+//! 空闲任务进入WFI前计算本核最近的任务超时与软件定时器到期时间，把Tick定时器设置为只在该时刻产生中断；
+//! 被任意中断唤醒后先根据实际经过的周期数补偿Tick计数与排序链表，再恢复周期性Tick。
+
+use crate::{
+    config::KERNEL_CORE_NUM,
+    ffi::bindings::{arch_curr_cpuid, hal_clock_get_cycles, hal_clock_tick_timer_reload},
+    interrupt::{disable_interrupts, restore_interrupt_state},
+    percpu::os_percpu_get,
+    tick::{get_cycles_per_tick, global::TICK_COUNT},
+    utils::sortlink::{os_sort_link_get_next_expire_time, os_sort_link_update_expire_time},
+};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+
+/// Tickless是否开启
+static TICKLESS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// 各核本次休眠设置的Tick数，0表示未处于休眠
+static SLEEP_TICKS: [AtomicU32; KERNEL_CORE_NUM] = [const { AtomicU32::new(0) }; KERNEL_CORE_NUM];
+
+/// 各核最近一次Tick中断时的周期计数，休眠时长从该Tick边界起算
+static LAST_TICK_CYCLES: [AtomicU64; KERNEL_CORE_NUM] =
+    [const { AtomicU64::new(0) }; KERNEL_CORE_NUM];
+
+/// 开启Tickless
+pub fn tickless_enable() {
+    TICKLESS_ENABLED.store(true, Ordering::Release);
+}
+
+/// 关闭Tickless，已经开始的休眠在下一次中断时结束
+pub fn tickless_disable() {
+    TICKLESS_ENABLED.store(false, Ordering::Release);
+}
+
+/// Tickless是否开启
+pub fn is_tickless_enabled() -> bool {
+    TICKLESS_ENABLED.load(Ordering::Acquire)
+}
+
+/// Tick中断处理时调用，记录本核的Tick边界
+///
+/// 边界按整Tick推进而不是取中断响应的时刻，中断延迟不会在多次休眠中累积。
+pub(crate) fn tickless_tick_mark() {
+    let cpuid = arch_curr_cpuid() as usize;
+    let cycles_per_tick = get_cycles_per_tick() as u64;
+    let last_tick = LAST_TICK_CYCLES[cpuid].load(Ordering::Relaxed);
+    let ticks = (hal_clock_get_cycles().saturating_sub(last_tick) / cycles_per_tick).max(1);
+    LAST_TICK_CYCLES[cpuid].store(last_tick + ticks * cycles_per_tick, Ordering::Relaxed);
+}
+
+/// 补偿休眠期间经过的Tick，最后一个Tick留给Tick中断处理
+fn sys_time_update(sleep_ticks: u32) {
+    if sleep_ticks == 0 {
+        return;
+    }
+
+    // 其他核可能正在从本核的排序链表中删除节点
+    let int_save = disable_interrupts();
+
+    let percpu = os_percpu_get();
+    TICK_COUNT[arch_curr_cpuid() as usize].fetch_add((sleep_ticks - 1) as u64, Ordering::Release);
+    os_sort_link_update_expire_time(sleep_ticks, &mut percpu.task_sort_link);
+    os_sort_link_update_expire_time(sleep_ticks, &mut percpu.swtmr_sort_link);
+
+    restore_interrupt_state(int_save);
+}
+
+/// 空闲任务进入WFI前调用，按最近的到期时间设置下一次Tick中断
+pub(crate) fn tickless_start() {
+    if !is_tickless_enabled() {
+        return;
+    }
+
+    let int_save = disable_interrupts();
+
+    let cpuid = arch_curr_cpuid() as usize;
+    let percpu = os_percpu_get();
+    let task_timeout = os_sort_link_get_next_expire_time(&mut percpu.task_sort_link);
+    let swtmr_timeout = os_sort_link_get_next_expire_time(&mut percpu.swtmr_sort_link);
+
+    // 定时器的重载值为32位
+    let cycles_per_tick = get_cycles_per_tick();
+    let sleep_ticks = task_timeout
+        .min(swtmr_timeout)
+        .min(u32::MAX / cycles_per_tick);
+
+    // 下一个Tick就有到期的节点时不必休眠
+    if sleep_ticks > 1 {
+        // 休眠在最近的Tick边界后sleep_ticks个Tick结束，扣除当前Tick已经经过的周期
+        let since_tick =
+            hal_clock_get_cycles().saturating_sub(LAST_TICK_CYCLES[cpuid].load(Ordering::Relaxed));
+        let since_tick = since_tick.min(cycles_per_tick as u64 - 1) as u32;
+        SLEEP_TICKS[cpuid].store(sleep_ticks, Ordering::Relaxed);
+        hal_clock_tick_timer_reload(sleep_ticks * cycles_per_tick - since_tick);
+    }
+
+    restore_interrupt_state(int_save);
+}
+
+/// 中断入口处调用，结束休眠并补偿实际经过的Tick
+///
+/// 补偿不会越过最近的到期时间，到期的节点总是由Tick中断处理
+pub fn tickless_update() {
+    let cpuid = arch_curr_cpuid() as usize;
+    let sleep_ticks = SLEEP_TICKS[cpuid].swap(0, Ordering::Relaxed);
+    if sleep_ticks == 0 {
+        return;
+    }
+
+    let cycles_per_tick = get_cycles_per_tick() as u64;
+    let last_tick = LAST_TICK_CYCLES[cpuid].load(Ordering::Relaxed);
+    let elapsed = hal_clock_get_cycles().saturating_sub(last_tick);
+    let elapsed_ticks = (elapsed / cycles_per_tick).min(sleep_ticks as u64) as u32;
+
+    // 补偿已经完整经过的Tick，Tick边界随之前移，下一个Tick仍在原有的Tick边界产生
+    let update_ticks = (elapsed_ticks + 1).min(sleep_ticks);
+    sys_time_update(update_ticks);
+    LAST_TICK_CYCLES[cpuid].store(
+        last_tick + (update_ticks - 1) as u64 * cycles_per_tick,
+        Ordering::Relaxed,
+    );
+    hal_clock_tick_timer_reload((cycles_per_tick - elapsed % cycles_per_tick) as u32);
+}
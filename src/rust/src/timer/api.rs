@@ -2,16 +2,20 @@ use crate::config::TIMER_LIMIT;
 use crate::interrupt::disable_interrupts;
 use crate::interrupt::restore_interrupt_state;
 use crate::result::SystemResult;
+use crate::task::resource::{OWNER_NONE, OwnerDeadPolicy};
+use crate::task::sched::schedule;
 use crate::timer::TimerError;
 use crate::timer::global::TimerPool;
 use crate::timer::internal::timer_delete_internal;
 use crate::timer::internal::timer_get_time_internal;
 use crate::timer::internal::timer_start_internal;
 use crate::timer::internal::timer_stop_internal;
+use crate::timer::types::TimerControlBlock;
 use crate::timer::types::TimerHandler;
 use crate::timer::types::TimerId;
 use crate::timer::types::TimerMode;
 use crate::timer::types::TimerState;
+use crate::utils::list::LinkedList;
 
 /// 创建定时器
 pub fn timer_create(timeout: u32, mode: TimerMode, handler: TimerHandler) -> SystemResult<TimerId> {
@@ -58,12 +62,14 @@ pub fn timer_start(timer_id: TimerId) -> SystemResult<()> {
             Err(TimerError::NotCreated.into())
         }
         TimerState::Created => {
+            timer.expired = 0;
             timer_start_internal(timer);
             restore_interrupt_state(int_save);
             Ok(())
         }
         TimerState::Running => {
             timer_stop_internal(timer);
+            timer.expired = 0;
             timer_start_internal(timer);
             restore_interrupt_state(int_save);
             Ok(())
@@ -104,7 +110,9 @@ pub fn timer_stop(timer_id: TimerId) -> SystemResult<()> {
     }
 }
 
-/// 删除定时器
+/// 删除定时器，多对象等待该定时器的任务返回[`SelectError::ObjectDeleted`]
+///
+/// [`SelectError::ObjectDeleted`]: crate::select::error::SelectError::ObjectDeleted
 pub fn timer_delete(timer_id: TimerId) -> SystemResult<()> {
     let index = timer_id.get_index();
     if index as u32 >= TIMER_LIMIT {
@@ -125,18 +133,97 @@ pub fn timer_delete(timer_id: TimerId) -> SystemResult<()> {
             restore_interrupt_state(int_save);
             Err(TimerError::NotCreated.into())
         }
-        TimerState::Created => {
-            timer_delete_internal(timer);
+        state => {
+            if state == TimerState::Running {
+                timer_stop_internal(timer);
+            }
+            let woken = timer_delete_internal(timer);
             restore_interrupt_state(int_save);
+            // 唤醒多对象等待该定时器的任务
+            if woken {
+                schedule();
+            }
             Ok(())
         }
-        TimerState::Running => {
+    }
+}
+
+/// 设置创建者被删除时的处理策略，默认为[`OwnerDeadPolicy::Orphan`]
+///
+/// `Release`停止定时器，`Destroy`停止并删除定时器。
+pub fn timer_owner_policy_set(timer_id: TimerId, policy: OwnerDeadPolicy) -> SystemResult<()> {
+    let index = timer_id.get_index();
+    if index as u32 >= TIMER_LIMIT {
+        return Err(TimerError::IdInvalid.into());
+    }
+
+    let int_save = disable_interrupts();
+    let timer = TimerPool::get_timer_by_index(index as usize);
+
+    if !timer.matches_id(timer_id) {
+        restore_interrupt_state(int_save);
+        return Err(TimerError::IdInvalid.into());
+    }
+    if timer.get_state() == TimerState::Unused {
+        restore_interrupt_state(int_save);
+        return Err(TimerError::NotCreated.into());
+    }
+    timer.owner_policy = policy;
+
+    restore_interrupt_state(int_save);
+    Ok(())
+}
+
+/// 处理被删除任务创建的定时器，须在关中断后调用，返回是否唤醒了任务
+pub(crate) fn timer_owner_dead(task_id: u32, count: &mut u16) -> bool {
+    let mut woken = false;
+    for index in 0..TIMER_LIMIT as usize {
+        let timer = TimerPool::get_timer_by_index(index);
+        if timer.get_state() == TimerState::Unused || timer.owner != task_id {
+            continue;
+        }
+
+        timer.owner = OWNER_NONE;
+        if timer.owner_policy == OwnerDeadPolicy::Orphan {
+            continue;
+        }
+        if timer.get_state() == TimerState::Running {
             timer_stop_internal(timer);
-            timer_delete_internal(timer);
-            restore_interrupt_state(int_save);
-            Ok(())
         }
+        if timer.owner_policy == OwnerDeadPolicy::Destroy {
+            woken |= timer_delete_internal(timer);
+        }
+        *count += 1;
     }
+    woken
+}
+
+/// 查找已创建的定时器，须在关中断后调用
+fn timer_get_created(timer_id: TimerId) -> SystemResult<&'static mut TimerControlBlock> {
+    let index = timer_id.get_index();
+    if index as u32 >= TIMER_LIMIT {
+        return Err(TimerError::IdInvalid.into());
+    }
+    let timer = TimerPool::get_timer_by_index(index as usize);
+    if !timer.matches_id(timer_id) {
+        return Err(TimerError::IdInvalid.into());
+    }
+    if timer.get_state() == TimerState::Unused {
+        return Err(TimerError::NotCreated.into());
+    }
+    Ok(timer)
+}
+
+/// 获取定时器启动后尚未报告的超时次数和多对象等待链表，须在关中断后调用
+pub(crate) fn timer_select_poll(timer_id: TimerId) -> SystemResult<(u32, *mut LinkedList)> {
+    let timer = timer_get_created(timer_id)?;
+    Ok((timer.expired, &raw mut timer.select_list))
+}
+
+/// 取走定时器尚未报告的超时次数，须在关中断后调用
+pub(crate) fn timer_expired_take(timer_id: TimerId) -> SystemResult<u32> {
+    let timer = timer_get_created(timer_id)?;
+    Ok(core::mem::take(&mut timer.expired))
 }
 
 /// 获取定时器剩余时间
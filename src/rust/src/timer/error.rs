@@ -101,4 +101,4 @@ const ERRNO_TIMER_QUEUE_CREATE_FAILED: u32 = 0x0200030b;
 const ERRNO_TIMER_TASK_CREATE_FAILED: u32 = 0x0200030c;
 const ERRNO_TIMER_NOT_STARTED: u32 = 0x0200030d;
 const ERRNO_TIMER_STATUS_INVALID: u32 = 0x0200030e;
-const ERRNO_SWTMR_TICK_PTR_NULL : u32 = 0x02000310;
+const ERRNO_SWTMR_TICK_PTR_NULL: u32 = 0x02000310;
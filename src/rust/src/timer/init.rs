@@ -1,5 +1,6 @@
 use crate::{
-    percpu::os_percpu_get,
+    config::KERNEL_CORE_NUM,
+    percpu::{os_percpu_get, os_percpu_get_by_id},
     result::SystemResult,
     timer::{global::TimerPool, types::TimerHandlerItem},
     utils::sortlink::os_sort_link_init,
@@ -12,9 +13,14 @@ pub fn timer_init() -> SystemResult<()> {
     #[cfg(not(feature = "timer-in-isr"))]
     {
         // 创建定时器处理队列
-        use crate::{config::TIMER_LIMIT, queue::management::create_queue, timer::TimerError};
-        match create_queue(TIMER_LIMIT as usize, 4) {
-            Ok(queue_id) => os_percpu_get().set_timer_queue_id(queue_id),
+        use crate::{
+            config::TIMER_LIMIT, queue::management::create_queue, timer::TimerError,
+            timer::types::TIMER_HANDLE_ITEM_SIZE,
+        };
+        match create_queue(TIMER_LIMIT as usize, TIMER_HANDLE_ITEM_SIZE) {
+            // 各核共用同一个定时器处理队列
+            Ok(queue_id) => (0..KERNEL_CORE_NUM)
+                .for_each(|cpuid| os_percpu_get_by_id(cpuid as u32).set_timer_queue_id(queue_id)),
             Err(_) => return Err(TimerError::QueueCreateFailed.into()),
         }
 
@@ -24,8 +30,10 @@ pub fn timer_init() -> SystemResult<()> {
             Err(_) => return Err(TimerError::TaskCreateFailed.into()),
         }
     }
-    // 初始化排序链表
-    os_sort_link_init(&mut os_percpu_get().swtmr_sort_link);
+    // 初始化每个核的排序链表
+    for cpuid in 0..KERNEL_CORE_NUM {
+        os_sort_link_init(&mut os_percpu_get_by_id(cpuid as u32).swtmr_sort_link);
+    }
     Ok(())
 }
 
@@ -72,7 +80,7 @@ fn timer_task_create() -> SystemResult<()> {
     let mut timer_task_init_param = TaskInitParam {
         task_entry: Some(timer_task),
         stack_size: TIMER_TASK_STACK_SIZE,
-        name: b"Swt_Task\0".as_ptr(),
+        name: c"Swt_Task".as_ptr(),
         priority: 0,
         ..Default::default()
     };
@@ -82,7 +90,13 @@ fn timer_task_create() -> SystemResult<()> {
         Ok(_) => {
             os_percpu_get().set_timer_task_id(timer_task_id);
             // 设置系统任务标志
-            get_tcb_from_id(timer_task_id).set_system_task();
+            let task_cb = get_tcb_from_id(timer_task_id);
+            task_cb.set_system_task();
+            // 定时器任务固定在创建它的核上
+            #[cfg(feature = "smp")]
+            {
+                task_cb.cpu_affi_mask = 1 << task_cb.curr_cpu;
+            }
             Ok(())
         }
         Err(err) => Err(err),
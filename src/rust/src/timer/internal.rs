@@ -1,4 +1,6 @@
-use crate::percpu::os_percpu_get;
+use crate::ffi::bindings::arch_curr_cpuid;
+use crate::percpu::{os_percpu_get, os_percpu_get_by_id};
+use crate::select::core::select_object_deleted;
 use crate::timer::global::TimerPool;
 use crate::timer::types::TimerControlBlock;
 use crate::timer::types::TimerMode;
@@ -11,27 +13,32 @@ use crate::utils::sortlink::get_target_expire_time;
 pub(super) fn timer_start_internal(timer: &mut TimerControlBlock) {
     // 对应OsSwtmrStart
     timer.sort_list.set_timeout(timer.get_timeout());
+    timer.sort_list.set_cpuid(arch_curr_cpuid());
     add_to_sort_link(&mut os_percpu_get().swtmr_sort_link, &mut timer.sort_list);
     timer.set_state(TimerState::Running);
 }
 
 /// 停止定时器（内部函数）
 pub(super) fn timer_stop_internal(timer: &mut TimerControlBlock) {
-    delete_from_sort_link(&mut os_percpu_get().swtmr_sort_link, &mut timer.sort_list);
+    let sort_link_header = &mut os_percpu_get_by_id(timer.sort_list.get_cpuid()).swtmr_sort_link;
+    delete_from_sort_link(sort_link_header, &mut timer.sort_list);
     timer.state = TimerState::Created;
 }
 
-/// 删除定时器（内部函数）
-pub(super) fn timer_delete_internal(timer: &mut TimerControlBlock) {
+/// 删除定时器（内部函数），返回是否唤醒了多对象等待的任务
+pub(super) fn timer_delete_internal(timer: &mut TimerControlBlock) -> bool {
+    let woken = select_object_deleted(&raw mut timer.select_list);
     TimerPool::deallocate(timer);
+    woken
 }
 
-/// 更新定时器（内部函数）
-pub(super) fn timer_update_internal(timer: &mut TimerControlBlock) {
+/// 更新定时器（内部函数），返回是否唤醒了多对象等待的任务
+pub(super) fn timer_update_internal(timer: &mut TimerControlBlock) -> bool {
     match timer.get_mode() {
         TimerMode::OneShot => {
-            timer_delete_internal(timer);
+            let woken = timer_delete_internal(timer);
             timer.increment_id_counter();
+            return woken;
         }
         TimerMode::NoSelfDelete => {
             timer.set_state(TimerState::Created);
@@ -40,11 +47,12 @@ pub(super) fn timer_update_internal(timer: &mut TimerControlBlock) {
             timer_start_internal(timer);
         }
     }
+    false
 }
 
 /// 获取定时器剩余时间（内部函数）
 pub(super) fn timer_get_time_internal(timer: &TimerControlBlock) -> u32 {
     // 对应OsSwtmrTimeGet
-    let sort_link_header = &os_percpu_get().swtmr_sort_link;
+    let sort_link_header = &os_percpu_get_by_id(timer.sort_list.get_cpuid()).swtmr_sort_link;
     get_target_expire_time(sort_link_header, &timer.sort_list)
 }
@@ -6,8 +6,11 @@ mod internal;
 mod scan;
 mod types;
 
-pub use api::{timer_create, timer_delete, timer_start, timer_stop, timer_time_get};
+pub use api::{
+    timer_create, timer_delete, timer_owner_policy_set, timer_start, timer_stop, timer_time_get,
+};
+pub(crate) use api::{timer_expired_take, timer_owner_dead, timer_select_poll};
 pub use error::TimerError;
 pub use init::timer_init;
 pub use scan::timer_scan;
-pub use types::{TimerHandler, TimerMode};
+pub use types::{TimerHandler, TimerId, TimerMode};
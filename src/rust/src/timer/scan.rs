@@ -1,13 +1,18 @@
 use core::ptr::addr_of;
 
 use crate::{
+    interrupt::{disable_interrupts, restore_interrupt_state},
     percpu::os_percpu_get,
+    select::core::select_notify,
+    task::sched::schedule,
     timer::{internal::timer_update_internal, types::TimerControlBlock},
     utils::{list::LinkedList, sortlink::SortLinkList},
 };
 
 /// 定时器扫描函数
 pub fn timer_scan() {
+    let int_save = disable_interrupts();
+
     // 获取当前CPU的软件定时器排序链表
     let swtmr_sort_link = &mut os_percpu_get().swtmr_sort_link;
 
@@ -17,9 +22,11 @@ pub fn timer_scan() {
 
     // 如果链表为空，返回
     if LinkedList::is_empty(list_object) {
+        restore_interrupt_state(int_save);
         return;
     }
 
+    let mut woken = false;
     unsafe {
         // 获取第一个节点并减少轮数
         let mut sort_list = SortLinkList::from_list((*list_object).next);
@@ -68,8 +75,12 @@ pub fn timer_scan() {
                 );
             }
 
+            // 通知等待该定时器的任务，单次定时器随后被删除
+            timer.expired = timer.expired.saturating_add(1);
+            woken |= select_notify(&raw mut timer.select_list, |_| true);
+
             // 更新定时器
-            timer_update_internal(timer);
+            woken |= timer_update_internal(timer);
 
             // 检查链表是否为空
             if LinkedList::is_empty(list_object) {
@@ -80,4 +91,10 @@ pub fn timer_scan() {
             sort_list = SortLinkList::from_list((*list_object).next);
         }
     }
+
+    restore_interrupt_state(int_save);
+
+    if woken {
+        schedule();
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
     container_of,
+    task::resource::{OWNER_NONE, OwnerDeadPolicy, current_owner},
     utils::{list::LinkedList, sortlink::SortLinkList},
 };
 
@@ -104,6 +105,14 @@ pub struct TimerControlBlock {
     pub timeout: u32,
     /// 软件定时器超时处理回调函数
     pub handler: TimerHandler,
+    /// 创建软件定时器的任务
+    pub owner: u32,
+    /// 创建者被删除时的处理策略
+    pub owner_policy: OwnerDeadPolicy,
+    /// 启动后尚未被多对象等待报告的超时次数
+    pub expired: u32,
+    /// 多对象等待此定时器的等待节点列表
+    pub select_list: LinkedList,
 }
 
 impl TimerControlBlock {
@@ -114,6 +123,10 @@ impl TimerControlBlock {
         timer_id: TimerId(0),
         timeout: 0,
         handler: None,
+        owner: OWNER_NONE,
+        owner_policy: OwnerDeadPolicy::Orphan,
+        expired: 0,
+        select_list: LinkedList::new(),
     };
 
     #[inline]
@@ -191,6 +204,10 @@ impl TimerControlBlock {
         self.set_mode(mode);
         self.set_timeout(timeout);
         self.set_handler(handler);
+        self.owner = current_owner();
+        self.owner_policy = OwnerDeadPolicy::Orphan;
+        self.expired = 0;
+        LinkedList::init(&raw mut self.select_list);
     }
 }
 
@@ -203,6 +220,10 @@ impl Default for TimerControlBlock {
             timer_id: TimerId(0),
             timeout: 0,
             handler: None,
+            owner: OWNER_NONE,
+            owner_policy: OwnerDeadPolicy::Orphan,
+            expired: 0,
+            select_list: LinkedList::new(),
         }
     }
 }
@@ -1,3 +1,5 @@
+use crate::config::TASK_PRIORITY_NUM;
+
 /// 定义无效位索引常量
 const INVALID_BIT_INDEX: u16 = 32;
 const BITMAP_MASK: u16 = 0x1F;
@@ -47,3 +49,98 @@ pub fn get_lowest_bit(bitmap: u32) -> u16 {
     }
     bitmap.trailing_zeros() as u16
 }
+
+/// 每组优先级数，即一个u32的位数
+const PRIORITY_GROUP_SIZE: usize = 32;
+/// 优先级组数
+const PRIORITY_GROUP_NUM: usize = TASK_PRIORITY_NUM.div_ceil(PRIORITY_GROUP_SIZE);
+const _: () = assert!(PRIORITY_GROUP_NUM <= 32, "组位图只有32位");
+
+/// 两级优先级位图
+///
+/// 每32个优先级为一组，组位图记录哪些组非空，查找最小和最大优先级都只需两次位扫描。
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriorityBitmap {
+    /// 组位图，第g位表示第g组非空
+    group: u32,
+    /// 每组的优先级位图，第i位对应组内第i个优先级
+    bits: [u32; PRIORITY_GROUP_NUM],
+}
+
+impl Default for PriorityBitmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PriorityBitmap {
+    pub const fn new() -> Self {
+        Self {
+            group: 0,
+            bits: [0; PRIORITY_GROUP_NUM],
+        }
+    }
+
+    /// 设置指定优先级
+    #[inline]
+    pub fn set(&mut self, priority: u16) {
+        let (group, bit) = Self::split(priority);
+        self.bits[group] |= 1 << bit;
+        self.group |= 1 << group;
+    }
+
+    /// 清除指定优先级
+    #[inline]
+    pub fn clear(&mut self, priority: u16) {
+        let (group, bit) = Self::split(priority);
+        self.bits[group] &= !(1 << bit);
+        if self.bits[group] == 0 {
+            self.group &= !(1 << group);
+        }
+    }
+
+    /// 是否设置了指定优先级
+    #[inline]
+    pub fn contains(&self, priority: u16) -> bool {
+        let (group, bit) = Self::split(priority);
+        self.bits[group] & (1 << bit) != 0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.group == 0
+    }
+
+    /// 获取数值最小（最高）的优先级
+    #[inline]
+    pub fn first(&self) -> Option<u16> {
+        if self.group == 0 {
+            return None;
+        }
+        let group = self.group.trailing_zeros() as usize;
+        let bit = self.bits[group].trailing_zeros() as usize;
+        Some((group * PRIORITY_GROUP_SIZE + bit) as u16)
+    }
+
+    /// 获取数值最大（最低）的优先级
+    #[inline]
+    pub fn last(&self) -> Option<u16> {
+        if self.group == 0 {
+            return None;
+        }
+        let group = 31 - self.group.leading_zeros() as usize;
+        let bit = 31 - self.bits[group].leading_zeros() as usize;
+        Some((group * PRIORITY_GROUP_SIZE + bit) as u16)
+    }
+
+    #[inline]
+    fn split(priority: u16) -> (usize, usize) {
+        let priority = priority as usize;
+        debug_assert!(priority < TASK_PRIORITY_NUM);
+        (
+            priority / PRIORITY_GROUP_SIZE,
+            priority % PRIORITY_GROUP_SIZE,
+        )
+    }
+}
@@ -8,6 +8,12 @@ pub struct LinkedList {
 unsafe impl Send for LinkedList {}
 unsafe impl Sync for LinkedList {}
 
+impl Default for LinkedList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LinkedList {
     pub const fn new() -> Self {
         Self {
@@ -76,7 +82,7 @@ impl LinkedList {
 
     #[inline]
     pub fn is_empty(list: *const LinkedList) -> bool {
-        unsafe { (*list).next as *const LinkedList == list }
+        unsafe { core::ptr::eq((*list).next, list) }
     }
 
     #[inline]
@@ -115,7 +121,7 @@ macro_rules! offset_of {
 #[macro_export]
 macro_rules! container_of {
     ($ptr:expr, $type:ty, $($field:ident).+) => {{
-        let offset = crate::offset_of!($type, $($field).+);
+        let offset = $crate::offset_of!($type, $($field).+);
         ($ptr as usize - offset) as *mut $type
     }};
 }
@@ -128,7 +134,7 @@ macro_rules! list_for_each_entry {
             if !list_head__.is_null() {
                 let mut current_node_ptr__ = (*list_head__).next;
                 while current_node_ptr__ != list_head__ {
-                    let $item: *mut $type = crate::container_of!(current_node_ptr__, $type, $($field).+);
+                    let $item: *mut $type = $crate::container_of!(current_node_ptr__, $type, $($field).+);
                     $code
                     current_node_ptr__ = (*current_node_ptr__).next;
                 }
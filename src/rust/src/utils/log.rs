@@ -66,10 +66,10 @@ pub fn _print_internal(prefix: &str, args: core::fmt::Arguments) {
     let mut message = heapless::String::<512>::new();
     // 写入前缀和格式化消息
     if write!(message, "{}{}", prefix, args).is_ok() && message.push('\0').is_ok() {
-        dprintf(message.as_ptr());
+        dprintf(message.as_ptr().cast());
     } else {
         // 格式化失败，输出错误消息
-        dprintf(b"Log message too long or format error\n\0".as_ptr());
+        dprintf(c"Log message too long or format error\n".as_ptr());
     }
 }
 
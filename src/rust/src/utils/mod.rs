@@ -1,6 +1,6 @@
 pub mod align;
 pub mod bitmap;
 pub mod list;
-pub mod memdump;
 pub mod log;
+pub mod memdump;
 pub mod sortlink;
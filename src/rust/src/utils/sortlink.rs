@@ -16,21 +16,52 @@ pub struct SortLinkList {
     pub sort_link_node: LinkedList,
     /// 索引和轮数
     pub idx_roll_num: u32,
+    /// 所在排序链表所属的CPU核
+    #[cfg(feature = "smp")]
+    pub cpuid: u32,
+}
+
+impl Default for SortLinkList {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SortLinkList {
     pub const UNINIT: Self = Self {
         sort_link_node: LinkedList::UNINIT,
         idx_roll_num: 0,
+        #[cfg(feature = "smp")]
+        cpuid: 0,
     };
 
     pub const fn new() -> Self {
         Self {
             sort_link_node: LinkedList::new(),
             idx_roll_num: 0,
+            #[cfg(feature = "smp")]
+            cpuid: 0,
         }
     }
 
+    /// 记录节点所在排序链表所属的CPU核
+    #[inline]
+    pub fn set_cpuid(&mut self, _cpuid: u32) {
+        #[cfg(feature = "smp")]
+        {
+            self.cpuid = _cpuid;
+        }
+    }
+
+    /// 获取节点所在排序链表所属的CPU核
+    #[inline]
+    pub fn get_cpuid(&self) -> u32 {
+        #[cfg(feature = "smp")]
+        return self.cpuid;
+        #[cfg(not(feature = "smp"))]
+        0
+    }
+
     #[inline]
     pub fn set_timeout(&mut self, timeout: u32) {
         self.idx_roll_num = timeout;
@@ -191,7 +222,7 @@ pub extern "C" fn add_to_sort_link(
                 );
 
                 // 如果已经到达链表末尾，结束查找
-                if &mut (*current_list).sort_link_node as *mut LinkedList == list_object {
+                if core::ptr::eq(&(*current_list).sort_link_node, list_object) {
                     break;
                 }
             }
@@ -254,17 +285,19 @@ fn os_calc_expire_time(roll_num: u32, sort_index: u32, cur_sort_index: u16) -> u
 
     // 计算 sort_index 和 cur_sort_index 之间的距离，考虑循环特性
     if sort_index > cur_sort_index as u32 {
-        sort_index = sort_index - cur_sort_index as u32;
+        sort_index -= cur_sort_index as u32;
     } else {
-        sort_index = OS_TSK_SORTLINK_LEN - cur_sort_index as u32 + sort_index;
+        sort_index += OS_TSK_SORTLINK_LEN - cur_sort_index as u32;
     }
 
     // 计算过期时间
     ((roll_num - 1) << OS_TSK_SORTLINK_LOGLEN) + sort_index
 }
 
-#[deprecated]
-#[allow(dead_code)]
+#[cfg(feature = "tickless")]
+/// 获取排序链表中最近一个节点的到期时间
+///
+/// 返回值为距离到期的Tick数，下一个Tick到期时为1，链表为空时返回`u32::MAX`
 pub fn os_sort_link_get_next_expire_time(sort_link_header: &mut SortLinkAttribute) -> u32 {
     let mut min_sort_index = u32::MAX;
     let mut min_roll_num = OS_TSK_LOW_BITS_MASK;
@@ -305,11 +338,11 @@ pub fn os_sort_link_get_next_expire_time(sort_link_header: &mut SortLinkAttribut
     }
 }
 
+#[cfg(feature = "tickless")]
 /// 更新排序链表中所有节点的到期时间
 ///
-/// 当系统休眠或跳过一段时间后，需要调整所有定时器的到期时间
-#[deprecated]
-#[allow(dead_code)]
+/// 当系统休眠或跳过一段时间后，需要调整所有定时器的到期时间。
+/// 只补偿前`sleep_ticks - 1`个Tick，最后一个Tick由Tick中断处理。
 pub fn os_sort_link_update_expire_time(sleep_ticks: u32, sort_link_header: &mut SortLinkAttribute) {
     // 如果跳过的时钟周期为0，直接返回
     if sleep_ticks == 0 {
@@ -375,7 +408,7 @@ pub extern "C" fn get_target_expire_time(
         let mut list_sorted = container_of!((*list_object).next, SortLinkList, sort_link_node);
 
         // 累加轮数直到找到目标节点
-        while list_sorted != target_sort_list as *const _ as *mut _ {
+        while !core::ptr::eq(list_sorted, target_sort_list) {
             // 累加当前节点的轮数
             roll_num += (*list_sorted).get_roll_num();
 
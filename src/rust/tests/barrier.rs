@@ -0,0 +1,184 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    barrier::{
+        core::{barrier_create, barrier_delete, barrier_generation_get, barrier_wait},
+        error::BarrierError,
+        latch::{latch_count_down, latch_count_get, latch_create, latch_delete, latch_wait},
+    },
+    result::SystemError,
+    sim,
+    task::{
+        manager::delay::task_delay,
+        sync::lock::{task_lock, task_unlock},
+    },
+    tick::clock::get_tick_count,
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+static TRIPS: AtomicU32 = AtomicU32::new(0);
+
+extern "C" fn count_trip(generation: u32) {
+    assert_eq!(TRIPS.fetch_add(1, Ordering::SeqCst), generation);
+}
+
+#[test]
+fn barrier_releases_parties_each_generation() {
+    sim::run(|| {
+        TRIPS.store(0, Ordering::SeqCst);
+        let barrier = barrier_create(3, Some(count_trip)).unwrap();
+        let lasts = Arc::new(AtomicU32::new(0));
+        let done = Arc::new(AtomicU32::new(0));
+        for _ in 0..2 {
+            let lasts = lasts.clone();
+            let done = done.clone();
+            sim::spawn(c"Party", 5, move || {
+                for _ in 0..2 {
+                    if barrier_wait(barrier, u32::MAX).unwrap() {
+                        lasts.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+                done.fetch_add(1, Ordering::SeqCst);
+            })
+            .unwrap();
+        }
+        for _ in 0..2 {
+            if barrier_wait(barrier, u32::MAX).unwrap() {
+                lasts.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        wait_until(|| done.load(Ordering::SeqCst) == 2);
+
+        assert_eq!(lasts.load(Ordering::SeqCst), 2);
+        assert_eq!(TRIPS.load(Ordering::SeqCst), 2);
+        assert_eq!(barrier_generation_get(barrier).unwrap(), 2);
+        barrier_delete(barrier).unwrap();
+    });
+}
+
+#[test]
+fn barrier_timeout_withdraws_arrival() {
+    sim::run(|| {
+        assert_eq!(
+            barrier_create(0, None),
+            Err(SystemError::Barrier(BarrierError::PartiesInvalid))
+        );
+        let barrier = barrier_create(2, None).unwrap();
+        assert_eq!(
+            barrier_wait(barrier, 0),
+            Err(SystemError::Barrier(BarrierError::Unavailable))
+        );
+        assert_eq!(
+            barrier_wait(barrier, 5),
+            Err(SystemError::Barrier(BarrierError::Timeout))
+        );
+
+        let released = Arc::new(AtomicU32::new(0));
+        let child_released = released.clone();
+        sim::spawn(c"Party", 5, move || {
+            child_released.store(1, Ordering::SeqCst);
+            barrier_wait(barrier, u32::MAX).unwrap();
+            child_released.store(2, Ordering::SeqCst);
+        })
+        .unwrap();
+        wait_until(|| released.load(Ordering::SeqCst) == 1);
+        task_delay(2).unwrap();
+        assert_eq!(
+            barrier_delete(barrier),
+            Err(SystemError::Barrier(BarrierError::Pended))
+        );
+        // 超时的到达已撤销，本次到达使本轮完成
+        assert!(barrier_wait(barrier, u32::MAX).unwrap());
+        wait_until(|| released.load(Ordering::SeqCst) == 2);
+        assert_eq!(barrier_generation_get(barrier).unwrap(), 1);
+        barrier_delete(barrier).unwrap();
+    });
+}
+
+#[test]
+fn arrival_between_timeout_and_resume_is_not_released_short() {
+    sim::run(|| {
+        let barrier = barrier_create(2, None).unwrap();
+        let timed_out = Arc::new(Mutex::new(None));
+        let child_timed_out = timed_out.clone();
+        sim::spawn(c"Waiter", 5, move || {
+            *child_timed_out.lock().unwrap() = Some(barrier_wait(barrier, 3));
+        })
+        .unwrap();
+
+        // 等待者超时后还未运行时，更高优先级的任务先到达
+        task_lock();
+        let deadline = get_tick_count() + 5;
+        while get_tick_count() < deadline {}
+        let arrived = Arc::new(Mutex::new(None));
+        let child_arrived = arrived.clone();
+        sim::spawn(c"Arriver", 4, move || {
+            *child_arrived.lock().unwrap() = Some(barrier_wait(barrier, u32::MAX));
+        })
+        .unwrap();
+        task_unlock();
+
+        assert_eq!(
+            *timed_out.lock().unwrap(),
+            Some(Err(SystemError::Barrier(BarrierError::Timeout)))
+        );
+        // 超时的任务不计入本轮，后到达的任务仍在等待
+        assert_eq!(*arrived.lock().unwrap(), None);
+        assert!(barrier_wait(barrier, u32::MAX).unwrap());
+        wait_until(|| arrived.lock().unwrap().is_some());
+        assert_eq!(*arrived.lock().unwrap(), Some(Ok(false)));
+        assert_eq!(barrier_generation_get(barrier).unwrap(), 1);
+        barrier_delete(barrier).unwrap();
+    });
+}
+
+#[test]
+fn latch_releases_waiters_at_zero() {
+    sim::run(|| {
+        let latch = latch_create(2).unwrap();
+        let released = Arc::new(AtomicU32::new(0));
+        for _ in 0..2 {
+            let released = released.clone();
+            sim::spawn(c"Waiter", 5, move || {
+                latch_wait(latch, u32::MAX).unwrap();
+                released.fetch_add(1, Ordering::SeqCst);
+            })
+            .unwrap();
+        }
+        latch_count_down(latch).unwrap();
+        assert_eq!(latch_count_get(latch).unwrap(), 1);
+        task_delay(2).unwrap();
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+
+        latch_count_down(latch).unwrap();
+        wait_until(|| released.load(Ordering::SeqCst) == 2);
+        latch_count_down(latch).unwrap();
+        assert_eq!(latch_count_get(latch).unwrap(), 0);
+        latch_wait(latch, 0).unwrap();
+        latch_delete(latch).unwrap();
+    });
+}
+
+#[test]
+fn latch_wait_times_out() {
+    sim::run(|| {
+        let latch = latch_create(1).unwrap();
+        assert_eq!(
+            latch_wait(latch, 0),
+            Err(SystemError::Barrier(BarrierError::Unavailable))
+        );
+        assert_eq!(
+            latch_wait(latch, 5),
+            Err(SystemError::Barrier(BarrierError::Timeout))
+        );
+        latch_delete(latch).unwrap();
+        assert_eq!(
+            latch_count_down(latch),
+            Err(SystemError::Barrier(BarrierError::Invalid))
+        );
+    });
+}
@@ -0,0 +1,14 @@
+//! 集成测试共用的辅助函数
+
+use rust::task::manager::delay::task_delay;
+
+/// 等待条件成立，多核下其他任务可能晚于当前任务的延时才开始运行
+pub fn wait_until(cond: impl Fn() -> bool) {
+    for _ in 0..100 {
+        if cond() {
+            return;
+        }
+        task_delay(1).unwrap();
+    }
+    panic!("condition not reached");
+}
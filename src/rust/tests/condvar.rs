@@ -0,0 +1,240 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    condvar::{
+        core::{
+            condvar_broadcast, condvar_create, condvar_delete, condvar_signal, condvar_timed_wait,
+            condvar_wait,
+        },
+        error::CondvarError,
+        types::CondvarId,
+    },
+    mutex::{
+        core::{mutex_create, mutex_delete, mutex_owner_policy_set, mutex_pend, mutex_post},
+        error::MutexError,
+        types::MutexId,
+    },
+    result::SystemError,
+    sim,
+    task::{
+        info::get_current_task_id,
+        manager::{delay::task_delay, delete::task_delete, priority::get_task_priority},
+        resource::OwnerDeadPolicy,
+    },
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+/// 创建在条件变量上等待的任务，`waiting`在开始等待前加一，被唤醒后把任务优先级记入`woken`
+fn spawn_waiter(
+    condvar: CondvarId,
+    mutex: MutexId,
+    priority: u16,
+    waiting: &Arc<AtomicU32>,
+    woken: &Arc<Mutex<Vec<u16>>>,
+) {
+    let waiting = waiting.clone();
+    let woken = woken.clone();
+    sim::spawn(c"Waiter", priority, move || {
+        mutex_pend(mutex, u32::MAX).unwrap();
+        waiting.fetch_add(1, Ordering::SeqCst);
+        condvar_wait(condvar, mutex).unwrap();
+        woken.lock().unwrap().push(priority);
+        mutex_post(mutex).unwrap();
+    })
+    .unwrap();
+}
+
+/// 等待`count`个任务开始等待，等待者释放互斥锁后才真正进入等待队列
+fn wait_for_waiters(waiting: &AtomicU32, count: u32, mutex: MutexId) {
+    wait_until(|| waiting.load(Ordering::SeqCst) == count);
+    mutex_pend(mutex, u32::MAX).unwrap();
+    mutex_post(mutex).unwrap();
+}
+
+#[test]
+fn wait_releases_mutex_until_signaled() {
+    sim::run(|| {
+        let condvar = condvar_create().unwrap();
+        let mutex = mutex_create().unwrap();
+        let waiting = Arc::new(AtomicU32::new(0));
+        let woken = Arc::new(Mutex::new(Vec::new()));
+        spawn_waiter(condvar, mutex, 5, &waiting, &woken);
+        wait_until(|| waiting.load(Ordering::SeqCst) == 1);
+
+        // 等待者已释放互斥锁
+        mutex_pend(mutex, 10).unwrap();
+        condvar_signal(condvar).unwrap();
+        task_delay(2).unwrap();
+        assert!(woken.lock().unwrap().is_empty());
+        mutex_post(mutex).unwrap();
+        wait_until(|| woken.lock().unwrap().len() == 1);
+
+        condvar_delete(condvar).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn timed_wait_reacquires_recursive_mutex() {
+    sim::run(|| {
+        let condvar = condvar_create().unwrap();
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        assert_eq!(
+            condvar_timed_wait(condvar, mutex, 5),
+            Err(SystemError::Condvar(CondvarError::Timeout))
+        );
+        mutex_post(mutex).unwrap();
+        mutex_post(mutex).unwrap();
+        assert_eq!(
+            mutex_post(mutex),
+            Err(SystemError::Mutex(MutexError::Invalid))
+        );
+        condvar_delete(condvar).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn signal_wakes_highest_priority_and_broadcast_wakes_all() {
+    sim::run(|| {
+        let condvar = condvar_create().unwrap();
+        let mutex = mutex_create().unwrap();
+        let waiting = Arc::new(AtomicU32::new(0));
+        let woken = Arc::new(Mutex::new(Vec::new()));
+        for (count, priority) in [7, 5, 6].into_iter().enumerate() {
+            spawn_waiter(condvar, mutex, priority, &waiting, &woken);
+            wait_for_waiters(&waiting, count as u32 + 1, mutex);
+        }
+
+        condvar_signal(condvar).unwrap();
+        wait_until(|| woken.lock().unwrap().len() == 1);
+        condvar_broadcast(condvar).unwrap();
+        wait_until(|| woken.lock().unwrap().len() == 3);
+        assert_eq!(woken.lock().unwrap()[0], 5);
+
+        condvar_delete(condvar).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn woken_waiter_boosts_mutex_owner() {
+    sim::run_with_priority(20, || {
+        let condvar = condvar_create().unwrap();
+        let mutex = mutex_create().unwrap();
+        let me = get_current_task_id();
+        let waiting = Arc::new(AtomicU32::new(0));
+        let woken = Arc::new(Mutex::new(Vec::new()));
+        spawn_waiter(condvar, mutex, 5, &waiting, &woken);
+        wait_for_waiters(&waiting, 1, mutex);
+
+        mutex_pend(mutex, 0).unwrap();
+        condvar_signal(condvar).unwrap();
+        wait_until(|| get_task_priority(me) == Ok(5));
+        mutex_post(mutex).unwrap();
+        assert_eq!(get_task_priority(me).unwrap(), 20);
+        wait_until(|| woken.lock().unwrap().len() == 1);
+
+        condvar_delete(condvar).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn wait_errors() {
+    sim::run(|| {
+        let condvar = condvar_create().unwrap();
+        let mutex = mutex_create().unwrap();
+        let other = mutex_create().unwrap();
+        assert_eq!(
+            condvar_wait(condvar, mutex),
+            Err(SystemError::Mutex(MutexError::Invalid))
+        );
+
+        let waiting = Arc::new(AtomicU32::new(0));
+        let woken = Arc::new(Mutex::new(Vec::new()));
+        spawn_waiter(condvar, mutex, 5, &waiting, &woken);
+        wait_for_waiters(&waiting, 1, mutex);
+        mutex_pend(other, 0).unwrap();
+        assert_eq!(
+            condvar_timed_wait(condvar, other, 5),
+            Err(SystemError::Condvar(CondvarError::MutexMismatch))
+        );
+        mutex_post(other).unwrap();
+        assert_eq!(
+            condvar_delete(condvar),
+            Err(SystemError::Condvar(CondvarError::Pended))
+        );
+
+        condvar_signal(condvar).unwrap();
+        wait_until(|| woken.lock().unwrap().len() == 1);
+        condvar_delete(condvar).unwrap();
+        mutex_delete(mutex).unwrap();
+        mutex_delete(other).unwrap();
+    });
+}
+
+/// 等待者被唤醒后在重新加锁时阻塞，持有互斥锁的任务随后被删除，返回等待者的等待结果与解锁结果
+fn relock_after_owner_deleted(
+    policy: OwnerDeadPolicy,
+) -> (Result<(), SystemError>, Result<(), SystemError>) {
+    let condvar = condvar_create().unwrap();
+    let mutex = mutex_create().unwrap();
+    mutex_owner_policy_set(mutex, policy).unwrap();
+    let result = Arc::new(Mutex::new(None));
+    let child_result = result.clone();
+    sim::spawn(c"Waiter", 5, move || {
+        mutex_pend(mutex, u32::MAX).unwrap();
+        let waited = condvar_wait(condvar, mutex);
+        *child_result.lock().unwrap() = Some((waited, mutex_post(mutex)));
+    })
+    .unwrap();
+
+    // 持有者加锁后唤醒等待者，等待者在重新加锁时阻塞
+    let holder = sim::spawn(c"Holder", 6, move || {
+        mutex_pend(mutex, u32::MAX).unwrap();
+        condvar_signal(condvar).unwrap();
+        task_delay(1000).unwrap();
+    })
+    .unwrap();
+    task_delete(holder).unwrap();
+
+    wait_until(|| result.lock().unwrap().is_some());
+    condvar_delete(condvar).unwrap();
+    let result = result.lock().unwrap().take().unwrap();
+    if policy != OwnerDeadPolicy::Destroy {
+        mutex_delete(mutex).unwrap();
+    }
+    result
+}
+
+#[test]
+fn relock_continues_after_mutex_owner_is_deleted() {
+    sim::run(|| {
+        // 持有者被删除后互斥锁被释放，等待者继续加锁并持有互斥锁返回
+        assert_eq!(
+            relock_after_owner_deleted(OwnerDeadPolicy::Release),
+            (Ok(()), Ok(()))
+        );
+    });
+}
+
+#[test]
+fn relock_failure_is_reported_separately() {
+    sim::run(|| {
+        // 互斥锁随持有者删除，等待者返回时不持有互斥锁
+        assert_eq!(
+            relock_after_owner_deleted(OwnerDeadPolicy::Destroy),
+            (
+                Err(SystemError::Condvar(CondvarError::RelockFailed)),
+                Err(SystemError::Mutex(MutexError::Invalid))
+            )
+        );
+    });
+}
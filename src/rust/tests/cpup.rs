@@ -0,0 +1,80 @@
+use rust::{
+    config::{TASK_LIMIT, TICK_PER_SECOND},
+    cpup::{CPUP_PRECISION, CpupError, CpupMode, system_cpu_usage, task_cpu_usage},
+    result::SystemError,
+    sim,
+    task::{
+        global::get_tcb_from_id,
+        info::get_current_task_id,
+        manager::{delay::task_delay, delete::task_delete},
+    },
+    tick::clock::{get_cycles_per_tick, get_tick_count},
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// 持续运行直到被停止的低优先级任务
+fn spawn_busy(stop: &Arc<AtomicBool>) -> u32 {
+    let task_stop = stop.clone();
+    sim::spawn(c"Busy", 20, move || {
+        while !task_stop.load(Ordering::SeqCst) {
+            get_tick_count();
+        }
+    })
+    .unwrap()
+}
+
+#[test]
+fn switch_accumulates_run_time() {
+    sim::run(|| {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker = spawn_busy(&stop);
+
+        // 当前任务延时期间CPU全部由忙任务占用
+        task_delay(50).unwrap();
+        let run_cycles = get_tcb_from_id(worker).cpup.all_cycles;
+        assert!(run_cycles >= 40 * get_cycles_per_tick() as u64);
+
+        stop.store(true, Ordering::SeqCst);
+        task_delay(2).unwrap();
+    });
+}
+
+#[test]
+fn busy_task_dominates_last_second() {
+    sim::run(|| {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker = spawn_busy(&stop);
+
+        // 等待至少一次采样，采样之后只有忙任务和当前任务运行
+        task_delay(TICK_PER_SECOND + 100).unwrap();
+        let me = get_current_task_id();
+        let worker_usage = task_cpu_usage(worker, CpupMode::LastOneSecond).unwrap();
+        let my_usage = task_cpu_usage(me, CpupMode::LastOneSecond).unwrap();
+        assert!(worker_usage + my_usage > CPUP_PRECISION * 9 / 10);
+        assert!(system_cpu_usage(CpupMode::LastOneSecond) > CPUP_PRECISION * 9 / 10);
+        assert!(task_cpu_usage(worker, CpupMode::AllTime).unwrap() > 0);
+
+        stop.store(true, Ordering::SeqCst);
+        task_delay(2).unwrap();
+    });
+}
+
+#[test]
+fn usage_of_invalid_task() {
+    sim::run(|| {
+        assert_eq!(
+            task_cpu_usage(TASK_LIMIT, CpupMode::AllTime),
+            Err(SystemError::Cpup(CpupError::TaskIdInvalid))
+        );
+
+        let task_id = sim::spawn(c"Victim", 20, || {}).unwrap();
+        task_delete(task_id).unwrap();
+        assert_eq!(
+            task_cpu_usage(task_id, CpupMode::LastTenSeconds),
+            Err(SystemError::Cpup(CpupError::TaskNotCreated))
+        );
+    });
+}
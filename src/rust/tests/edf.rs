@@ -0,0 +1,187 @@
+use rust::{
+    result::SystemError,
+    sim,
+    task::{
+        edf::{TaskEdfParam, task_edf_stats_get, task_edf_wait_next_period},
+        error::TaskError,
+        info::get_current_task_id,
+        manager::{delay::task_delay, delete::task_delete},
+        sync::lock::{task_lock, task_unlock},
+    },
+    tick::clock::get_tick_count,
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+fn edf_param(period: u32, budget: u32, deadline: u32) -> TaskEdfParam {
+    TaskEdfParam {
+        period,
+        budget,
+        deadline,
+    }
+}
+
+/// 不让出CPU地等待指定的Tick数，期间照常响应Tick中断
+fn busy_wait(ticks: u64) {
+    let deadline = get_tick_count() + ticks;
+    while get_tick_count() < deadline {}
+}
+
+#[test]
+fn edf_runs_ahead_of_priorities() {
+    sim::run(|| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let prio_order = order.clone();
+        let edf_order = order.clone();
+        task_lock();
+        sim::spawn(c"High", 1, move || prio_order.lock().unwrap().push("prio")).unwrap();
+        sim::spawn_edf(c"Edf", 30, edf_param(100, 10, 0), move || {
+            edf_order.lock().unwrap().push("edf")
+        })
+        .unwrap();
+        task_unlock();
+        assert_eq!(*order.lock().unwrap(), ["edf", "prio"]);
+    });
+}
+
+#[test]
+fn earliest_deadline_runs_first() {
+    sim::run(|| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let late_order = order.clone();
+        let early_order = order.clone();
+        task_lock();
+        sim::spawn_edf(c"Late", 20, edf_param(100, 5, 50), move || {
+            late_order.lock().unwrap().push("late")
+        })
+        .unwrap();
+        sim::spawn_edf(c"Early", 20, edf_param(100, 5, 10), move || {
+            early_order.lock().unwrap().push("early")
+        })
+        .unwrap();
+        task_unlock();
+        assert_eq!(*order.lock().unwrap(), ["early", "late"]);
+    });
+}
+
+#[test]
+fn periodic_jobs_meet_deadlines() {
+    sim::run(|| {
+        let count = Arc::new(AtomicU32::new(0));
+        let stats = Arc::new(Mutex::new(None));
+        let task_count = count.clone();
+        let task_stats = stats.clone();
+        sim::spawn_edf(c"Periodic", 20, edf_param(10, 3, 0), move || {
+            for _ in 0..5 {
+                task_count.fetch_add(1, Ordering::SeqCst);
+                task_edf_wait_next_period().unwrap();
+            }
+            *task_stats.lock().unwrap() = Some(task_edf_stats_get(get_current_task_id()).unwrap());
+        })
+        .unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        task_delay(60).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 5);
+        let stats = stats.lock().unwrap().unwrap();
+        assert_eq!(stats.jobs, 6, "{stats:?}");
+        assert_eq!(stats.misses, 0, "{stats:?}");
+        assert_eq!(stats.overruns, 0, "{stats:?}");
+    });
+}
+
+#[test]
+fn overrun_falls_back_and_counts_miss() {
+    sim::run(|| {
+        let stop = Arc::new(AtomicBool::new(false));
+        let task_stop = stop.clone();
+        // 预算耗尽后以低于测试任务的优先级继续运行
+        let task = sim::spawn_edf(c"Overrun", 20, edf_param(10, 2, 5), move || {
+            while !task_stop.load(Ordering::SeqCst) {
+                get_tick_count();
+            }
+        })
+        .unwrap();
+        // 测试任务只有在EDF任务耗尽预算后才能运行
+        task_delay(30).unwrap();
+        stop.store(true, Ordering::SeqCst);
+        let stats = task_edf_stats_get(task).unwrap();
+        assert!(stats.jobs >= 3, "{stats:?}");
+        assert!(stats.overruns >= 2, "{stats:?}");
+        assert!(stats.misses >= 2, "{stats:?}");
+    });
+}
+
+#[test]
+fn late_completion_is_a_miss() {
+    sim::run(|| {
+        let stats = Arc::new(Mutex::new(None));
+        let task_stats = stats.clone();
+        sim::spawn_edf(c"Late", 5, edf_param(50, 10, 10), move || {
+            busy_wait(15);
+            task_edf_wait_next_period().unwrap();
+            *task_stats.lock().unwrap() = Some(task_edf_stats_get(get_current_task_id()).unwrap());
+        })
+        .unwrap();
+        task_delay(70).unwrap();
+        let stats = stats.lock().unwrap().unwrap();
+        assert_eq!(stats.misses, 1, "{stats:?}");
+        assert_eq!(stats.overruns, 1, "{stats:?}");
+    });
+}
+
+#[test]
+fn deleted_task_stops_job_releases() {
+    sim::run(|| {
+        let count = Arc::new(AtomicU32::new(0));
+        let task_count = count.clone();
+        let task = sim::spawn_edf(c"Deleted", 20, edf_param(5, 1, 0), move || {
+            loop {
+                task_count.fetch_add(1, Ordering::SeqCst);
+                task_edf_wait_next_period().unwrap();
+            }
+        })
+        .unwrap();
+        task_delay(12).unwrap();
+        task_delete(task).unwrap();
+        let released = count.load(Ordering::SeqCst);
+        assert!(released >= 2, "released {released} jobs");
+        // 复用控制块的普通任务不受之前的EDF状态影响
+        let other = sim::spawn(c"Reuse", 20, || task_delay(20).unwrap()).unwrap();
+        task_delay(20).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), released);
+        assert_eq!(
+            task_edf_stats_get(other),
+            Err(SystemError::Task(TaskError::NotEdfTask))
+        );
+    });
+}
+
+#[test]
+fn edf_param_and_api_errors() {
+    sim::run(|| {
+        assert_eq!(
+            sim::spawn_edf(c"Bad", 20, edf_param(10, 20, 0), || {}),
+            Err(SystemError::Task(TaskError::EdfParamError))
+        );
+        assert_eq!(
+            sim::spawn_edf(c"Bad", 20, edf_param(10, 5, 20), || {}),
+            Err(SystemError::Task(TaskError::EdfParamError))
+        );
+        assert_eq!(
+            task_edf_stats_get(get_current_task_id()),
+            Err(SystemError::Task(TaskError::NotEdfTask))
+        );
+        assert_eq!(
+            task_edf_wait_next_period(),
+            Err(SystemError::Task(TaskError::NotEdfTask))
+        );
+        task_lock();
+        assert_eq!(
+            task_edf_wait_next_period(),
+            Err(SystemError::Task(TaskError::EdfWaitInLock))
+        );
+        task_unlock();
+    });
+}
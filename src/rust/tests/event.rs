@@ -0,0 +1,238 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    event::{
+        core::{event_clear, event_destroy, event_init, event_read, event_write},
+        error::EventError,
+        group::{
+            event_group_clear, event_group_destroy, event_group_get, event_group_init,
+            event_group_set, event_group_sync, event_group_wait, event_group_wait_cleared,
+        },
+        types::{EventCB, EventGroupCB, EventWaitMode},
+    },
+    result::SystemError,
+    sim,
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, AtomicU64, Ordering},
+};
+
+struct SharedEvent(*mut EventCB);
+
+unsafe impl Send for SharedEvent {}
+
+impl SharedEvent {
+    fn get(&self) -> &'static mut EventCB {
+        unsafe { &mut *self.0 }
+    }
+}
+
+struct SharedGroup(*mut EventGroupCB);
+
+unsafe impl Send for SharedGroup {}
+
+impl SharedGroup {
+    fn get(&self) -> &'static mut EventGroupCB {
+        unsafe { &mut *self.0 }
+    }
+}
+
+fn new_group() -> &'static mut EventGroupCB {
+    let group = Box::leak(Box::new(EventGroupCB::new()));
+    event_group_init(group);
+    group
+}
+
+fn new_event() -> &'static mut EventCB {
+    let event = Box::leak(Box::new(EventCB::new()));
+    event_init(event);
+    event
+}
+
+#[test]
+fn or_mode_wakes_on_any_bit() {
+    sim::run(|| {
+        let event = new_event();
+        let shared = SharedEvent(event);
+        let seen = Arc::new(AtomicU32::new(0));
+        let child_seen = seen.clone();
+        sim::spawn(c"Reader", 5, move || {
+            let events =
+                event_read(shared.get(), 0b110, EventWaitMode::Or as u32, u32::MAX).unwrap();
+            child_seen.store(events, Ordering::SeqCst);
+        })
+        .unwrap();
+        event_write(event, 0b100).unwrap();
+        assert_eq!(seen.load(Ordering::SeqCst), 0b100);
+        event_destroy(event).unwrap();
+    });
+}
+
+#[test]
+fn and_mode_waits_for_all_bits() {
+    sim::run(|| {
+        let event = new_event();
+        let shared = SharedEvent(event);
+        let seen = Arc::new(AtomicU32::new(0));
+        let child_seen = seen.clone();
+        sim::spawn(c"Reader", 5, move || {
+            let events =
+                event_read(shared.get(), 0b11, EventWaitMode::And as u32, u32::MAX).unwrap();
+            child_seen.store(events, Ordering::SeqCst);
+        })
+        .unwrap();
+        event_write(event, 0b01).unwrap();
+        assert_eq!(seen.load(Ordering::SeqCst), 0);
+        event_write(event, 0b10).unwrap();
+        assert_eq!(seen.load(Ordering::SeqCst), 0b11);
+        event_destroy(event).unwrap();
+    });
+}
+
+#[test]
+fn clear_mode_consumes_bits() {
+    sim::run(|| {
+        let event = new_event();
+        event_write(event, 0b101).unwrap();
+        let mode = EventWaitMode::Or as u32 | EventWaitMode::Clear as u32;
+        assert_eq!(event_read(event, 0b001, mode, 1).unwrap(), 0b001);
+        assert_eq!(event.event_id, 0b100);
+        event_clear(event, !0b100);
+        assert_eq!(event.event_id, 0);
+        event_destroy(event).unwrap();
+    });
+}
+
+#[test]
+fn read_times_out() {
+    sim::run(|| {
+        let event = new_event();
+        assert_eq!(
+            event_read(event, 0b1, EventWaitMode::Or as u32, 3),
+            Err(SystemError::Event(EventError::ReadTimeout))
+        );
+        event_destroy(event).unwrap();
+    });
+}
+
+#[test]
+fn read_without_timeout_returns_immediately() {
+    sim::run(|| {
+        let event = new_event();
+        assert_eq!(
+            event_read(event, 0b1, EventWaitMode::Or as u32, 0).unwrap(),
+            0
+        );
+        // 返回后中断应已恢复，Tick继续推进
+        let start = rust::tick::global::get_current_tick_count();
+        rust::task::manager::delay::task_delay(2).unwrap();
+        assert!(rust::tick::global::get_current_tick_count() > start);
+        event_destroy(event).unwrap();
+    });
+}
+
+#[test]
+fn group_wakes_on_high_bits() {
+    sim::run(|| {
+        let group = new_group();
+        let shared = SharedGroup(group);
+        let seen = Arc::new(AtomicU64::new(0));
+        let child_seen = seen.clone();
+        sim::spawn(c"Waiter", 5, move || {
+            let mode = EventWaitMode::And as u32 | EventWaitMode::Clear as u32;
+            let bits = event_group_wait(shared.get(), 1 << 63 | 1 << 40, mode, u32::MAX).unwrap();
+            child_seen.store(bits, Ordering::SeqCst);
+        })
+        .unwrap();
+        wait_until(|| !group.is_wait_list_empty());
+        event_group_set(group, 1 << 63);
+        assert_eq!(seen.load(Ordering::SeqCst), 0);
+        event_group_set(group, 1 << 40 | 1);
+        wait_until(|| seen.load(Ordering::SeqCst) != 0);
+        assert_eq!(seen.load(Ordering::SeqCst), 1 << 63 | 1 << 40 | 1);
+        assert_eq!(event_group_get(group), 1);
+        event_group_destroy(group).unwrap();
+    });
+}
+
+#[test]
+fn group_sync_releases_all_parties() {
+    sim::run(|| {
+        let group = new_group();
+        let done = Arc::new(AtomicU32::new(0));
+        for bit in 0..2 {
+            let shared = SharedGroup(group);
+            let child_done = done.clone();
+            sim::spawn(c"Party", 5, move || {
+                let bits = event_group_sync(shared.get(), 1 << bit, 0b111, u32::MAX).unwrap();
+                assert_eq!(bits & 0b111, 0b111);
+                child_done.fetch_add(1, Ordering::SeqCst);
+            })
+            .unwrap();
+        }
+        let bits = event_group_sync(group, 0b100, 0b111, u32::MAX).unwrap();
+        assert_eq!(bits & 0b111, 0b111);
+        wait_until(|| done.load(Ordering::SeqCst) == 2);
+        assert_eq!(event_group_get(group), 0);
+        event_group_destroy(group).unwrap();
+    });
+}
+
+#[test]
+fn group_waits_for_bits_cleared() {
+    sim::run(|| {
+        let group = new_group();
+        event_group_set(group, 0b111);
+        let shared = SharedGroup(group);
+        let seen = Arc::new(AtomicU64::new(u64::MAX));
+        let child_seen = seen.clone();
+        sim::spawn(c"Waiter", 5, move || {
+            let bits =
+                event_group_wait_cleared(shared.get(), 0b11, EventWaitMode::And as u32, u32::MAX)
+                    .unwrap();
+            child_seen.store(bits, Ordering::SeqCst);
+        })
+        .unwrap();
+        wait_until(|| !group.is_wait_list_empty());
+        assert_eq!(event_group_clear(group, 0b01), 0b110);
+        assert_eq!(seen.load(Ordering::SeqCst), u64::MAX);
+        event_group_clear(group, 0b10);
+        wait_until(|| seen.load(Ordering::SeqCst) != u64::MAX);
+        assert_eq!(seen.load(Ordering::SeqCst), 0b100);
+        // 或模式下任意一位已清除即满足
+        let mode = EventWaitMode::Or as u32;
+        assert_eq!(
+            event_group_wait_cleared(group, 0b101, mode, 0).unwrap(),
+            0b100
+        );
+        event_group_destroy(group).unwrap();
+    });
+}
+
+#[test]
+fn group_rejects_invalid_wait() {
+    sim::run(|| {
+        let group = new_group();
+        let mode = EventWaitMode::Or as u32;
+        assert_eq!(
+            event_group_wait(group, 0, mode, 0),
+            Err(SystemError::Event(EventError::MaskInvalid))
+        );
+        let clear_mode = mode | EventWaitMode::Clear as u32;
+        assert_eq!(
+            event_group_wait_cleared(group, 0b1, clear_mode, 0),
+            Err(SystemError::Event(EventError::ModeInvalid))
+        );
+        assert_eq!(
+            event_group_wait(group, 0b1, mode, 0),
+            Err(SystemError::Event(EventError::ReadTimeout))
+        );
+        assert_eq!(
+            event_group_wait(group, 0b1, mode, 3),
+            Err(SystemError::Event(EventError::ReadTimeout))
+        );
+        event_group_destroy(group).unwrap();
+    });
+}
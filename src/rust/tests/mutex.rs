@@ -0,0 +1,562 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    mutex::{
+        core::{
+            mutex_consistent, mutex_create, mutex_create_with_attr, mutex_delete,
+            mutex_owner_policy_set, mutex_pend, mutex_post, mutex_wait_mode_set,
+        },
+        error::MutexError,
+        types::{MutexAttr, MutexId, MutexProtocol, MutexWaitMode},
+    },
+    result::SystemError,
+    sim,
+    task::{
+        error::TaskError,
+        info::get_current_task_id,
+        manager::{
+            delay::task_delay,
+            delete::task_delete,
+            priority::{get_task_priority, set_task_priority},
+        },
+        resource::OwnerDeadPolicy,
+    },
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+#[test]
+fn lock_and_unlock() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn recursive_lock() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        mutex_post(mutex).unwrap();
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn pend_times_out_while_owned() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        mutex_pend(mutex, 0).unwrap();
+        sim::spawn(c"Waiter", 5, move || {
+            *child_result.lock().unwrap() = Some(mutex_pend(mutex, 5));
+        })
+        .unwrap();
+        task_delay(10).unwrap();
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Mutex(MutexError::Timeout)))
+        );
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn post_hands_over_to_waiter() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        let acquired = Arc::new(AtomicU32::new(0));
+        let child_acquired = acquired.clone();
+        mutex_pend(mutex, 0).unwrap();
+        sim::spawn(c"Waiter", 5, move || {
+            mutex_pend(mutex, u32::MAX).unwrap();
+            child_acquired.store(1, Ordering::SeqCst);
+            mutex_post(mutex).unwrap();
+        })
+        .unwrap();
+        assert_eq!(acquired.load(Ordering::SeqCst), 0);
+        mutex_post(mutex).unwrap();
+        assert_eq!(acquired.load(Ordering::SeqCst), 1);
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn owner_inherits_waiter_priority() {
+    sim::run_with_priority(20, || {
+        let mutex = mutex_create().unwrap();
+        let me = get_current_task_id();
+        mutex_pend(mutex, 0).unwrap();
+        sim::spawn(c"High", 5, move || {
+            mutex_pend(mutex, u32::MAX).unwrap();
+            mutex_post(mutex).unwrap();
+        })
+        .unwrap();
+        assert_eq!(get_task_priority(me).unwrap(), 5);
+        mutex_post(mutex).unwrap();
+        assert_eq!(get_task_priority(me).unwrap(), 20);
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn delete_fails_while_locked() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        assert_eq!(
+            mutex_delete(mutex),
+            Err(SystemError::Mutex(MutexError::Pended))
+        );
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn deleting_owner_releases_mutex() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        let holder = sim::spawn(c"Holder", 5, move || {
+            mutex_pend(mutex, 0).unwrap();
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Waiter", 6, move || {
+            *child_result.lock().unwrap() = Some(mutex_pend(mutex, u32::MAX));
+        })
+        .unwrap();
+        assert_eq!(*result.lock().unwrap(), None);
+
+        // 持有者被删除后等待者以OwnerDead返回，互斥锁回到未持有状态
+        task_delete(holder).unwrap();
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Task(TaskError::OwnerDead)))
+        );
+        mutex_pend(mutex, 0).unwrap();
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn deleting_owner_destroys_mutex() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        mutex_owner_policy_set(mutex, OwnerDeadPolicy::Destroy).unwrap();
+        assert_eq!(
+            mutex_owner_policy_set(mutex, OwnerDeadPolicy::Orphan),
+            Err(SystemError::Task(TaskError::OwnerPolicyInvalid))
+        );
+        let holder = sim::spawn(c"Holder", 5, move || {
+            mutex_pend(mutex, 0).unwrap();
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+
+        task_delete(holder).unwrap();
+        assert!(mutex_pend(mutex, 0).is_err());
+    });
+}
+
+#[test]
+fn robust_mutex_passes_to_next_waiter_when_owner_dies() {
+    sim::run(|| {
+        let attr = MutexAttr {
+            robust: true,
+            ..Default::default()
+        };
+        let mutex = mutex_create_with_attr(&attr).unwrap();
+        let holder = sim::spawn(c"Holder", 5, move || {
+            mutex_pend(mutex, 0).unwrap();
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Waiter", 6, move || {
+            let ret = mutex_pend(mutex, u32::MAX);
+            // 接管后修复状态并解锁
+            let consistent = mutex_consistent(mutex);
+            mutex_post(mutex).unwrap();
+            *child_result.lock().unwrap() = Some((ret, consistent));
+        })
+        .unwrap();
+
+        task_delete(holder).unwrap();
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some((Err(SystemError::Mutex(MutexError::OwnerDied)), Ok(())))
+        );
+        assert_eq!(
+            mutex_consistent(mutex),
+            Err(SystemError::Mutex(MutexError::Invalid))
+        );
+        mutex_pend(mutex, 0).unwrap();
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn robust_mutex_heir_inherits_from_remaining_waiters() {
+    sim::run(|| {
+        let attr = MutexAttr {
+            robust: true,
+            ..Default::default()
+        };
+        let mutex = mutex_create_with_attr(&attr).unwrap();
+        // 先进先出方式下低优先级的等待者先接管
+        mutex_wait_mode_set(mutex, MutexWaitMode::Fifo).unwrap();
+        let holder = sim::spawn(c"Holder", 5, move || {
+            mutex_pend(mutex, 0).unwrap();
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+
+        let heir_priority = Arc::new(Mutex::new(None));
+        let child_priority = heir_priority.clone();
+        sim::spawn(c"Low", 8, move || {
+            let ret = mutex_pend(mutex, u32::MAX);
+            *child_priority.lock().unwrap() = Some((ret, get_task_priority(get_current_task_id())));
+            mutex_consistent(mutex).unwrap();
+            mutex_post(mutex).unwrap();
+        })
+        .unwrap();
+        let done = Arc::new(AtomicU32::new(0));
+        let high_done = done.clone();
+        sim::spawn(c"High", 6, move || {
+            mutex_pend(mutex, u32::MAX).unwrap();
+            mutex_post(mutex).unwrap();
+            high_done.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        task_delete(holder).unwrap();
+        wait_until(|| done.load(Ordering::SeqCst) == 1);
+        assert_eq!(
+            *heir_priority.lock().unwrap(),
+            Some((Err(SystemError::Mutex(MutexError::OwnerDied)), Ok(6)))
+        );
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn robust_mutex_unlocked_without_consistent_is_not_recoverable() {
+    sim::run(|| {
+        let attr = MutexAttr {
+            robust: true,
+            ..Default::default()
+        };
+        let mutex = mutex_create_with_attr(&attr).unwrap();
+        let holder = sim::spawn(c"Holder", 5, move || {
+            mutex_pend(mutex, 0).unwrap();
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+        task_delete(holder).unwrap();
+
+        // 没有等待者时由下一个获取者接管
+        assert_eq!(
+            mutex_pend(mutex, 0),
+            Err(SystemError::Mutex(MutexError::OwnerDied))
+        );
+
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Waiter", 6, move || {
+            *child_result.lock().unwrap() = Some(mutex_pend(mutex, u32::MAX));
+        })
+        .unwrap();
+        mutex_post(mutex).unwrap();
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Mutex(MutexError::NotRecoverable)))
+        );
+        assert_eq!(
+            mutex_pend(mutex, 0),
+            Err(SystemError::Mutex(MutexError::NotRecoverable))
+        );
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+fn ceiling_attr(ceiling: u16) -> MutexAttr {
+    MutexAttr {
+        protocol: MutexProtocol::Protect,
+        ceiling,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn ceiling_raises_owner_until_unlock() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let outer = mutex_create_with_attr(&ceiling_attr(6)).unwrap();
+        let inner = mutex_create_with_attr(&ceiling_attr(4)).unwrap();
+
+        mutex_pend(outer, 0).unwrap();
+        assert_eq!(get_task_priority(me), Ok(6));
+        mutex_pend(inner, 0).unwrap();
+        assert_eq!(get_task_priority(me), Ok(4));
+
+        // 同优先级的任务在持有期间不能抢占
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        sim::spawn(c"Peer", 5, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+
+        mutex_post(inner).unwrap();
+        assert_eq!(get_task_priority(me), Ok(6));
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+        mutex_post(outer).unwrap();
+        assert_eq!(get_task_priority(me), Ok(sim::SIM_TEST_TASK_PRIORITY));
+
+        mutex_delete(inner).unwrap();
+        mutex_delete(outer).unwrap();
+    });
+}
+
+#[test]
+fn ceiling_restores_on_out_of_order_unlock() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let first = mutex_create_with_attr(&ceiling_attr(6)).unwrap();
+        let second = mutex_create_with_attr(&ceiling_attr(4)).unwrap();
+
+        mutex_pend(first, 0).unwrap();
+        mutex_pend(second, 0).unwrap();
+        assert_eq!(get_task_priority(me), Ok(4));
+
+        // 先释放天花板较低的互斥锁，仍持有的互斥锁决定优先级
+        mutex_post(first).unwrap();
+        assert_eq!(get_task_priority(me), Ok(4));
+        mutex_post(second).unwrap();
+        assert_eq!(get_task_priority(me), Ok(sim::SIM_TEST_TASK_PRIORITY));
+
+        // 先释放天花板较高的互斥锁，回落到仍持有的互斥锁的天花板
+        mutex_pend(first, 0).unwrap();
+        mutex_pend(second, 0).unwrap();
+        mutex_post(second).unwrap();
+        assert_eq!(get_task_priority(me), Ok(6));
+        mutex_post(first).unwrap();
+        assert_eq!(get_task_priority(me), Ok(sim::SIM_TEST_TASK_PRIORITY));
+
+        mutex_delete(second).unwrap();
+        mutex_delete(first).unwrap();
+    });
+}
+
+#[test]
+fn ceiling_rejects_higher_priority_locker() {
+    sim::run(|| {
+        assert_eq!(
+            mutex_create_with_attr(&ceiling_attr(u16::MAX)),
+            Err(SystemError::Mutex(MutexError::CeilingInvalid))
+        );
+
+        let mutex = mutex_create_with_attr(&ceiling_attr(sim::SIM_TEST_TASK_PRIORITY + 2)).unwrap();
+        assert_eq!(
+            mutex_pend(mutex, 0),
+            Err(SystemError::Mutex(MutexError::CeilingViolated))
+        );
+
+        // 天花板提升不改变基础优先级
+        let outer = mutex_create_with_attr(&ceiling_attr(3)).unwrap();
+        mutex_pend(outer, 0).unwrap();
+        assert_eq!(
+            mutex_pend(mutex, 0),
+            Err(SystemError::Mutex(MutexError::CeilingViolated))
+        );
+        mutex_post(outer).unwrap();
+
+        mutex_delete(outer).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn set_priority_while_holding_ceiling_mutex() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let mutex = mutex_create_with_attr(&ceiling_attr(6)).unwrap();
+        mutex_pend(mutex, 0).unwrap();
+
+        // 修改的是基础优先级，持有期间仍保持天花板优先级
+        set_task_priority(me, 12).unwrap();
+        assert_eq!(get_task_priority(me), Ok(6));
+        set_task_priority(me, 4).unwrap();
+        assert_eq!(get_task_priority(me), Ok(4));
+        set_task_priority(me, 8).unwrap();
+        assert_eq!(get_task_priority(me), Ok(6));
+
+        mutex_post(mutex).unwrap();
+        assert_eq!(get_task_priority(me), Ok(8));
+
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+/// 创建阻塞链：Low持有`m2`，Mid持有`m1`并等待`m2`
+fn spawn_blocking_chain(m1: MutexId, m2: MutexId) -> (u32, u32) {
+    let locked = Arc::new(AtomicU32::new(0));
+    let child_locked = locked.clone();
+    let low = sim::spawn(c"Low", 20, move || {
+        mutex_pend(m2, 0).unwrap();
+        child_locked.store(1, Ordering::SeqCst);
+        task_delay(1000).unwrap();
+    })
+    .unwrap();
+    wait_until(|| locked.load(Ordering::SeqCst) == 1);
+    let mid = sim::spawn(c"Mid", 15, move || {
+        mutex_pend(m1, 0).unwrap();
+        let _ = mutex_pend(m2, u32::MAX);
+    })
+    .unwrap();
+    wait_until(|| get_task_priority(low) == Ok(15));
+    (low, mid)
+}
+
+#[test]
+fn inheritance_follows_blocking_chain() {
+    sim::run_with_priority(4, || {
+        let m1 = mutex_create().unwrap();
+        let m2 = mutex_create().unwrap();
+        let (low, mid) = spawn_blocking_chain(m1, m2);
+
+        sim::spawn(c"High", 8, move || {
+            let _ = mutex_pend(m1, 20);
+        })
+        .unwrap();
+        wait_until(|| get_task_priority(mid) == Ok(8));
+        assert_eq!(get_task_priority(low), Ok(8));
+
+        // 等待超时后沿阻塞链撤销提升
+        task_delay(30).unwrap();
+        assert_eq!(get_task_priority(mid), Ok(15));
+        assert_eq!(get_task_priority(low), Ok(15));
+
+        task_delete(mid).unwrap();
+        task_delete(low).unwrap();
+        mutex_delete(m1).unwrap();
+        mutex_delete(m2).unwrap();
+    });
+}
+
+#[test]
+fn waiter_priority_change_updates_blocking_chain() {
+    sim::run_with_priority(4, || {
+        let m1 = mutex_create().unwrap();
+        let m2 = mutex_create().unwrap();
+        let (low, mid) = spawn_blocking_chain(m1, m2);
+
+        let high = sim::spawn(c"High", 8, move || {
+            let _ = mutex_pend(m1, u32::MAX);
+        })
+        .unwrap();
+        wait_until(|| get_task_priority(mid) == Ok(8));
+
+        set_task_priority(high, 6).unwrap();
+        assert_eq!(get_task_priority(mid), Ok(6));
+        assert_eq!(get_task_priority(low), Ok(6));
+
+        set_task_priority(high, 12).unwrap();
+        assert_eq!(get_task_priority(high), Ok(12));
+        assert_eq!(get_task_priority(mid), Ok(12));
+        assert_eq!(get_task_priority(low), Ok(12));
+
+        task_delete(high).unwrap();
+        task_delete(mid).unwrap();
+        task_delete(low).unwrap();
+        mutex_delete(m1).unwrap();
+        mutex_delete(m2).unwrap();
+    });
+}
+
+/// 当前任务持有互斥锁时先后加入优先级为9和5的等待者，返回各等待者获得锁时的(基础优先级, 当前优先级)
+fn handover_order(mutex: MutexId) -> Vec<(u16, u16)> {
+    let me = get_current_task_id();
+    let order = Arc::new(Mutex::new(Vec::new()));
+    mutex_pend(mutex, 0).unwrap();
+    for priority in [9, 5] {
+        let task_order = order.clone();
+        sim::spawn(c"Waiter", priority, move || {
+            mutex_pend(mutex, u32::MAX).unwrap();
+            let current = get_task_priority(get_current_task_id()).unwrap();
+            task_order.lock().unwrap().push((priority, current));
+            mutex_post(mutex).unwrap();
+        })
+        .unwrap();
+        wait_until(|| get_task_priority(me) == Ok(priority));
+    }
+
+    mutex_post(mutex).unwrap();
+    assert_eq!(get_task_priority(me), Ok(sim::SIM_TEST_TASK_PRIORITY));
+    wait_until(|| order.lock().unwrap().len() == 2);
+    mutex_delete(mutex).unwrap();
+    order.lock().unwrap().clone()
+}
+
+#[test]
+fn priority_wait_mode_hands_over_by_priority() {
+    sim::run(|| {
+        let attr = MutexAttr {
+            wait_mode: MutexWaitMode::Priority,
+            ..Default::default()
+        };
+        let mutex = mutex_create_with_attr(&attr).unwrap();
+        assert_eq!(handover_order(mutex), [(5, 5), (9, 9)]);
+    });
+}
+
+#[test]
+fn fifo_wait_mode_hands_over_in_arrival_order() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        mutex_wait_mode_set(mutex, MutexWaitMode::Fifo).unwrap();
+
+        // 先到的低优先级任务获得锁，并继承仍在等待的高优先级任务的优先级
+        assert_eq!(handover_order(mutex), [(9, 5), (5, 5)]);
+    });
+}
+
+#[test]
+fn wait_mode_cannot_change_with_waiters() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        let waiter = sim::spawn(c"Waiter", 5, move || {
+            let _ = mutex_pend(mutex, u32::MAX);
+        })
+        .unwrap();
+        assert_eq!(
+            mutex_wait_mode_set(mutex, MutexWaitMode::Fifo),
+            Err(SystemError::Mutex(MutexError::Pended))
+        );
+
+        task_delete(waiter).unwrap();
+        mutex_wait_mode_set(mutex, MutexWaitMode::Fifo).unwrap();
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    result::SystemError,
+    sim,
+    task::{
+        error::TaskError,
+        info::get_current_task_id,
+        manager::delay::task_delay,
+        notify::{NotifyAction, task_notify, task_notify_clear, task_notify_take},
+    },
+    timer::{TimerMode, timer_create, timer_delete, timer_start},
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+static TIMER_TARGET: AtomicU32 = AtomicU32::new(0);
+
+extern "C" fn notify_from_timer() {
+    let task_id = TIMER_TARGET.load(Ordering::SeqCst);
+    task_notify(task_id, 0b100, NotifyAction::SetBits).unwrap();
+}
+
+#[test]
+fn notify_wakes_waiting_task() {
+    sim::run(|| {
+        let seen = Arc::new(AtomicU32::new(0));
+        let child_seen = seen.clone();
+        let waiter = sim::spawn(c"Waiter", 5, move || {
+            let value = task_notify_take(true, u32::MAX).unwrap();
+            child_seen.store(value, Ordering::SeqCst);
+        })
+        .unwrap();
+        task_delay(2).unwrap();
+        assert_eq!(seen.load(Ordering::SeqCst), 0);
+        task_notify(waiter, 0b01, NotifyAction::SetBits).unwrap();
+        wait_until(|| seen.load(Ordering::SeqCst) != 0);
+        assert_eq!(seen.load(Ordering::SeqCst), 0b01);
+    });
+}
+
+#[test]
+fn increment_acts_as_counting_semaphore() {
+    sim::run(|| {
+        let task_id = get_current_task_id();
+        for _ in 0..3 {
+            task_notify(task_id, 0, NotifyAction::Increment).unwrap();
+        }
+        assert_eq!(task_notify_take(false, 0), Ok(3));
+        assert_eq!(task_notify_take(false, 0), Ok(2));
+        assert_eq!(task_notify_take(true, 0), Ok(1));
+        assert_eq!(
+            task_notify_take(false, 0),
+            Err(SystemError::Task(TaskError::NotifyTimeout))
+        );
+    });
+}
+
+#[test]
+fn set_if_empty_keeps_pending_value() {
+    sim::run(|| {
+        let task_id = get_current_task_id();
+        task_notify(task_id, 7, NotifyAction::SetIfEmpty).unwrap();
+        assert_eq!(
+            task_notify(task_id, 9, NotifyAction::SetIfEmpty),
+            Err(SystemError::Task(TaskError::NotifyPending))
+        );
+        task_notify(task_id, 0b11, NotifyAction::SetBits).unwrap();
+        assert_eq!(task_notify_clear(task_id), Ok(0b111));
+        task_notify(task_id, 9, NotifyAction::SetIfEmpty).unwrap();
+        task_notify(task_id, 5, NotifyAction::Overwrite).unwrap();
+        assert_eq!(task_notify_take(true, 0), Ok(5));
+    });
+}
+
+#[test]
+fn take_times_out() {
+    sim::run(|| {
+        assert_eq!(
+            task_notify_take(true, 3),
+            Err(SystemError::Task(TaskError::NotifyTimeout))
+        );
+        assert_eq!(
+            NotifyAction::try_from(4),
+            Err(SystemError::Task(TaskError::NotifyActionInvalid))
+        );
+    });
+}
+
+#[test]
+fn notify_from_timer_handler() {
+    sim::run(|| {
+        TIMER_TARGET.store(get_current_task_id(), Ordering::SeqCst);
+        let timer = timer_create(2, TimerMode::NoSelfDelete, Some(notify_from_timer)).unwrap();
+        timer_start(timer).unwrap();
+        assert_eq!(task_notify_take(true, 100), Ok(0b100));
+        timer_delete(timer).unwrap();
+    });
+}
@@ -0,0 +1,75 @@
+use rust::{
+    config::TASK_PRIORITY_LOWEST,
+    mutex::core::{mutex_create, mutex_delete, mutex_pend, mutex_post},
+    result::SystemError,
+    sim,
+    task::{
+        error::TaskError,
+        info::get_current_task_id,
+        manager::{
+            delay::task_delay,
+            priority::{get_task_priority, set_task_priority},
+        },
+    },
+};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn ready_tasks_run_in_priority_order_across_groups() {
+    sim::run(|| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for priority in [200, 40, 254, 100, 33] {
+            let task_order = order.clone();
+            sim::spawn(c"Worker", priority, move || {
+                task_order.lock().unwrap().push(priority)
+            })
+            .unwrap();
+        }
+        task_delay(5).unwrap();
+        assert_eq!(*order.lock().unwrap(), [33, 40, 100, 200, 254]);
+    });
+}
+
+#[test]
+fn priority_range_is_checked() {
+    sim::run(|| {
+        assert_eq!(TASK_PRIORITY_LOWEST, 255);
+        assert_eq!(
+            sim::spawn(c"Invalid", TASK_PRIORITY_LOWEST + 1, || {}),
+            Err(SystemError::Task(TaskError::PriorityError))
+        );
+
+        let me = get_current_task_id();
+        let original = get_task_priority(me).unwrap();
+        set_task_priority(me, 128).unwrap();
+        assert_eq!(get_task_priority(me), Ok(128));
+        set_task_priority(me, original).unwrap();
+    });
+}
+
+#[test]
+fn mutex_inheritance_across_groups() {
+    sim::run(|| {
+        let mutex = mutex_create().unwrap();
+        let me = get_current_task_id();
+        let original = get_task_priority(me).unwrap();
+        set_task_priority(me, 150).unwrap();
+        mutex_pend(mutex, 0).unwrap();
+
+        // 高优先级任务等待互斥锁时，持有者继承其优先级
+        let waiter = sim::spawn(c"Waiter", 20, move || {
+            mutex_pend(mutex, u32::MAX).unwrap();
+            mutex_post(mutex).unwrap();
+        })
+        .unwrap();
+        assert_eq!(get_task_priority(me), Ok(20));
+
+        // 释放后恢复原始优先级
+        mutex_post(mutex).unwrap();
+        assert_eq!(get_task_priority(me), Ok(150));
+        assert!(get_task_priority(waiter).is_err());
+
+        set_task_priority(me, original).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
@@ -0,0 +1,175 @@
+use rust::{
+    queue::{
+        error::QueueError,
+        management::{create_queue, create_queue_static, delete_queue, queue_static_mem_size},
+        operation::{queue_read, queue_write, queue_write_head},
+    },
+    result::SystemError,
+    sim,
+    task::manager::delay::task_delay,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::{Arc, Mutex},
+};
+
+thread_local! {
+    /// 当前线程开始计数后的堆分配与释放次数
+    static HEAP_OPS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// 统计开始计数的线程上的堆操作，其余线程不受影响
+struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count() {
+        let _ = HEAP_OPS.try_with(|ops| {
+            if let Some(count) = ops.get() {
+                ops.set(Some(count + 1));
+            }
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::count();
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn write_then_read_in_order() {
+    sim::run(|| {
+        let queue = create_queue(4, 8).unwrap();
+        queue_write(queue, &mut [1, 2, 3], 0).unwrap();
+        queue_write(queue, &mut [4, 5], 0).unwrap();
+        queue_write_head(queue, &mut [9], 0).unwrap();
+
+        let mut buffer = [0u8; 8];
+        assert_eq!(queue_read(queue, &mut buffer, 0).unwrap(), 1);
+        assert_eq!(buffer[..1], [9]);
+        assert_eq!(queue_read(queue, &mut buffer, 0).unwrap(), 3);
+        assert_eq!(buffer[..3], [1, 2, 3]);
+        assert_eq!(queue_read(queue, &mut buffer, 0).unwrap(), 2);
+        assert_eq!(buffer[..2], [4, 5]);
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn read_empty_times_out() {
+    sim::run(|| {
+        let queue = create_queue(1, 4).unwrap();
+        let mut buffer = [0u8; 4];
+        assert_eq!(
+            queue_read(queue, &mut buffer, 0),
+            Err(SystemError::Queue(QueueError::IsEmpty))
+        );
+        assert_eq!(
+            queue_read(queue, &mut buffer, 3),
+            Err(SystemError::Queue(QueueError::Timeout))
+        );
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn write_full_times_out() {
+    sim::run(|| {
+        let queue = create_queue(1, 4).unwrap();
+        queue_write(queue, &mut [1], 0).unwrap();
+        assert_eq!(
+            queue_write(queue, &mut [2], 0),
+            Err(SystemError::Queue(QueueError::IsFull))
+        );
+        assert_eq!(
+            queue_write(queue, &mut [2], 3),
+            Err(SystemError::Queue(QueueError::Timeout))
+        );
+        let mut buffer = [0u8; 4];
+        queue_read(queue, &mut buffer, 0).unwrap();
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn blocked_reader_receives_message() {
+    sim::run(|| {
+        let queue = create_queue(2, 4).unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let child_received = received.clone();
+        sim::spawn(c"Reader", 5, move || {
+            let mut buffer = [0u8; 4];
+            let size = queue_read(queue, &mut buffer, u32::MAX).unwrap();
+            child_received
+                .lock()
+                .unwrap()
+                .extend_from_slice(&buffer[..size]);
+        })
+        .unwrap();
+        assert!(received.lock().unwrap().is_empty());
+        queue_write(queue, &mut [7, 8], 0).unwrap();
+        assert_eq!(*received.lock().unwrap(), [7, 8]);
+        task_delay(1).unwrap();
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn static_queue_uses_caller_storage() {
+    sim::run(|| {
+        let size = queue_static_mem_size(2, 4);
+        let mem: &'static mut [u8] = Box::leak(vec![0u8; size].into_boxed_slice());
+        let queue = create_queue_static(2, 4, mem).unwrap();
+        queue_write(queue, &mut [7, 8], 0).unwrap();
+        queue_write(queue, &mut [9], 0).unwrap();
+        assert_eq!(
+            queue_write(queue, &mut [1], 0),
+            Err(SystemError::Queue(QueueError::IsFull))
+        );
+        let mut buffer = [0u8; 4];
+        assert_eq!(queue_read(queue, &mut buffer, 0).unwrap(), 2);
+        assert_eq!(buffer[..2], [7, 8]);
+        assert_eq!(queue_read(queue, &mut buffer, 0).unwrap(), 1);
+        assert_eq!(buffer[0], 9);
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn static_queue_create_and_delete_skip_the_heap() {
+    sim::run(|| {
+        let size = queue_static_mem_size(2, 4);
+        let mems: [&'static mut [u8]; 2] =
+            core::array::from_fn(|_| Box::leak(vec![0u8; size].into_boxed_slice()));
+
+        HEAP_OPS.set(Some(0));
+        for mem in mems {
+            let queue = create_queue_static(2, 4, mem).unwrap();
+            delete_queue(queue).unwrap();
+        }
+        assert_eq!(HEAP_OPS.replace(None), Some(0));
+    });
+}
+
+#[test]
+fn static_queue_rejects_small_storage() {
+    sim::run(|| {
+        let size = queue_static_mem_size(2, 4);
+        let mem: &'static mut [u8] = Box::leak(vec![0u8; size - 1].into_boxed_slice());
+        assert_eq!(
+            create_queue_static(2, 4, mem),
+            Err(SystemError::Queue(QueueError::CreateNoMemory))
+        );
+    });
+}
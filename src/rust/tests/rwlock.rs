@@ -0,0 +1,314 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    config::RWLOCK_READ_HOLD_LIMIT,
+    result::SystemError,
+    rwlock::{
+        core::{rwlock_create, rwlock_delete, rwlock_rdlock, rwlock_unlock, rwlock_wrlock},
+        error::RwlockError,
+        types::{RwlockId, RwlockPolicy},
+    },
+    sim,
+    task::{
+        error::TaskError,
+        info::get_current_task_id,
+        manager::{delay::task_delay, delete::task_delete, priority::get_task_priority},
+        resource::task_reclaim_report_pop,
+    },
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+#[test]
+fn readers_share_and_writer_is_exclusive() {
+    sim::run(|| {
+        let rwlock = rwlock_create(RwlockPolicy::default()).unwrap();
+        rwlock_rdlock(rwlock, 0).unwrap();
+        rwlock_rdlock(rwlock, 0).unwrap();
+        assert_eq!(
+            rwlock_wrlock(rwlock, 0),
+            Err(SystemError::Rwlock(RwlockError::Deadlock))
+        );
+        rwlock_unlock(rwlock).unwrap();
+        rwlock_unlock(rwlock).unwrap();
+
+        rwlock_wrlock(rwlock, 0).unwrap();
+        rwlock_wrlock(rwlock, 0).unwrap();
+        assert_eq!(
+            rwlock_rdlock(rwlock, 0),
+            Err(SystemError::Rwlock(RwlockError::Deadlock))
+        );
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Reader", 5, move || {
+            *child_result.lock().unwrap() = Some(rwlock_rdlock(rwlock, 0));
+        })
+        .unwrap();
+        wait_until(|| result.lock().unwrap().is_some());
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Rwlock(RwlockError::Unavailable)))
+        );
+        rwlock_unlock(rwlock).unwrap();
+        rwlock_unlock(rwlock).unwrap();
+        rwlock_delete(rwlock).unwrap();
+    });
+}
+
+#[test]
+fn writer_times_out_while_read_locked() {
+    sim::run(|| {
+        let rwlock = rwlock_create(RwlockPolicy::default()).unwrap();
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        rwlock_rdlock(rwlock, 0).unwrap();
+        sim::spawn(c"Writer", 5, move || {
+            *child_result.lock().unwrap() = Some(rwlock_wrlock(rwlock, 5));
+        })
+        .unwrap();
+        wait_until(|| result.lock().unwrap().is_some());
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Rwlock(RwlockError::Timeout)))
+        );
+        rwlock_unlock(rwlock).unwrap();
+        rwlock_delete(rwlock).unwrap();
+    });
+}
+
+/// 持有读锁时让一个写者等待，返回此时新的读者能否加锁
+fn reader_admitted_with_writer_waiting(policy: RwlockPolicy) -> bool {
+    let rwlock = rwlock_create(policy).unwrap();
+    let state = Arc::new(AtomicU32::new(0));
+    let child_state = state.clone();
+    rwlock_rdlock(rwlock, 0).unwrap();
+    sim::spawn(c"Writer", 5, move || {
+        child_state.store(1, Ordering::SeqCst);
+        rwlock_wrlock(rwlock, u32::MAX).unwrap();
+        child_state.store(2, Ordering::SeqCst);
+        rwlock_unlock(rwlock).unwrap();
+    })
+    .unwrap();
+    wait_until(|| state.load(Ordering::SeqCst) == 1);
+    task_delay(2).unwrap();
+
+    // 已持有读锁的任务递归加锁不受等待的写者影响
+    rwlock_rdlock(rwlock, 0).unwrap();
+    rwlock_unlock(rwlock).unwrap();
+
+    // 新的读者由另一个任务充当
+    let result = Arc::new(Mutex::new(None));
+    let child_result = result.clone();
+    sim::spawn(c"Reader", 5, move || {
+        let admitted = rwlock_rdlock(rwlock, 0).is_ok();
+        if admitted {
+            rwlock_unlock(rwlock).unwrap();
+        }
+        *child_result.lock().unwrap() = Some(admitted);
+    })
+    .unwrap();
+    wait_until(|| result.lock().unwrap().is_some());
+    let admitted = result.lock().unwrap().unwrap();
+    assert_eq!(state.load(Ordering::SeqCst), 1);
+    rwlock_unlock(rwlock).unwrap();
+    wait_until(|| state.load(Ordering::SeqCst) == 2);
+    rwlock_delete(rwlock).unwrap();
+    admitted
+}
+
+#[test]
+fn writer_preferred_blocks_new_readers() {
+    sim::run(|| {
+        assert!(reader_admitted_with_writer_waiting(
+            RwlockPolicy::ReaderPreferred
+        ));
+        assert!(!reader_admitted_with_writer_waiting(
+            RwlockPolicy::WriterPreferred
+        ));
+    });
+}
+
+/// 创建在读写锁上等待的高优先级读者，返回记录等待结果的位置
+fn spawn_reader(rwlock: RwlockId, timeout: u32) -> Arc<Mutex<Option<Result<(), SystemError>>>> {
+    let result = Arc::new(Mutex::new(None));
+    let child_result = result.clone();
+    sim::spawn(c"High", 5, move || {
+        let ret = rwlock_rdlock(rwlock, timeout);
+        if ret.is_ok() {
+            rwlock_unlock(rwlock).unwrap();
+        }
+        *child_result.lock().unwrap() = Some(ret);
+    })
+    .unwrap();
+    result
+}
+
+#[test]
+fn writer_inherits_waiter_priority() {
+    sim::run_with_priority(20, || {
+        let rwlock = rwlock_create(RwlockPolicy::default()).unwrap();
+        let me = get_current_task_id();
+        rwlock_wrlock(rwlock, 0).unwrap();
+
+        // 等待者超时后撤销继承
+        let result = spawn_reader(rwlock, 5);
+        wait_until(|| get_task_priority(me) == Ok(5));
+        wait_until(|| result.lock().unwrap().is_some());
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Rwlock(RwlockError::Timeout)))
+        );
+        assert_eq!(get_task_priority(me).unwrap(), 20);
+
+        // 解锁时撤销继承并把读锁交给等待者
+        let result = spawn_reader(rwlock, u32::MAX);
+        wait_until(|| get_task_priority(me) == Ok(5));
+        rwlock_unlock(rwlock).unwrap();
+        assert_eq!(get_task_priority(me).unwrap(), 20);
+        wait_until(|| result.lock().unwrap().is_some());
+        assert_eq!(*result.lock().unwrap(), Some(Ok(())));
+        rwlock_delete(rwlock).unwrap();
+    });
+}
+
+#[test]
+fn unlock_and_delete_errors() {
+    sim::run(|| {
+        assert_eq!(
+            rwlock_unlock(rwlock_create(RwlockPolicy::default()).unwrap()),
+            Err(SystemError::Rwlock(RwlockError::NotLocked))
+        );
+        let rwlock = rwlock_create(RwlockPolicy::WriterPreferred).unwrap();
+        rwlock_rdlock(rwlock, 0).unwrap();
+        assert_eq!(
+            rwlock_delete(rwlock),
+            Err(SystemError::Rwlock(RwlockError::Pended))
+        );
+        rwlock_unlock(rwlock).unwrap();
+        rwlock_delete(rwlock).unwrap();
+        assert_eq!(
+            rwlock_rdlock(rwlock, 0),
+            Err(SystemError::Rwlock(RwlockError::Invalid))
+        );
+    });
+}
+
+#[test]
+fn unlock_requires_holding_a_lock() {
+    sim::run(|| {
+        let rwlock = rwlock_create(RwlockPolicy::default()).unwrap();
+        rwlock_rdlock(rwlock, 0).unwrap();
+
+        // 没有加锁的任务不能释放别人的读锁
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Other", 5, move || {
+            *child_result.lock().unwrap() = Some(rwlock_unlock(rwlock));
+        })
+        .unwrap();
+        wait_until(|| result.lock().unwrap().is_some());
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Rwlock(RwlockError::NotOwner)))
+        );
+
+        // 持有读锁的任务再加写锁会等待自己
+        assert_eq!(
+            rwlock_wrlock(rwlock, u32::MAX),
+            Err(SystemError::Rwlock(RwlockError::Deadlock))
+        );
+        rwlock_unlock(rwlock).unwrap();
+        assert_eq!(
+            rwlock_unlock(rwlock),
+            Err(SystemError::Rwlock(RwlockError::NotLocked))
+        );
+        rwlock_delete(rwlock).unwrap();
+    });
+}
+
+#[test]
+fn read_holds_are_limited_per_task() {
+    sim::run(|| {
+        let rwlocks: Vec<RwlockId> = (0..=RWLOCK_READ_HOLD_LIMIT)
+            .map(|_| rwlock_create(RwlockPolicy::default()).unwrap())
+            .collect();
+        for &rwlock in &rwlocks[..RWLOCK_READ_HOLD_LIMIT] {
+            rwlock_rdlock(rwlock, 0).unwrap();
+        }
+        // 递归加锁不占用新的记录
+        rwlock_rdlock(rwlocks[0], 0).unwrap();
+        assert_eq!(
+            rwlock_rdlock(rwlocks[RWLOCK_READ_HOLD_LIMIT], 0),
+            Err(SystemError::Rwlock(RwlockError::ReadHoldFull))
+        );
+        rwlock_unlock(rwlocks[0]).unwrap();
+        rwlock_unlock(rwlocks[0]).unwrap();
+        rwlock_rdlock(rwlocks[RWLOCK_READ_HOLD_LIMIT], 0).unwrap();
+        for &rwlock in &rwlocks[1..] {
+            rwlock_unlock(rwlock).unwrap();
+        }
+        for rwlock in rwlocks {
+            rwlock_delete(rwlock).unwrap();
+        }
+    });
+}
+
+/// 持有读写锁的任务被删除时另一个任务在等待，返回等待者的加锁结果
+fn delete_holder_with_waiter(
+    rwlock: RwlockId,
+    hold_write: bool,
+    wait_write: bool,
+) -> Option<Result<(), SystemError>> {
+    let holder = sim::spawn(c"Holder", 5, move || {
+        if hold_write {
+            rwlock_wrlock(rwlock, 0).unwrap();
+        } else {
+            rwlock_rdlock(rwlock, 0).unwrap();
+            rwlock_rdlock(rwlock, 0).unwrap();
+        }
+        task_delay(1000).unwrap();
+    })
+    .unwrap();
+    let result = Arc::new(Mutex::new(None));
+    let child_result = result.clone();
+    sim::spawn(c"Waiter", 6, move || {
+        let ret = if wait_write {
+            rwlock_wrlock(rwlock, u32::MAX)
+        } else {
+            rwlock_rdlock(rwlock, u32::MAX)
+        };
+        *child_result.lock().unwrap() = Some(ret);
+    })
+    .unwrap();
+
+    task_delete(holder).unwrap();
+    let report = task_reclaim_report_pop().unwrap();
+    assert_eq!((report.task_id, report.rwlocks), (holder, 1));
+    result.lock().unwrap().take()
+}
+
+#[test]
+fn deleting_holder_releases_rwlock() {
+    sim::run(|| {
+        while task_reclaim_report_pop().is_some() {}
+        let rwlock = rwlock_create(RwlockPolicy::WriterPreferred).unwrap();
+
+        // 被删除的写者和读者都不再持有读写锁，等待者返回OwnerDead
+        for (hold_write, wait_write) in [(true, false), (false, true)] {
+            assert_eq!(
+                delete_holder_with_waiter(rwlock, hold_write, wait_write),
+                Some(Err(SystemError::Task(TaskError::OwnerDead)))
+            );
+            assert_eq!(
+                rwlock_unlock(rwlock),
+                Err(SystemError::Rwlock(RwlockError::NotLocked))
+            );
+            rwlock_wrlock(rwlock, 0).unwrap();
+            rwlock_unlock(rwlock).unwrap();
+        }
+        rwlock_delete(rwlock).unwrap();
+    });
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use common::wait_until;
+use rust::{
+    event::{
+        group::{event_group_init, event_group_set},
+        types::EventGroupCB,
+    },
+    queue::{
+        management::{create_queue, delete_queue},
+        operation::{queue_read, queue_write},
+    },
+    result::SystemError,
+    select::{core::wait_any, error::SelectError, types::SelectObject},
+    semaphore::core::{create_semaphore, delete_semaphore, semaphore_pend, semaphore_post},
+    sim,
+    task::manager::{delay::task_delay, delete::task_delete},
+    timer::{TimerMode, timer_create, timer_delete, timer_start},
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+struct SharedGroup(*mut EventGroupCB);
+
+unsafe impl Send for SharedGroup {}
+
+impl SharedGroup {
+    fn get(&self) -> &'static mut EventGroupCB {
+        unsafe { &mut *self.0 }
+    }
+}
+
+fn new_group() -> &'static mut EventGroupCB {
+    let group = Box::leak(Box::new(EventGroupCB::new()));
+    event_group_init(group);
+    group
+}
+
+extern "C" fn idle_timer_handler() {}
+
+#[test]
+fn ready_object_is_reported_without_waiting() {
+    sim::run(|| {
+        let semaphore = create_semaphore(0).unwrap();
+        let queue = create_queue(4, 8).unwrap();
+        let objects = [
+            SelectObject::Semaphore(semaphore),
+            SelectObject::Queue(queue),
+        ];
+        assert_eq!(
+            wait_any(&objects, 0),
+            Err(SystemError::Select(SelectError::Unavailable))
+        );
+
+        queue_write(queue, &mut [7], 0).unwrap();
+        assert_eq!(wait_any(&objects, 0), Ok(1));
+        semaphore_post(semaphore).unwrap();
+        assert_eq!(wait_any(&objects, 0), Ok(0));
+
+        // 只报告就绪，对象仍由调用者获取
+        semaphore_pend(semaphore, 0).unwrap();
+        let mut buffer = [0u8; 8];
+        assert_eq!(queue_read(queue, &mut buffer, 0).unwrap(), 1);
+        delete_semaphore(semaphore).unwrap();
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn wakes_on_whichever_object_fires() {
+    sim::run(|| {
+        let semaphore = create_semaphore(0).unwrap();
+        let queue = create_queue(4, 8).unwrap();
+        let group = new_group();
+        let objects = [
+            SelectObject::Semaphore(semaphore),
+            SelectObject::Queue(queue),
+            SelectObject::EventGroup(group, 1 << 40),
+        ];
+
+        sim::spawn(c"Writer", 11, move || {
+            task_delay(2).unwrap();
+            queue_write(queue, &mut [1], 0).unwrap();
+        })
+        .unwrap();
+        assert_eq!(wait_any(&objects, 100), Ok(1));
+        let mut buffer = [0u8; 8];
+        queue_read(queue, &mut buffer, 0).unwrap();
+
+        let shared = SharedGroup(group);
+        sim::spawn(c"Writer", 11, move || {
+            task_delay(2).unwrap();
+            // 掩码外的位不会唤醒
+            event_group_set(shared.get(), 0b01);
+            task_delay(2).unwrap();
+            event_group_set(shared.get(), 1 << 40);
+        })
+        .unwrap();
+        assert_eq!(wait_any(&objects, 100), Ok(2));
+
+        delete_semaphore(semaphore).unwrap();
+        delete_queue(queue).unwrap();
+    });
+}
+
+#[test]
+fn timer_expiry_is_reported_once() {
+    sim::run(|| {
+        let periodic = timer_create(3, TimerMode::Periodic, Some(idle_timer_handler)).unwrap();
+        let one_shot = timer_create(2, TimerMode::OneShot, Some(idle_timer_handler)).unwrap();
+        let objects = [SelectObject::Timer(periodic), SelectObject::Timer(one_shot)];
+        timer_start(one_shot).unwrap();
+        assert_eq!(
+            wait_any(&objects, 0),
+            Err(SystemError::Select(SelectError::Unavailable))
+        );
+        // 单次定时器超时后被删除，仍能报告给等待中的任务
+        assert_eq!(wait_any(&objects, 100), Ok(1));
+
+        let objects = [SelectObject::Timer(periodic)];
+        timer_start(periodic).unwrap();
+        assert_eq!(wait_any(&objects, 100), Ok(0));
+        assert_eq!(
+            wait_any(&objects, 0),
+            Err(SystemError::Select(SelectError::Unavailable))
+        );
+        assert_eq!(wait_any(&objects, 100), Ok(0));
+        timer_delete(periodic).unwrap();
+    });
+}
+
+#[test]
+fn wait_times_out_and_rejects_bad_sets() {
+    sim::run(|| {
+        let semaphore = create_semaphore(0).unwrap();
+        assert_eq!(
+            wait_any(&[], 5),
+            Err(SystemError::Select(SelectError::SetInvalid))
+        );
+        assert_eq!(
+            wait_any(&[SelectObject::Semaphore(semaphore)], 5),
+            Err(SystemError::Select(SelectError::Timeout))
+        );
+        delete_semaphore(semaphore).unwrap();
+        assert_eq!(
+            wait_any(&[SelectObject::Semaphore(semaphore)], 5),
+            Err(SystemError::Select(SelectError::ObjectInvalid))
+        );
+    });
+}
+
+#[test]
+fn deleting_waiting_task_unlinks_its_nodes() {
+    sim::run(|| {
+        let semaphore = create_semaphore(0).unwrap();
+        let started = Arc::new(AtomicU32::new(0));
+        let child_started = started.clone();
+        let waiter = sim::spawn(c"Waiter", 5, move || {
+            child_started.store(1, Ordering::SeqCst);
+            let _ = wait_any(&[SelectObject::Semaphore(semaphore)], u32::MAX);
+            child_started.store(2, Ordering::SeqCst);
+        })
+        .unwrap();
+        wait_until(|| started.load(Ordering::SeqCst) == 1);
+        task_delay(2).unwrap();
+        task_delete(waiter).unwrap();
+
+        semaphore_post(semaphore).unwrap();
+        assert_eq!(wait_any(&[SelectObject::Semaphore(semaphore)], 0), Ok(0));
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+        semaphore_pend(semaphore, 0).unwrap();
+        delete_semaphore(semaphore).unwrap();
+    });
+}
+
+#[test]
+fn deleting_waited_object_wakes_waiter() {
+    sim::run(|| {
+        let semaphore = create_semaphore(0).unwrap();
+        let queue = create_queue(4, 8).unwrap();
+        let started = Arc::new(AtomicU32::new(0));
+        let child_started = started.clone();
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Waiter", 5, move || {
+            let objects = [
+                SelectObject::Semaphore(semaphore),
+                SelectObject::Queue(queue),
+            ];
+            child_started.store(1, Ordering::SeqCst);
+            *child_result.lock().unwrap() = Some(wait_any(&objects, u32::MAX));
+        })
+        .unwrap();
+        wait_until(|| started.load(Ordering::SeqCst) == 1);
+        task_delay(2).unwrap();
+        delete_semaphore(semaphore).unwrap();
+        wait_until(|| result.lock().unwrap().is_some());
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Select(SelectError::ObjectDeleted)))
+        );
+
+        // 重新创建的信号量上没有旧的等待节点
+        let semaphore = create_semaphore(0).unwrap();
+        semaphore_post(semaphore).unwrap();
+        queue_write(queue, &mut [1], 0).unwrap();
+        delete_semaphore(semaphore).unwrap();
+        delete_queue(queue).unwrap();
+    });
+}
@@ -0,0 +1,143 @@
+use rust::{
+    result::SystemError,
+    semaphore::{
+        core::{
+            create_semaphore, delete_semaphore, semaphore_owner_policy_set, semaphore_pend,
+            semaphore_post,
+        },
+        error::SemaphoreError,
+    },
+    sim,
+    task::{
+        error::TaskError, manager::delay::task_delay, manager::delete::task_delete,
+        resource::OwnerDeadPolicy,
+    },
+    tick::global::get_current_tick_count,
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+#[test]
+fn counting_pend_and_post() {
+    sim::run(|| {
+        let sem = create_semaphore(2).unwrap();
+        semaphore_pend(sem, 0).unwrap();
+        semaphore_pend(sem, 0).unwrap();
+        assert_eq!(
+            semaphore_pend(sem, 0),
+            Err(SystemError::Semaphore(SemaphoreError::Unavailable))
+        );
+        semaphore_post(sem).unwrap();
+        semaphore_pend(sem, 0).unwrap();
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn pend_times_out() {
+    sim::run(|| {
+        let sem = create_semaphore(0).unwrap();
+        let start = get_current_tick_count();
+        assert_eq!(
+            semaphore_pend(sem, 5),
+            Err(SystemError::Semaphore(SemaphoreError::Timeout))
+        );
+        assert!(get_current_tick_count() - start >= 5);
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn post_wakes_waiter() {
+    sim::run(|| {
+        let sem = create_semaphore(0).unwrap();
+        let woken = Arc::new(AtomicU32::new(0));
+        let child_woken = woken.clone();
+        sim::spawn(c"Waiter", 5, move || {
+            semaphore_pend(sem, u32::MAX).unwrap();
+            child_woken.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert_eq!(woken.load(Ordering::SeqCst), 0);
+        semaphore_post(sem).unwrap();
+        assert_eq!(woken.load(Ordering::SeqCst), 1);
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn delete_fails_with_waiters() {
+    sim::run(|| {
+        let sem = create_semaphore(0).unwrap();
+        sim::spawn(c"Waiter", 5, move || {
+            let _ = semaphore_pend(sem, 10);
+        })
+        .unwrap();
+        assert_eq!(
+            delete_semaphore(sem),
+            Err(SystemError::Semaphore(SemaphoreError::Pended))
+        );
+        semaphore_post(sem).unwrap();
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn deleting_creator_destroys_semaphore() {
+    sim::run(|| {
+        let sem = Arc::new(Mutex::new(None));
+        let child_sem = sem.clone();
+        let creator = sim::spawn(c"Creator", 5, move || {
+            let handle = create_semaphore(0).unwrap();
+            semaphore_owner_policy_set(handle, OwnerDeadPolicy::Destroy).unwrap();
+            *child_sem.lock().unwrap() = Some(handle);
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+        let handle = sem.lock().unwrap().unwrap();
+
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Waiter", 6, move || {
+            *child_result.lock().unwrap() = Some(semaphore_pend(handle, u32::MAX));
+        })
+        .unwrap();
+
+        task_delete(creator).unwrap();
+        assert_eq!(
+            *result.lock().unwrap(),
+            Some(Err(SystemError::Task(TaskError::OwnerDead)))
+        );
+        assert!(delete_semaphore(handle).is_err());
+    });
+}
+
+#[test]
+fn deleting_creator_keeps_semaphore_by_default() {
+    sim::run(|| {
+        let sem = Arc::new(Mutex::new(None));
+        let child_sem = sem.clone();
+        let creator = sim::spawn(c"Creator", 5, move || {
+            *child_sem.lock().unwrap() = Some(create_semaphore(0).unwrap());
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+        let handle = sem.lock().unwrap().unwrap();
+
+        let result = Arc::new(Mutex::new(None));
+        let child_result = result.clone();
+        sim::spawn(c"Waiter", 6, move || {
+            *child_result.lock().unwrap() = Some(semaphore_pend(handle, u32::MAX));
+        })
+        .unwrap();
+
+        // 创建者被删除后信号量和等待者都不受影响
+        task_delete(creator).unwrap();
+        assert_eq!(*result.lock().unwrap(), None);
+        semaphore_post(handle).unwrap();
+        assert_eq!(*result.lock().unwrap(), Some(Ok(())));
+        delete_semaphore(handle).unwrap();
+    });
+}
@@ -0,0 +1,237 @@
+use rust::{
+    condvar::core::{condvar_create, condvar_delete, condvar_signal, condvar_wait},
+    config::TASK_LIMIT,
+    mutex::core::{mutex_create, mutex_delete, mutex_pend, mutex_post},
+    result::SystemError,
+    semaphore::core::{create_semaphore, delete_semaphore, semaphore_pend},
+    sim,
+    task::{
+        error::TaskError,
+        info::get_current_task_id,
+        manager::{delay::task_delay, delete::task_delete, priority::get_task_priority},
+        user_signal::{
+            SIGNAL_NUM, signal_block, signal_handler_set, signal_pending, signal_send,
+            signal_unblock,
+        },
+    },
+    tick::delay_microseconds,
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+/// 每个信号被处理的次数
+static HANDLED: [AtomicU32; SIGNAL_NUM as usize] =
+    [const { AtomicU32::new(0) }; SIGNAL_NUM as usize];
+
+extern "C" fn count_handler(signo: u32) {
+    HANDLED[signo as usize].fetch_add(1, Ordering::SeqCst);
+}
+
+fn handled(signo: u32) -> u32 {
+    HANDLED[signo as usize].load(Ordering::SeqCst)
+}
+
+#[test]
+fn signal_to_self_runs_handler_before_return() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        assert_eq!(signal_handler_set(1, Some(count_handler)), Ok(None));
+        let before = handled(1);
+        signal_send(me, 1).unwrap();
+        assert_eq!(handled(1), before + 1);
+        signal_handler_set(1, None).unwrap();
+    });
+}
+
+#[test]
+fn handler_runs_when_target_next_scheduled() {
+    sim::run(|| {
+        let ready = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (task_ready, task_stop) = (ready.clone(), stop.clone());
+        let before = handled(2);
+        let worker = sim::spawn(c"Sleeper", 5, move || {
+            signal_handler_set(2, Some(count_handler)).unwrap();
+            task_ready.store(true, Ordering::SeqCst);
+            while !task_stop.load(Ordering::SeqCst) {
+                task_delay(10).unwrap();
+            }
+        })
+        .unwrap();
+        assert!(ready.load(Ordering::SeqCst));
+
+        // 延时中的任务不被打断，醒来后执行处理函数
+        signal_send(worker, 2).unwrap();
+        assert_eq!(handled(2), before);
+        task_delay(15).unwrap();
+        assert_eq!(handled(2), before + 1);
+
+        stop.store(true, Ordering::SeqCst);
+        task_delay(15).unwrap();
+    });
+}
+
+#[test]
+fn busy_target_handles_signal_on_interrupt_return() {
+    sim::run(|| {
+        let ready = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (task_ready, task_stop) = (ready.clone(), stop.clone());
+        let before = handled(6);
+        // 目标任务只在忙等中响应中断，不调用其他内核接口
+        let worker = sim::spawn(c"Busy", 15, move || {
+            signal_handler_set(6, Some(count_handler)).unwrap();
+            task_ready.store(true, Ordering::SeqCst);
+            while !task_stop.load(Ordering::SeqCst) {
+                delay_microseconds(100);
+            }
+        })
+        .unwrap();
+        task_delay(2).unwrap();
+        assert!(ready.load(Ordering::SeqCst));
+
+        signal_send(worker, 6).unwrap();
+        task_delay(5).unwrap();
+        assert_eq!(handled(6), before + 1);
+
+        stop.store(true, Ordering::SeqCst);
+        task_delay(2).unwrap();
+    });
+}
+
+#[test]
+fn blocked_signal_stays_pending() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        signal_handler_set(3, Some(count_handler)).unwrap();
+        assert_eq!(signal_block(1 << 3), Ok(0));
+        let before = handled(3);
+
+        signal_send(me, 3).unwrap();
+        signal_send(me, 3).unwrap();
+        assert_eq!(handled(3), before);
+        assert_eq!(signal_pending(), Ok(1 << 3));
+
+        // 同一信号的多次发送合并为一次
+        assert_eq!(signal_unblock(1 << 3), Ok(1 << 3));
+        assert_eq!(handled(3), before + 1);
+        assert_eq!(signal_pending(), Ok(0));
+        signal_handler_set(3, None).unwrap();
+    });
+}
+
+#[test]
+fn signal_interrupts_semaphore_wait() {
+    sim::run(|| {
+        let sem = create_semaphore(0).unwrap();
+        let result = Arc::new(Mutex::new(None));
+        let task_result = result.clone();
+        let before = handled(4);
+        let worker = sim::spawn(c"Waiter", 5, move || {
+            signal_handler_set(4, Some(count_handler)).unwrap();
+            let ret = semaphore_pend(sem, u32::MAX);
+            *task_result.lock().unwrap() = Some((ret, handled(4)));
+        })
+        .unwrap();
+
+        signal_send(worker, 4).unwrap();
+        let (ret, count) = result.lock().unwrap().take().unwrap();
+        assert_eq!(ret, Err(SystemError::Task(TaskError::Interrupted)));
+        assert_eq!(count, before + 1);
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn interrupted_mutex_wait_drops_inheritance() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+
+        let result = Arc::new(Mutex::new(None));
+        let task_result = result.clone();
+        let worker = sim::spawn(c"Waiter", 5, move || {
+            signal_handler_set(5, Some(count_handler)).unwrap();
+            *task_result.lock().unwrap() = Some(mutex_pend(mutex, u32::MAX));
+        })
+        .unwrap();
+        assert_eq!(get_task_priority(me), Ok(5));
+
+        // 当前任务继承了等待者的优先级，让出CPU使被打断的等待者返回
+        signal_send(worker, 5).unwrap();
+        task_delay(1).unwrap();
+        assert_eq!(
+            result.lock().unwrap().take(),
+            Some(Err(SystemError::Task(TaskError::Interrupted)))
+        );
+        assert_eq!(get_task_priority(me), Ok(sim::SIM_TEST_TASK_PRIORITY));
+
+        mutex_post(mutex).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn interrupted_condvar_relock_keeps_relocking() {
+    sim::run(|| {
+        let condvar = condvar_create().unwrap();
+        let mutex = mutex_create().unwrap();
+        let result = Arc::new(Mutex::new(None));
+        let task_result = result.clone();
+        let before = handled(6);
+        let worker = sim::spawn(c"Waiter", 5, move || {
+            signal_handler_set(6, Some(count_handler)).unwrap();
+            mutex_pend(mutex, u32::MAX).unwrap();
+            let waited = condvar_wait(condvar, mutex);
+            *task_result.lock().unwrap() = Some((waited, mutex_post(mutex)));
+        })
+        .unwrap();
+
+        // 唤醒等待者时持有互斥锁，等待者在重新加锁时阻塞
+        mutex_pend(mutex, 0).unwrap();
+        condvar_signal(condvar).unwrap();
+        signal_send(worker, 6).unwrap();
+        task_delay(1).unwrap();
+        assert_eq!(handled(6), before + 1);
+        assert_eq!(*result.lock().unwrap(), None);
+
+        // 被打断后继续加锁，返回时持有互斥锁
+        mutex_post(mutex).unwrap();
+        assert_eq!(result.lock().unwrap().take(), Some((Ok(()), Ok(()))));
+        condvar_delete(condvar).unwrap();
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn signal_errors() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        assert_eq!(
+            signal_send(TASK_LIMIT, 0),
+            Err(SystemError::Task(TaskError::InvalidId))
+        );
+        assert_eq!(
+            signal_send(me, SIGNAL_NUM),
+            Err(SystemError::Task(TaskError::SignalInvalid))
+        );
+        assert_eq!(
+            signal_handler_set(SIGNAL_NUM, Some(count_handler)),
+            Err(SystemError::Task(TaskError::SignalInvalid))
+        );
+
+        // 没有处理函数的信号被丢弃
+        signal_send(me, 6).unwrap();
+        assert_eq!(signal_pending(), Ok(0));
+
+        let task_id = sim::spawn(c"Victim", 20, || {}).unwrap();
+        task_delete(task_id).unwrap();
+        assert_eq!(
+            signal_send(task_id, 0),
+            Err(SystemError::Task(TaskError::NotCreated))
+        );
+    });
+}
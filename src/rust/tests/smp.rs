@@ -0,0 +1,257 @@
+use rust::{
+    config::{KERNEL_CORE_NUM, KERNEL_CPU_MASK},
+    ffi::bindings::arch_curr_cpuid,
+    result::SystemError,
+    semaphore::core::{create_semaphore, delete_semaphore, semaphore_pend, semaphore_post},
+    sim,
+    task::{
+        error::TaskError,
+        global::get_tcb_from_id,
+        manager::{
+            affinity::{task_cpu_affi_get, task_cpu_affi_set},
+            delay::task_delay,
+            delete::task_delete,
+            suspend::{task_resume, task_suspend},
+        },
+        types::TaskStatus,
+    },
+    tick::clock::get_tick_count,
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+const NO_CPU: u32 = u32::MAX;
+
+fn run<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    sim::set_cpu_count(KERNEL_CORE_NUM);
+    sim::run(f);
+}
+
+/// 忙等直到条件成立，期间的内核调用为其他核的核间中断提供响应时机
+fn spin_until(mut cond: impl FnMut() -> bool) {
+    let deadline = get_tick_count() + 1000;
+    while !cond() {
+        assert!(get_tick_count() < deadline, "condition not met in time");
+    }
+}
+
+/// 不让出CPU地等待指定的Tick数
+fn task_delay_busy(ticks: u64) {
+    let deadline = get_tick_count() + ticks;
+    spin_until(|| get_tick_count() >= deadline);
+}
+
+/// 持续运行直到被停止的任务
+struct Busy {
+    task_id: u32,
+    cpu: Arc<AtomicU32>,
+    count: Arc<AtomicU32>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Busy {
+    fn spawn(priority: u16) -> Self {
+        let cpu = Arc::new(AtomicU32::new(NO_CPU));
+        let count = Arc::new(AtomicU32::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (task_cpu, task_count, task_stop) = (cpu.clone(), count.clone(), stop.clone());
+        let task_id = sim::spawn(c"Busy", priority, move || {
+            task_cpu.store(arch_curr_cpuid(), Ordering::SeqCst);
+            while !task_stop.load(Ordering::SeqCst) {
+                task_count.fetch_add(1, Ordering::SeqCst);
+                task_cpu.store(arch_curr_cpuid(), Ordering::SeqCst);
+                get_tick_count();
+            }
+        })
+        .unwrap();
+        Self {
+            task_id,
+            cpu,
+            count,
+            stop,
+        }
+    }
+
+    fn started(&self) -> bool {
+        self.cpu.load(Ordering::SeqCst) != NO_CPU
+    }
+
+    fn cpu(&self) -> u32 {
+        self.cpu.load(Ordering::SeqCst)
+    }
+
+    fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        spin_until(|| get_tcb_from_id(self.task_id).is_unused());
+    }
+}
+
+#[test]
+fn tasks_spread_across_idle_cores() {
+    run(|| {
+        let me = arch_curr_cpuid();
+        let workers: Vec<_> = (0..KERNEL_CORE_NUM - 1).map(|_| Busy::spawn(20)).collect();
+        spin_until(|| workers.iter().all(Busy::started));
+
+        // 低优先级任务在空闲核上与当前任务并行运行
+        let mut cpus: Vec<_> = workers.iter().map(Busy::cpu).collect();
+        cpus.sort();
+        cpus.dedup();
+        assert_eq!(cpus.len(), KERNEL_CORE_NUM - 1);
+        assert!(!cpus.contains(&me));
+
+        workers.into_iter().for_each(Busy::stop);
+    });
+}
+
+#[test]
+fn wake_runs_on_idle_core() {
+    run(|| {
+        let sem = create_semaphore(0).unwrap();
+        let woken = Arc::new(AtomicU32::new(NO_CPU));
+        let task_woken = woken.clone();
+        sim::spawn(c"Waiter", 20, move || {
+            semaphore_pend(sem, u32::MAX).unwrap();
+            task_woken.store(arch_curr_cpuid(), Ordering::SeqCst);
+        })
+        .unwrap();
+        task_delay(2).unwrap();
+
+        // 被唤醒的低优先级任务无需等待当前任务让出CPU
+        semaphore_post(sem).unwrap();
+        spin_until(|| woken.load(Ordering::SeqCst) != NO_CPU);
+        assert_ne!(woken.load(Ordering::SeqCst), arch_curr_cpuid());
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn higher_priority_task_preempts_remote_core() {
+    run(|| {
+        let me = arch_curr_cpuid();
+        let workers: Vec<_> = (0..KERNEL_CORE_NUM - 1).map(|_| Busy::spawn(25)).collect();
+        spin_until(|| workers.iter().all(Busy::started));
+
+        // 所有核都在忙，新任务抢占运行最低优先级任务的核
+        let ran = Arc::new(AtomicU32::new(NO_CPU));
+        let task_ran = ran.clone();
+        sim::spawn(c"Urgent", 15, move || {
+            task_ran.store(arch_curr_cpuid(), Ordering::SeqCst);
+        })
+        .unwrap();
+        spin_until(|| ran.load(Ordering::SeqCst) != NO_CPU);
+        assert_ne!(ran.load(Ordering::SeqCst), me);
+
+        workers.into_iter().for_each(Busy::stop);
+    });
+}
+
+#[test]
+fn idle_core_takes_waiting_task() {
+    run(|| {
+        let mut workers: Vec<_> = (0..KERNEL_CORE_NUM - 1).map(|_| Busy::spawn(20)).collect();
+        spin_until(|| workers.iter().all(Busy::started));
+
+        // 其余核都在忙，新任务排在当前核上等待
+        let waiting = Busy::spawn(20);
+        task_delay_busy(5);
+        assert!(!waiting.started());
+
+        // 某个核空闲后从其他核的就绪队列取走等待的任务
+        let finished = workers.pop().unwrap();
+        let freed_cpu = finished.cpu();
+        finished.stop();
+        spin_until(|| waiting.started());
+        assert_eq!(waiting.cpu(), freed_cpu);
+
+        waiting.stop();
+        workers.into_iter().for_each(Busy::stop);
+    });
+}
+
+#[test]
+fn delete_task_running_on_other_core() {
+    run(|| {
+        let worker = Busy::spawn(20);
+        spin_until(|| worker.started());
+        assert_ne!(worker.cpu(), arch_curr_cpuid());
+        assert!(
+            get_tcb_from_id(worker.task_id)
+                .task_status
+                .contains(TaskStatus::RUNNING)
+        );
+
+        task_delete(worker.task_id).unwrap();
+        spin_until(|| get_tcb_from_id(worker.task_id).is_unused());
+        let frozen = worker.count.load(Ordering::SeqCst);
+        task_delay(5).unwrap();
+        assert_eq!(worker.count.load(Ordering::SeqCst), frozen);
+    });
+}
+
+#[test]
+fn suspend_task_running_on_other_core() {
+    run(|| {
+        let worker = Busy::spawn(20);
+        spin_until(|| worker.started());
+        assert_ne!(worker.cpu(), arch_curr_cpuid());
+
+        task_suspend(worker.task_id).unwrap();
+        spin_until(|| {
+            get_tcb_from_id(worker.task_id)
+                .task_status
+                .contains(TaskStatus::SUSPEND)
+        });
+        let frozen = worker.count.load(Ordering::SeqCst);
+        task_delay(5).unwrap();
+        assert_eq!(worker.count.load(Ordering::SeqCst), frozen);
+
+        task_resume(worker.task_id).unwrap();
+        spin_until(|| worker.count.load(Ordering::SeqCst) > frozen);
+        worker.stop();
+    });
+}
+
+#[test]
+fn affinity_mask_validation() {
+    run(|| {
+        let worker = Busy::spawn(20);
+        assert_eq!(task_cpu_affi_get(worker.task_id), Ok(KERNEL_CPU_MASK));
+        assert_eq!(
+            task_cpu_affi_set(worker.task_id, 0),
+            Err(SystemError::Task(TaskError::CpuAffinityMaskError))
+        );
+        assert_eq!(
+            task_cpu_affi_set(worker.task_id, !KERNEL_CPU_MASK),
+            Err(SystemError::Task(TaskError::CpuAffinityMaskError))
+        );
+        task_cpu_affi_set(worker.task_id, 0b10).unwrap();
+        assert_eq!(task_cpu_affi_get(worker.task_id), Ok(0b10));
+        spin_until(|| worker.started());
+        worker.stop();
+    });
+}
+
+#[test]
+fn running_task_migrates_to_allowed_core() {
+    run(|| {
+        let worker = Busy::spawn(20);
+        spin_until(|| worker.started());
+
+        // 把运行中的任务绑定到另一个核，任务迁移后只在该核上运行
+        let target = (0..KERNEL_CORE_NUM as u32)
+            .find(|&cpu| cpu != worker.cpu() && cpu != arch_curr_cpuid())
+            .unwrap();
+        task_cpu_affi_set(worker.task_id, 1 << target).unwrap();
+        spin_until(|| worker.cpu() == target);
+        task_delay(5).unwrap();
+        assert_eq!(worker.cpu(), target);
+
+        worker.stop();
+    });
+}
@@ -0,0 +1,631 @@
+use rust::{
+    config::{OK, TASK_LIMIT, TASK_NAME_LEN},
+    ffi::exports::task::{CTaskInitParam, los_task_create, los_task_create_ex},
+    memory::{free, malloc, mem_owner_policy_set},
+    mutex::core::{mutex_create, mutex_delete, mutex_pend, mutex_post},
+    result::SystemError,
+    semaphore::core::{create_semaphore, delete_semaphore, semaphore_pend, semaphore_post},
+    sim,
+    task::{
+        builder::Task,
+        error::TaskError,
+        global::get_tcb_from_id,
+        info::{get_current_task_id, task_info_get, task_info_iter},
+        manager::{
+            delay::{task_delay, task_yield},
+            delete::task_delete,
+            exit::{task_detach, task_exit, task_join},
+            priority::{get_task_priority, set_task_priority},
+            suspend::{task_resume, task_suspend},
+            timeslice::{
+                priority_time_slice_get, priority_time_slice_set, task_time_slice_get,
+                task_time_slice_set,
+            },
+        },
+        monitor::{
+            TASK_SWITCH_HANDLER_LIMIT, TaskSwitchReason, task_switch_handler_register,
+            task_switch_handler_unregister,
+        },
+        resource::{OwnerDeadPolicy, task_reclaim_report_pop},
+        types::{TASK_TIME_SLICE_DEFAULT, TaskPendObject, TaskStatus},
+    },
+    tick::{clock::get_tick_count, global::get_current_tick_count},
+    timer::{TimerMode, timer_create, timer_owner_policy_set, timer_start},
+};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+};
+
+#[test]
+fn higher_priority_task_preempts_creator() {
+    sim::run(|| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let child_order = order.clone();
+        sim::spawn(c"High", 5, move || {
+            child_order.lock().unwrap().push("child")
+        })
+        .unwrap();
+        order.lock().unwrap().push("parent");
+        assert_eq!(*order.lock().unwrap(), ["child", "parent"]);
+    });
+}
+
+#[test]
+fn lower_priority_task_runs_when_creator_blocks() {
+    sim::run(|| {
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        sim::spawn(c"Low", 20, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        task_delay(2).unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn delay_waits_for_ticks() {
+    sim::run(|| {
+        let start = get_current_tick_count();
+        task_delay(10).unwrap();
+        assert!(get_current_tick_count() - start >= 10);
+    });
+}
+
+#[test]
+fn suspend_and_resume() {
+    sim::run(|| {
+        let counter = Arc::new(AtomicU32::new(0));
+        let child_counter = counter.clone();
+        let child = sim::spawn(c"Worker", 5, move || {
+            loop {
+                child_counter.fetch_add(1, Ordering::SeqCst);
+                task_delay(1).unwrap();
+            }
+        })
+        .unwrap();
+
+        task_delay(5).unwrap();
+        task_suspend(child).unwrap();
+        assert!(
+            get_tcb_from_id(child)
+                .task_status
+                .contains(TaskStatus::SUSPEND)
+        );
+        let frozen = counter.load(Ordering::SeqCst);
+        task_delay(5).unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), frozen);
+
+        task_resume(child).unwrap();
+        task_delay(5).unwrap();
+        assert!(counter.load(Ordering::SeqCst) > frozen);
+        task_delete(child).unwrap();
+    });
+}
+
+#[test]
+fn yield_round_robins_equal_priority() {
+    sim::run(|| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let child_order = order.clone();
+        let prio = get_task_priority(get_current_task_id()).unwrap();
+        sim::spawn(c"Peer", prio, move || {
+            child_order.lock().unwrap().push("peer")
+        })
+        .unwrap();
+        order.lock().unwrap().push("self");
+        task_yield().unwrap();
+        order.lock().unwrap().push("self again");
+        assert_eq!(*order.lock().unwrap(), ["self", "peer", "self again"]);
+    });
+}
+
+#[test]
+fn raising_priority_preempts() {
+    sim::run(|| {
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        let child = sim::spawn(c"Low", 20, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        set_task_priority(child, 5).unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn deleted_task_never_runs() {
+    sim::run(|| {
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        let child = sim::spawn(c"Victim", 20, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        task_delete(child).unwrap();
+        task_delay(5).unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+    });
+}
+
+#[test]
+fn join_returns_exit_code() {
+    sim::run(|| {
+        let child = sim::spawn_joinable(c"Worker", 20, || task_exit(7)).unwrap();
+        assert_eq!(task_join(child, u32::MAX), Ok(7));
+
+        // 等待者回收后控制块可被复用
+        task_delay(2).unwrap();
+        assert!(get_tcb_from_id(child).is_unused());
+    });
+}
+
+#[test]
+fn exited_joinable_task_is_kept_until_joined() {
+    sim::run(|| {
+        let child = sim::spawn_joinable(c"Worker", 5, || {}).unwrap();
+
+        // 空闲任务释放栈后仍保留控制块
+        task_delay(5).unwrap();
+        let task_cb = get_tcb_from_id(child);
+        assert!(task_cb.task_status.contains(TaskStatus::EXIT));
+        assert!(!task_cb.is_unused());
+
+        assert_eq!(task_join(child, 0), Ok(0));
+        assert!(get_tcb_from_id(child).is_unused());
+    });
+}
+
+#[test]
+fn delete_wakes_joiner() {
+    sim::run(|| {
+        let child = sim::spawn_joinable(c"Victim", 20, || {
+            loop {
+                task_delay(1).unwrap();
+            }
+        })
+        .unwrap();
+        sim::spawn(c"Killer", 25, move || task_delete(child).unwrap()).unwrap();
+
+        assert_eq!(task_join(child, u32::MAX), Ok(0));
+    });
+}
+
+#[test]
+fn join_errors() {
+    sim::run(|| {
+        let detached = sim::spawn(c"Detached", 20, || {}).unwrap();
+        assert_eq!(
+            task_join(detached, u32::MAX),
+            Err(SystemError::Task(TaskError::NotJoinable))
+        );
+        assert_eq!(
+            task_join(get_current_task_id(), u32::MAX),
+            Err(SystemError::Task(TaskError::NotJoinable))
+        );
+
+        let child = sim::spawn_joinable(c"Worker", 20, || {
+            loop {
+                task_delay(1).unwrap();
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            task_join(child, 3),
+            Err(SystemError::Task(TaskError::JoinTimeout))
+        );
+
+        // 分离后的任务被删除时直接回收
+        task_detach(child).unwrap();
+        assert_eq!(
+            task_join(child, 3),
+            Err(SystemError::Task(TaskError::NotJoinable))
+        );
+        task_delete(child).unwrap();
+        task_delay(2).unwrap();
+        assert!(get_tcb_from_id(child).is_unused());
+    });
+}
+
+/// 不让出CPU地等待指定的Tick数，期间照常响应Tick中断
+fn busy_wait(ticks: u64) {
+    let deadline = get_tick_count() + ticks;
+    while get_tick_count() < deadline {}
+}
+
+#[test]
+fn time_slice_expiry_rotates_equal_priority() {
+    sim::run(|| {
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        let prio = get_task_priority(get_current_task_id()).unwrap();
+        sim::spawn(c"Peer", prio, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        busy_wait(10);
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn time_slice_rotates_to_late_peer() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        task_time_slice_set(me, 5).unwrap();
+        // 同优先级没有其他任务时时间片用完，重新获得完整的时间片后继续运行
+        busy_wait(12);
+
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        let prio = get_task_priority(me).unwrap();
+        sim::spawn(c"Peer", prio, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        busy_wait(6);
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn zero_time_slice_runs_fifo() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        task_time_slice_set(me, 0).unwrap();
+        let ran = Arc::new(AtomicU32::new(0));
+        let child_ran = ran.clone();
+        let prio = get_task_priority(me).unwrap();
+        sim::spawn(c"Peer", prio, move || {
+            child_ran.store(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        busy_wait(10);
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        task_yield().unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    });
+}
+
+extern "C" fn idle_entry(_args: *mut core::ffi::c_void) {}
+
+#[test]
+fn c_create_ex_sets_time_slice() {
+    sim::run(|| {
+        let prio = get_task_priority(get_current_task_id()).unwrap() + 5;
+        let mut param = CTaskInitParam {
+            task_entry: Some(idle_entry),
+            priority: prio,
+            args: core::ptr::null_mut(),
+            stack_size: 0,
+            name: c"CSlice".as_ptr(),
+            resved: 0,
+        };
+        let mut task_id = 0;
+        assert_eq!(los_task_create_ex(&mut task_id, &mut param, 3), OK);
+        assert_eq!(task_time_slice_get(task_id).unwrap(), 3);
+        task_delete(task_id).unwrap();
+
+        // 0表示先进先出
+        assert_eq!(los_task_create_ex(&mut task_id, &mut param, 0), OK);
+        assert_eq!(task_time_slice_get(task_id).unwrap(), 0);
+        task_delete(task_id).unwrap();
+
+        // 不指定时使用优先级的默认值
+        assert_eq!(los_task_create(&mut task_id, &mut param), OK);
+        assert_eq!(
+            task_time_slice_get(task_id).unwrap(),
+            priority_time_slice_get(prio).unwrap()
+        );
+        task_delete(task_id).unwrap();
+    });
+}
+
+#[test]
+fn time_slice_set_and_get() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let prio = get_task_priority(me).unwrap();
+        let default = priority_time_slice_get(prio).unwrap();
+        assert_eq!(task_time_slice_get(me).unwrap(), default);
+
+        task_time_slice_set(me, 5).unwrap();
+        assert_eq!(task_time_slice_get(me).unwrap(), 5);
+
+        // 恢复为优先级默认值后跟随优先级的设置
+        task_time_slice_set(me, TASK_TIME_SLICE_DEFAULT).unwrap();
+        priority_time_slice_set(prio, 7).unwrap();
+        assert_eq!(task_time_slice_get(me).unwrap(), 7);
+        priority_time_slice_set(prio, default).unwrap();
+
+        assert_eq!(
+            priority_time_slice_set(prio, TASK_TIME_SLICE_DEFAULT),
+            Err(SystemError::Task(TaskError::TimeSliceError))
+        );
+        assert_eq!(
+            priority_time_slice_get(u16::MAX),
+            Err(SystemError::Task(TaskError::PriorityError))
+        );
+        assert_eq!(
+            task_time_slice_set(u32::MAX, 1),
+            Err(SystemError::Task(TaskError::InvalidId))
+        );
+    });
+}
+
+#[test]
+fn builder_spawns_closure_with_owned_name() {
+    sim::run(|| {
+        let ran = Arc::new(AtomicU32::new(0));
+        let task_ran = ran.clone();
+        let name = String::from("net");
+        let task = Task::builder()
+            .name(&name)
+            .priority(5)
+            .stack(8192)
+            .joinable()
+            .spawn(move || {
+                task_ran.store(get_current_task_id(), Ordering::SeqCst);
+                task_exit(7);
+            })
+            .unwrap();
+        drop(name);
+
+        // 名称已复制到控制块中，不依赖调用者的字符串
+        assert_eq!(get_tcb_from_id(task.id()).name(), "net");
+        assert_eq!(ran.load(Ordering::SeqCst), task.id());
+        assert_eq!(task.join(0), Ok(7));
+    });
+}
+
+#[test]
+fn builder_truncates_long_name() {
+    sim::run(|| {
+        let name = "任务".repeat(TASK_NAME_LEN);
+        let task = Task::builder()
+            .name(&name)
+            .priority(20)
+            .spawn(|| {})
+            .unwrap();
+        let copied = get_tcb_from_id(task.id()).name();
+        assert!(copied.len() < TASK_NAME_LEN);
+        assert!(name.starts_with(copied));
+        task.delete().unwrap();
+    });
+}
+
+#[test]
+fn builder_errors() {
+    sim::run(|| {
+        assert_eq!(
+            Task::builder().spawn(|| {}),
+            Err(SystemError::Task(TaskError::NameEmpty))
+        );
+        assert_eq!(
+            Task::builder().name("Bad").priority(u16::MAX).spawn(|| {}),
+            Err(SystemError::Task(TaskError::PriorityError))
+        );
+    });
+}
+
+/// 任务切换记录，按(换出任务, 换入任务, 原因)打包，切换处理函数中不能加锁
+static SWITCHES: [AtomicU64; 256] = [const { AtomicU64::new(0) }; 256];
+static SWITCH_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn record_switch(old_task_id: u32, new_task_id: u32, reason: TaskSwitchReason) {
+    let index = SWITCH_COUNT.fetch_add(1, Ordering::SeqCst);
+    if let Some(slot) = SWITCHES.get(index) {
+        let record = (old_task_id as u64) << 40 | (new_task_id as u64) << 8 | reason as u64;
+        slot.store(record, Ordering::SeqCst);
+    }
+}
+
+extern "C" fn ignore_switch(_: u32, _: u32, _: TaskSwitchReason) {}
+
+/// 与指定任务有关的切换记录
+fn switches_of(task_id: u32) -> Vec<(u32, u32, u64)> {
+    let count = SWITCH_COUNT.load(Ordering::SeqCst).min(SWITCHES.len());
+    SWITCHES[..count]
+        .iter()
+        .map(|slot| {
+            let record = slot.load(Ordering::SeqCst);
+            ((record >> 40) as u32, (record >> 8) as u32, record & 0xff)
+        })
+        .filter(|&(old, new, _)| old == task_id || new == task_id)
+        .collect()
+}
+
+#[test]
+fn switch_handlers_see_tasks_and_reason() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        SWITCH_COUNT.store(0, Ordering::SeqCst);
+        task_switch_handler_register(record_switch).unwrap();
+
+        // 抢占当前任务，延时阻塞，随后退出
+        let high = sim::spawn(c"High", 5, || task_delay(2).unwrap()).unwrap();
+        task_delay(5).unwrap();
+        let high_switches = switches_of(high);
+        assert_eq!(
+            high_switches[0],
+            (me, high, TaskSwitchReason::Preempt as u64)
+        );
+        assert_eq!(high_switches[1], (high, me, TaskSwitchReason::Block as u64));
+        assert_eq!(
+            high_switches.last().map(|&(old, _, reason)| (old, reason)),
+            Some((high, TaskSwitchReason::Delete as u64))
+        );
+
+        // 同优先级任务之间主动让出CPU，关闭时间片以免被轮转打断
+        task_time_slice_set(me, 0).unwrap();
+        SWITCH_COUNT.store(0, Ordering::SeqCst);
+        let peer = sim::spawn(c"Peer", sim::SIM_TEST_TASK_PRIORITY, || {}).unwrap();
+        task_yield().unwrap();
+        task_time_slice_set(me, TASK_TIME_SLICE_DEFAULT).unwrap();
+        assert_eq!(
+            switches_of(peer)[0],
+            (me, peer, TaskSwitchReason::Yield as u64)
+        );
+
+        // 注销后不再被调用
+        task_switch_handler_unregister(record_switch).unwrap();
+        let count = SWITCH_COUNT.load(Ordering::SeqCst);
+        task_delay(2).unwrap();
+        assert_eq!(SWITCH_COUNT.load(Ordering::SeqCst), count);
+    });
+}
+
+#[test]
+fn switch_handler_registry_limits() {
+    sim::run(|| {
+        assert_eq!(
+            task_switch_handler_unregister(ignore_switch),
+            Err(SystemError::Task(TaskError::SwitchHandlerNotFound))
+        );
+        for _ in 0..TASK_SWITCH_HANDLER_LIMIT {
+            task_switch_handler_register(ignore_switch).unwrap();
+        }
+        assert_eq!(
+            task_switch_handler_register(ignore_switch),
+            Err(SystemError::Task(TaskError::SwitchHandlerFull))
+        );
+        for _ in 0..TASK_SWITCH_HANDLER_LIMIT {
+            task_switch_handler_unregister(ignore_switch).unwrap();
+        }
+        assert_eq!(
+            task_switch_handler_unregister(ignore_switch),
+            Err(SystemError::Task(TaskError::SwitchHandlerNotFound))
+        );
+    });
+}
+
+#[test]
+fn task_info_reports_waits_and_delays() {
+    sim::run(|| {
+        let sem = create_semaphore(0).unwrap();
+        let waiter = sim::spawn(c"InfoWaiter", 5, move || {
+            semaphore_pend(sem, 100).unwrap();
+        })
+        .unwrap();
+        let sleeper = sim::spawn(c"InfoSleeper", 5, || task_delay(50).unwrap()).unwrap();
+
+        let info = task_info_get(waiter).unwrap();
+        assert_eq!(info.task_id, waiter);
+        assert_eq!(info.name(), "InfoWaiter");
+        assert!(
+            info.status
+                .contains(TaskStatus::PEND | TaskStatus::PEND_TIME)
+        );
+        assert_eq!(info.pend_object, TaskPendObject::Semaphore(sem.into()));
+        assert!(info.delay_ticks > 0 && info.delay_ticks <= 100);
+        // 仿真任务运行在宿主线程上，栈只被填充而不被使用
+        assert!(info.stack_size > 0 && info.stack_waterline <= info.stack_size);
+
+        let info = task_info_get(sleeper).unwrap();
+        assert!(info.status.contains(TaskStatus::DELAY));
+        assert_eq!(info.pend_object, TaskPendObject::None);
+        assert!(info.delay_ticks > 0 && info.delay_ticks <= 50);
+
+        // 唤醒后不再报告等待对象
+        semaphore_post(sem).unwrap();
+        assert_eq!(
+            task_info_get(waiter),
+            Err(SystemError::Task(TaskError::NotCreated))
+        );
+        task_delay(50).unwrap();
+        delete_semaphore(sem).unwrap();
+    });
+}
+
+#[test]
+fn task_info_reports_inherited_priority() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let mutex = mutex_create().unwrap();
+        mutex_pend(mutex, 0).unwrap();
+        let waiter = sim::spawn(c"InfoMutex", 5, move || {
+            mutex_pend(mutex, u32::MAX).unwrap();
+            mutex_post(mutex).unwrap();
+        })
+        .unwrap();
+
+        let info = task_info_get(me).unwrap();
+        assert!(info.status.contains(TaskStatus::RUNNING));
+        assert_eq!(info.priority, 5);
+        assert_eq!(info.base_priority, sim::SIM_TEST_TASK_PRIORITY);
+        assert_eq!(
+            task_info_get(waiter).unwrap().pend_object,
+            TaskPendObject::Mutex(mutex.into())
+        );
+
+        mutex_post(mutex).unwrap();
+        let info = task_info_get(me).unwrap();
+        assert_eq!(info.priority, sim::SIM_TEST_TASK_PRIORITY);
+        assert_eq!(info.base_priority, sim::SIM_TEST_TASK_PRIORITY);
+        mutex_delete(mutex).unwrap();
+    });
+}
+
+#[test]
+fn task_info_iter_lists_created_tasks() {
+    sim::run(|| {
+        let me = get_current_task_id();
+        let task_id = sim::spawn(c"InfoIdle", 20, || {}).unwrap();
+
+        let infos: Vec<_> = task_info_iter().collect();
+        assert!(infos.windows(2).all(|w| w[0].task_id < w[1].task_id));
+        assert!(infos.iter().any(|info| info.task_id == me));
+        let info = infos.iter().find(|info| info.task_id == task_id).unwrap();
+        assert_eq!(info.name(), "InfoIdle");
+        assert!(info.status.contains(TaskStatus::READY));
+        assert_eq!(info.priority, 20);
+        assert_eq!(info.base_priority, 20);
+        assert_eq!(task_info_get(task_id).as_ref(), Ok(info));
+
+        assert_eq!(
+            task_info_get(TASK_LIMIT),
+            Err(SystemError::Task(TaskError::InvalidId))
+        );
+        task_delete(task_id).unwrap();
+        assert!(task_info_iter().all(|info| info.task_id != task_id));
+    });
+}
+
+extern "C" fn idle_timer_handler() {}
+
+#[test]
+fn deleted_task_reclaims_objects_by_policy() {
+    sim::run(|| {
+        while task_reclaim_report_pop().is_some() {}
+
+        let kept = Arc::new(AtomicUsize::new(0));
+        let child_kept = kept.clone();
+        let task_id = sim::spawn(c"Owner", 5, move || {
+            let doomed = malloc(64);
+            mem_owner_policy_set(doomed, OwnerDeadPolicy::Destroy).unwrap();
+            child_kept.store(malloc(64) as usize, Ordering::SeqCst);
+
+            let timer = timer_create(100, TimerMode::Periodic, Some(idle_timer_handler)).unwrap();
+            timer_owner_policy_set(timer, OwnerDeadPolicy::Destroy).unwrap();
+            timer_start(timer).unwrap();
+            task_delay(1000).unwrap();
+        })
+        .unwrap();
+
+        task_delete(task_id).unwrap();
+        let report = task_reclaim_report_pop().unwrap();
+        assert_eq!(report.task_id, task_id);
+        assert_eq!(report.mem_blocks, 1);
+        assert_eq!(report.timers, 1);
+        assert_eq!(report.mutexes, 0);
+
+        // 默认策略的内存块不随任务释放
+        free(kept.load(Ordering::SeqCst) as *mut _);
+    });
+}
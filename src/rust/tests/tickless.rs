@@ -0,0 +1,103 @@
+use rust::{
+    sim::{self, post_host_request},
+    task::manager::delay::task_delay,
+    tick::{
+        clock::{get_cpu_cycles, get_cycles_per_tick, get_tick_count},
+        delay_microseconds,
+        global::get_current_tick_count,
+        tickless::{tickless_disable, tickless_enable},
+    },
+    timer::{TimerMode, timer_create, timer_delete, timer_start, timer_stop},
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+static PERIODIC_FIRED: AtomicU32 = AtomicU32::new(0);
+
+extern "C" fn periodic_handler() {
+    PERIODIC_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn idle_skips_periodic_ticks() {
+    sim::run(|| {
+        let irqs = sim::tick_interrupt_count(0);
+        let start = get_tick_count();
+        let wall = Instant::now();
+        task_delay(100).unwrap();
+        let ticks = get_tick_count() - start;
+        let fired = sim::tick_interrupt_count(0) - irqs;
+        assert!((100..=103).contains(&ticks), "advanced {ticks} ticks");
+        assert!(wall.elapsed() >= Duration::from_millis(95));
+        assert!(fired < 20, "{fired} tick interrupts while idle");
+    });
+}
+
+#[test]
+fn disabled_tickless_keeps_periodic_ticks() {
+    sim::run(|| {
+        tickless_disable();
+        let irqs = sim::tick_interrupt_count(0);
+        task_delay(50).unwrap();
+        let fired = sim::tick_interrupt_count(0) - irqs;
+        tickless_enable();
+        assert!(fired >= 45, "{fired} tick interrupts while idle");
+    });
+}
+
+#[test]
+fn other_interrupt_catches_up_ticks() {
+    sim::run(|| {
+        let seen = Arc::new(AtomicU64::new(0));
+        let host_seen = seen.clone();
+        let start = get_tick_count();
+        // 宿主请求以中断的形式在休眠中途唤醒CPU
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(30));
+            post_host_request(Box::new(move || {
+                host_seen.store(get_current_tick_count(), Ordering::SeqCst);
+            }));
+        });
+        task_delay(100).unwrap();
+        let seen = seen.load(Ordering::SeqCst) - start;
+        assert!((28..=40).contains(&seen), "interrupt saw {seen} ticks");
+        assert!(get_tick_count() - start >= 100);
+    });
+}
+
+#[test]
+fn software_timer_fires_while_idle() {
+    sim::run(|| {
+        let timer = timer_create(20, TimerMode::Periodic, Some(periodic_handler)).unwrap();
+        timer_start(timer).unwrap();
+        task_delay(105).unwrap();
+        timer_stop(timer).unwrap();
+        let fired = PERIODIC_FIRED.load(Ordering::SeqCst);
+        assert!((4..=5).contains(&fired), "fired {fired} times");
+        timer_delete(timer).unwrap();
+    });
+}
+
+#[test]
+fn repeated_sleeps_keep_tick_phase() {
+    sim::run(|| {
+        let cycles_per_tick = get_cycles_per_tick() as u64;
+        // 每次休眠都从Tick中途开始，唤醒时刻相对周期计数的相位应保持不变。
+        // 宿主线程的调度抖动会让部分唤醒偏离，所以只要求至少一半的唤醒落在同一个四分之一Tick内
+        let mut bins = [0u32; 8];
+        for _ in 0..80 {
+            delay_microseconds(300);
+            task_delay(3).unwrap();
+            let phase = get_cpu_cycles() % cycles_per_tick;
+            bins[(phase * 8 / cycles_per_tick) as usize] += 1;
+        }
+        let aligned = (0..8).map(|i| bins[i] + bins[(i + 1) % 8]).max().unwrap();
+        assert!(aligned >= 40, "wakeup phases spread over {bins:?}");
+    });
+}
@@ -0,0 +1,60 @@
+use rust::{
+    sim,
+    task::manager::delay::task_delay,
+    timer::{TimerMode, timer_create, timer_delete, timer_start, timer_stop},
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static ONE_SHOT_FIRED: AtomicU32 = AtomicU32::new(0);
+static PERIODIC_FIRED: AtomicU32 = AtomicU32::new(0);
+static STOPPED_FIRED: AtomicU32 = AtomicU32::new(0);
+
+extern "C" fn one_shot_handler() {
+    ONE_SHOT_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+extern "C" fn periodic_handler() {
+    PERIODIC_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+extern "C" fn stopped_handler() {
+    STOPPED_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn one_shot_fires_once() {
+    sim::run(|| {
+        let timer = timer_create(3, TimerMode::NoSelfDelete, Some(one_shot_handler)).unwrap();
+        timer_start(timer).unwrap();
+        task_delay(20).unwrap();
+        assert_eq!(ONE_SHOT_FIRED.load(Ordering::SeqCst), 1);
+        timer_delete(timer).unwrap();
+    });
+}
+
+#[test]
+fn periodic_fires_repeatedly() {
+    sim::run(|| {
+        let timer = timer_create(2, TimerMode::Periodic, Some(periodic_handler)).unwrap();
+        timer_start(timer).unwrap();
+        task_delay(21).unwrap();
+        timer_stop(timer).unwrap();
+        let fired = PERIODIC_FIRED.load(Ordering::SeqCst);
+        assert!((9..=11).contains(&fired), "fired {fired} times");
+        task_delay(10).unwrap();
+        assert_eq!(PERIODIC_FIRED.load(Ordering::SeqCst), fired);
+        timer_delete(timer).unwrap();
+    });
+}
+
+#[test]
+fn stopped_timer_does_not_fire() {
+    sim::run(|| {
+        let timer = timer_create(5, TimerMode::OneShot, Some(stopped_handler)).unwrap();
+        timer_start(timer).unwrap();
+        timer_stop(timer).unwrap();
+        task_delay(10).unwrap();
+        assert_eq!(STOPPED_FIRED.load(Ordering::SeqCst), 0);
+        timer_delete(timer).unwrap();
+    });
+}